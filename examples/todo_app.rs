@@ -1,5 +1,6 @@
 use rust_native::*;  // This will now include Component
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 #[derive(Clone, Default)]
 struct Todo {
@@ -10,15 +11,21 @@ struct Todo {
 pub struct TodoApp {
     todos: Arc<Mutex<Vec<Todo>>>,
     input: String,
+    // The window's size, which the root view is laid out to fill.
+    size: Size,
     view: Option<View>,
+    // When the last frame's tick was sent.
+    last_frame: Instant,
 }
 
 impl TodoApp {
-    pub fn new() -> Self {
+    pub fn new(size: Size) -> Self {
         let mut app = Self {
             todos: Arc::new(Mutex::new(Vec::new())),
             input: String::new(),
+            size,
             view: None,
+            last_frame: Instant::now(),
         };
         app.rebuild_ui();
         app
@@ -37,17 +44,17 @@ impl TodoApp {
             
             #[cfg(target_os = "ios")]
             {
-                style.padding = 20.0; // Account for iOS safe area
+                style.padding = EdgeInsets::all(20.0); // Account for iOS safe area
             }
             
             #[cfg(target_os = "android")]
             {
-                style.padding = 16.0; // Material Design spacing
+                style.padding = EdgeInsets::all(16.0); // Material Design spacing
             }
 
             #[cfg(target_arch = "wasm32")]
             {
-                style.padding = 12.0; // Web spacing
+                style.padding = EdgeInsets::all(12.0); // Web spacing
             }
         }
 
@@ -69,7 +76,7 @@ impl TodoApp {
         let mut add_button = Button::new("Add Todo");
         {
             let style = add_button.style_mut();
-            style.padding = 10.0;
+            style.padding = EdgeInsets::all(10.0);
             style.background = Color::rgb(0.2, 0.6, 1.0);
         }
 
@@ -81,8 +88,12 @@ impl TodoApp {
         });
 
         main_view = main_view.child(add_button);
-        main_view = main_view.child(self.build_todo_list());
+        // The list takes the remaining height and scrolls once it is longer.
+        let list = ScrollView::new(self.build_todo_list())
+            .with_style(Style::default().set_flex_grow(1.0).set_flex_basis(Dimension::Points(0.0)));
+        main_view = main_view.child(list);
 
+        main_view.arrange(Rect::new(0.0, 0.0, self.size.width, self.size.height));
         self.view = Some(main_view);
     }
 
@@ -154,8 +165,13 @@ impl Application for TodoApp {
     }
 
     fn update(&mut self) {
-        // For now, just handle window close events
-        // You'll need to implement proper event handling based on your Event system
+        // Nothing else sends `Event::Tick`, so the app does once per frame to
+        // keep the list's scroll momentum and bounce running.
+        let now = Instant::now();
+        let elapsed = now - std::mem::replace(&mut self.last_frame, now);
+        if let Some(view) = &mut self.view {
+            dispatch(view, &Event::Tick(elapsed));
+        }
     }
 
     fn render(&self, renderer: &mut dyn Renderer) {
@@ -167,5 +183,7 @@ impl Application for TodoApp {
 }
 
 fn main() {
-    RustUI::new().run(|| Box::new(TodoApp::new()));
+    let ui = RustUI::new();
+    let size = ui.window().get_platform_specific_size();
+    ui.run(move || Box::new(TodoApp::new(size)));
 }
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 use crate::style::{Style, Color};
 use crate::renderer::Renderer;
-use crate::event::Event;
-use crate::geometry::Rect;
+use crate::event::{Event, EventContext, EventPhase, KeyCode};
+use crate::geometry::{Rect, Size};
+use crate::layout::{content_insets, measure_styled, Constraints};
+use crate::text;
 use super::Component;
 
 type ClickCallback = Arc<dyn Fn() + Send + Sync>;
@@ -12,6 +14,9 @@ pub struct Button {
     style: Style,
     bounds: Rect,
     on_click: Option<ClickCallback>,
+    focus_visible_style: Option<Style>,
+    focused: bool,
+    focus_visible: bool,
 }
 
 impl Button {
@@ -23,6 +28,9 @@ impl Button {
                 .set_padding(10.0),
             bounds: Rect::default(),
             on_click: None,
+            focus_visible_style: None,
+            focused: false,
+            focus_visible: false,
         }
     }
 
@@ -31,6 +39,29 @@ impl Button {
         self
     }
 
+    // Drawn instead of the regular style while keyboard focus is on the button.
+    pub fn with_focus_visible_style(mut self, style: Style) -> Self {
+        self.focus_visible_style = Some(style);
+        self
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn click(&self) {
+        if let Some(callback) = &self.on_click {
+            (callback)();
+        }
+    }
+
+    fn current_style(&self) -> &Style {
+        match &self.focus_visible_style {
+            Some(style) if self.focused && self.focus_visible => style,
+            _ => &self.style,
+        }
+    }
+
     pub fn on_click<F>(mut self, callback: F) -> Self 
     where
         F: Fn() + Send + Sync + 'static
@@ -42,16 +73,28 @@ impl Button {
 
 impl Component for Button {
     fn render(&self, renderer: &mut dyn Renderer) {
-        renderer.begin_group(&self.style);
-        renderer.draw_text(&self.label, &self.style);
+        let style = self.current_style();
+        renderer.begin_group(style, &self.bounds);
+        renderer.draw_text(&self.label, style);
         renderer.end_group();
     }
 
-    fn handle_event(&mut self, event: Event) {
-        if let Event::Click { .. } = event {
-            if let Some(callback) = &self.on_click {
-                (callback)();
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext) {
+        if context.phase() != EventPhase::Target {
+            return;
+        }
+        match event {
+            Event::Click { .. } => self.click(),
+            Event::KeyDown(key) if key.code == KeyCode::Enter && self.focused => self.click(),
+            Event::Focus { visible } => {
+                self.focused = true;
+                self.focus_visible = *visible;
+            }
+            Event::Blur => {
+                self.focused = false;
+                self.focus_visible = false;
             }
+            _ => {}
         }
     }
 
@@ -59,6 +102,19 @@ impl Component for Button {
         self.bounds.clone()
     }
 
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let available = constraints.max.width - insets.horizontal();
+            let text = text::layout_paragraph(&self.label, &self.style, available).size();
+            Size::new(text.width + insets.horizontal(), text.height + insets.vertical())
+        })
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+    }
+
     fn apply_style(&mut self, style: Style) {
         self.style = style;
     }
@@ -67,6 +123,10 @@ impl Component for Button {
         "button"
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
     fn style_mut(&mut self) -> &mut Style {
         &mut self.style
     }
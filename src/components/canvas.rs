@@ -0,0 +1,196 @@
+use std::sync::Arc;
+use crate::style::{Color, Style};
+use crate::renderer::{Brush, FillRule, Path, Renderer, StrokeStyle};
+use crate::event::{Event, EventContext, EventPhase};
+use crate::geometry::{Point, Rect, Size};
+use crate::layout::{content_box, content_insets, measure_styled, Constraints};
+use crate::svg::SvgDocument;
+use crate::texture::Texture;
+use super::Component;
+
+type DrawCallback = Arc<dyn Fn(&mut DrawContext) + Send + Sync>;
+type EventCallback = Arc<dyn Fn(&Event, Option<Point>) + Send + Sync>;
+
+// Handed to `Canvas::on_draw`. Coordinates start at the top-left corner of
+// the canvas's content box, and everything drawn is clipped to it.
+pub struct DrawContext<'a> {
+    renderer: &'a mut dyn Renderer,
+    size: Size,
+    translation: Point,
+}
+
+impl DrawContext<'_> {
+    // The size of the content box.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    // `brush` is a `Color` or a gradient `Brush`.
+    pub fn fill<B: Into<Brush>>(&mut self, path: &Path, brush: B) {
+        self.renderer.fill_path(path, &brush.into(), FillRule::NonZero);
+    }
+
+    pub fn fill_with_rule<B: Into<Brush>>(&mut self, path: &Path, brush: B, rule: FillRule) {
+        self.renderer.fill_path(path, &brush.into(), rule);
+    }
+
+    pub fn stroke<B: Into<Brush>>(&mut self, path: &Path, brush: B, stroke: &StrokeStyle) {
+        self.renderer.stroke_path(path, &brush.into(), stroke);
+    }
+
+    pub fn fill_rect<B: Into<Brush>>(&mut self, rect: &Rect, brush: B) {
+        let mut path = Path::new();
+        path.rect(rect);
+        self.fill(&path, brush);
+    }
+
+    // Lays `text` out inside `rect` as a `Text` with `style` would, including
+    // its background, border and padding.
+    pub fn text(&mut self, text: &str, rect: &Rect, style: &Style) {
+        self.renderer.begin_group(style, rect);
+        self.renderer.draw_text(text, style);
+        self.renderer.end_group();
+    }
+
+    pub fn image(&mut self, texture: &Texture, rect: &Rect, tint: Option<Color>) {
+        self.renderer.draw_image(texture, rect, tint);
+    }
+
+    // Draws `document` stretched over `rect`, optionally repainted in `color`.
+    pub fn svg(&mut self, document: &SvgDocument, rect: &Rect, color: Option<Color>) {
+        document.draw(&mut *self.renderer, rect, color);
+    }
+
+    // Moves the origin for everything drawn afterwards; undone once
+    // `on_draw` returns.
+    pub fn translate(&mut self, x: f32, y: f32) {
+        self.renderer.translate(x, y);
+        self.translation.x += x;
+        self.translation.y += y;
+    }
+}
+
+// A surface drawn by a callback with paths, text and images, for charts and
+// custom controls. It has no content of its own, so give it a size through
+// `with_size`, its style or a flexing parent.
+pub struct Canvas {
+    on_draw: Option<DrawCallback>,
+    on_event: Option<EventCallback>,
+    natural_size: Size,
+    style: Style,
+    bounds: Rect,
+}
+
+impl Canvas {
+    pub fn new() -> Self {
+        Self {
+            on_draw: None,
+            on_event: None,
+            natural_size: Size::new(0.0, 0.0),
+            style: Style::default(),
+            bounds: Rect::default(),
+        }
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    // The content size the canvas asks for when measured.
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.natural_size = size;
+        self
+    }
+
+    // Called on every render after the style's background and border.
+    pub fn on_draw<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&mut DrawContext) + Send + Sync + 'static
+    {
+        self.on_draw = Some(Arc::new(callback));
+        self
+    }
+
+    // Called with each event targeting the canvas, along with the pointer
+    // position in the same coordinates `on_draw` uses.
+    pub fn on_event<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Event, Option<Point>) + Send + Sync + 'static
+    {
+        self.on_event = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for Canvas {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.begin_group(&self.style, &self.bounds);
+        if let Some(on_draw) = &self.on_draw {
+            let content = content_box(&self.style, &self.bounds);
+            renderer.push_clip(&content);
+            renderer.translate(content.origin.x, content.origin.y);
+            let mut context = DrawContext {
+                renderer: &mut *renderer,
+                size: content.size,
+                translation: content.origin,
+            };
+            (on_draw)(&mut context);
+            let translation = context.translation;
+            renderer.translate(-translation.x, -translation.y);
+            renderer.pop_clip();
+        }
+        renderer.end_group();
+    }
+
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext) {
+        if context.phase() != EventPhase::Target {
+            return;
+        }
+        if let Some(on_event) = &self.on_event {
+            let origin = content_box(&self.style, &self.bounds).origin;
+            let position = context.position().map(|point| Point::new(point.x - origin.x, point.y - origin.y));
+            (on_event)(event, position);
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |_| {
+            let insets = content_insets(&self.style);
+            Size::new(
+                self.natural_size.width + insets.horizontal(),
+                self.natural_size.height + insets.vertical(),
+            )
+        })
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_name(&self) -> &str {
+        "canvas"
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+}
@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use crate::style::{Color, Style};
+use crate::renderer::Renderer;
+use crate::event::{Event, EventContext};
+use crate::geometry::{Rect, Size};
+use crate::layout::{content_box, content_insets, measure_styled, Constraints};
+use crate::texture::{self, ImageSource, Texture};
+use super::Component;
+
+// How an image is sized within its content box; it is always centered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContentFit {
+    // Stretched to the box, ignoring the aspect ratio.
+    #[default]
+    Fill,
+    // As large as fits entirely inside the box.
+    Contain,
+    // As small as covers the whole box, clipping the overflow.
+    Cover,
+    // At its natural size.
+    None,
+    // The smaller of `None` and `Contain`.
+    ScaleDown,
+}
+
+impl ContentFit {
+    // Where content of `natural` size lands inside `area`.
+    pub(crate) fn place(self, natural: Size, area: &Rect) -> Rect {
+        let (width, height) = (area.size.width, area.size.height);
+        let contain = (width / natural.width).min(height / natural.height);
+        let scale = match self {
+            ContentFit::Fill => return area.clone(),
+            ContentFit::Contain => contain,
+            ContentFit::Cover => (width / natural.width).max(height / natural.height),
+            ContentFit::None => 1.0,
+            ContentFit::ScaleDown => contain.min(1.0),
+        };
+        let size = Size::new(natural.width * scale, natural.height * scale);
+        Rect::new(
+            area.origin.x + (width - size.width) / 2.0,
+            area.origin.y + (height - size.height) / 2.0,
+            size.width,
+            size.height,
+        )
+    }
+}
+
+// `natural` scaled down to fit `available` while keeping the aspect ratio.
+pub(crate) fn fit_natural_size(natural: Size, available: Size) -> Size {
+    let scale = (available.width / natural.width).min(available.height / natural.height).min(1.0);
+    let scale = if scale.is_finite() { scale.max(0.0) } else { 1.0 };
+    Size::new(natural.width * scale, natural.height * scale)
+}
+
+// Shows a PNG, JPEG or WebP image, decoded once through the shared texture
+// cache. Corners follow `Style::border_radius`. When decoding fails the
+// component draws only its style and reports the failure from `error`.
+pub struct Image {
+    texture: Option<Arc<Texture>>,
+    error: Option<String>,
+    fit: ContentFit,
+    tint: Option<Color>,
+    style: Style,
+    bounds: Rect,
+}
+
+impl Image {
+    pub fn new(source: ImageSource) -> Self {
+        let (texture, error) = match texture::load(&source) {
+            Ok(texture) => (Some(texture), None),
+            Err(error) => (None, Some(error.to_string())),
+        };
+        Self {
+            texture,
+            error,
+            fit: ContentFit::Fill,
+            tint: None,
+            style: Style::default(),
+            bounds: Rect::default(),
+        }
+    }
+
+    pub fn from_file<P: Into<PathBuf>>(path: P) -> Self {
+        Self::new(ImageSource::file(path))
+    }
+
+    pub fn from_bytes<B: Into<Arc<[u8]>>>(bytes: B) -> Self {
+        Self::new(ImageSource::bytes(bytes))
+    }
+
+    // Shows an already decoded texture, bypassing the cache.
+    pub fn from_texture(texture: Arc<Texture>) -> Self {
+        Self {
+            texture: Some(texture),
+            error: None,
+            fit: ContentFit::Fill,
+            tint: None,
+            style: Style::default(),
+            bounds: Rect::default(),
+        }
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_fit(mut self, fit: ContentFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    // Draws every pixel in `color`, keeping the image's alpha, e.g. for
+    // monochrome icons.
+    pub fn tint(mut self, color: Color) -> Self {
+        self.tint = Some(color);
+        self
+    }
+
+    pub fn texture(&self) -> Option<&Arc<Texture>> {
+        self.texture.as_ref()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+impl Component for Image {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.begin_group(&self.style, &self.bounds);
+        if let Some(texture) = &self.texture {
+            let rect = self.fit.place(texture.size(), &content_box(&self.style, &self.bounds));
+            renderer.draw_image(texture, &rect, self.tint);
+        }
+        renderer.end_group();
+    }
+
+    fn handle_event(&mut self, _event: &Event, _context: &mut EventContext) {}
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    // The natural size, scaled down to fit the constraints while keeping the
+    // aspect ratio.
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let available = constraints.deflate(insets.horizontal(), insets.vertical()).max;
+            let natural = self.texture.as_ref().map_or(Size::new(0.0, 0.0), |texture| texture.size());
+            let size = fit_natural_size(natural, available);
+            Size::new(size.width + insets.horizontal(), size.height + insets.vertical())
+        })
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_name(&self) -> &str {
+        "image"
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+}
@@ -1,12 +1,45 @@
-use crate::geometry::{Rect, Point, Size};
+use std::ops::Range;
+use std::sync::Arc;
+use crate::style::{Border, BorderSide, Color, Style, TextWrap};
 use crate::renderer::Renderer;
-use crate::event::Event;
+use crate::event::{Event, EventContext, EventPhase, KeyCode, KeyEvent};
+use crate::geometry::{Point, Rect, Size};
+use crate::layout::{content_box, content_insets, measure_styled, Constraints};
+use crate::font;
+use crate::text;
 use super::Component;
 
+type TextCallback = Arc<dyn Fn(&str) + Send + Sync>;
+type PasteCallback = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
+const DEFAULT_WIDTH: f32 = 200.0;
+const CARET_WIDTH: f32 = 1.0;
+const MASK: char = '•';
+
+// A single-line text field. `caret` and `anchor` are byte offsets into `value`
+// on grapheme boundaries; the selection is the range between them.
 pub struct Input {
     value: String,
     placeholder: String,
+    style: Style,
+    placeholder_color: Option<Color>,
+    selection_color: Color,
     bounds: Rect,
+    caret: usize,
+    anchor: usize,
+    preedit: String,
+    // How far the text is scrolled left to keep the caret visible when it
+    // is wider than the content box.
+    scroll_x: f32,
+    max_length: Option<usize>,
+    password: bool,
+    focused: bool,
+    dragging: bool,
+    pressed: bool,
+    on_change: Option<TextCallback>,
+    on_submit: Option<TextCallback>,
+    on_copy: Option<TextCallback>,
+    on_paste: Option<PasteCallback>,
 }
 
 impl Input {
@@ -14,36 +47,546 @@ impl Input {
         Self {
             value: String::new(),
             placeholder: placeholder.into(),
-            bounds: Rect {
-                origin: Point::new(0.0, 0.0),
-                size: Size { width: 200.0, height: 40.0 },
-            },
+            style: Style::default()
+                .set_background(Color::WHITE)
+                .set_color(Color::BLACK)
+                .set_padding(8.0)
+                .set_border(Border::all(BorderSide::solid(1.0, Color::rgb(0.6, 0.6, 0.6)))),
+            placeholder_color: None,
+            selection_color: Color::rgba(0.2, 0.6, 1.0, 0.4),
+            bounds: Rect::default(),
+            caret: 0,
+            anchor: 0,
+            preedit: String::new(),
+            scroll_x: 0.0,
+            max_length: None,
+            password: false,
+            focused: false,
+            dragging: false,
+            pressed: false,
+            on_change: None,
+            on_submit: None,
+            on_copy: None,
+            on_paste: None,
         }
     }
 
-    pub fn set_value(&mut self, value: String) {
-        self.value = value;
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_value<S: Into<String>>(mut self, value: S) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    // Limits the value to `length` graphemes; longer input is truncated.
+    pub fn max_length(mut self, length: usize) -> Self {
+        self.max_length = Some(length);
+        self.set_value(self.value.clone());
+        self
+    }
+
+    // Draws every grapheme as a bullet and disables copy and cut.
+    pub fn password(mut self, enabled: bool) -> Self {
+        self.password = enabled;
+        self
+    }
+
+    // Defaults to the text color at half opacity.
+    pub fn placeholder_color(mut self, color: Color) -> Self {
+        self.placeholder_color = Some(color);
+        self
+    }
+
+    pub fn selection_color(mut self, color: Color) -> Self {
+        self.selection_color = color;
+        self
+    }
+
+    // Called with the new value after every edit made through the field.
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static
+    {
+        self.on_change = Some(Arc::new(callback));
+        self
+    }
+
+    // Called with the value when Enter is pressed.
+    pub fn on_submit<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static
+    {
+        self.on_submit = Some(Arc::new(callback));
+        self
+    }
+
+    // Clipboard hooks: `on_copy` receives text copied or cut from the field,
+    // `on_paste` supplies the text to insert on paste.
+    pub fn on_copy<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static
+    {
+        self.on_copy = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_paste<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() -> Option<String> + Send + Sync + 'static
+    {
+        self.on_paste = Some(Arc::new(callback));
+        self
+    }
+
+    // Replaces the value without calling `on_change`; the caret moves to the end.
+    pub fn set_value<S: Into<String>>(&mut self, value: S) {
+        let value: String = value.into();
+        let value = sanitize(&value);
+        let end = match self.max_length {
+            Some(max) => text::grapheme_prefix(&value, max),
+            None => value.len(),
+        };
+        self.value = value[..end].to_string();
+        self.caret = self.value.len();
+        self.anchor = self.caret;
+        self.preedit.clear();
+        self.follow_caret();
     }
 
     pub fn get_value(&self) -> &str {
-        if self.value.is_empty() {
-            &self.placeholder
+        &self.value
+    }
+
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    pub fn selection(&self) -> Range<usize> {
+        self.caret.min(self.anchor)..self.caret.max(self.anchor)
+    }
+
+    pub fn selected_text(&self) -> &str {
+        &self.value[self.selection()]
+    }
+
+    // Selects `range`, snapped outward to grapheme boundaries. The caret ends
+    // up at `range.end`.
+    pub fn select(&mut self, range: Range<usize>) {
+        let boundaries = text::grapheme_boundaries(&self.value);
+        let start = boundaries.iter().rev().copied().find(|&b| b <= range.start).unwrap_or(0);
+        let end = boundaries.iter().copied().find(|&b| b >= range.end).unwrap_or(self.value.len());
+        self.anchor = start;
+        self.caret = end.max(start);
+        self.follow_caret();
+    }
+
+    pub fn select_all(&mut self) {
+        self.select(0..self.value.len());
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn has_selection(&self) -> bool {
+        self.caret != self.anchor
+    }
+
+    fn move_caret(&mut self, offset: usize, extend: bool) {
+        self.caret = offset;
+        if !extend {
+            self.anchor = offset;
+        }
+        self.follow_caret();
+    }
+
+    // Replaces the selection with `insert`, dropping control characters and
+    // whatever would exceed `max_length`.
+    fn replace_selection(&mut self, insert: &str) {
+        let selection = self.selection();
+        let mut insert = sanitize(insert);
+        if let Some(max) = self.max_length {
+            let kept = text::grapheme_count(&self.value) - text::grapheme_count(&self.value[selection.clone()]);
+            insert.truncate(text::grapheme_prefix(&insert, max.saturating_sub(kept)));
+        }
+        if selection.is_empty() && insert.is_empty() {
+            return;
+        }
+        self.value.replace_range(selection.clone(), &insert);
+        self.move_caret(selection.start + insert.len(), false);
+        if let Some(callback) = &self.on_change {
+            (callback)(&self.value);
+        }
+    }
+
+    fn delete(&mut self, forward: bool, by_word: bool) {
+        if !self.has_selection() {
+            self.anchor = match (forward, by_word) {
+                (false, false) => text::previous_grapheme(&self.value, self.caret),
+                (false, true) => text::previous_word(&self.value, self.caret),
+                (true, false) => text::next_grapheme(&self.value, self.caret),
+                (true, true) => text::next_word(&self.value, self.caret),
+            };
+        }
+        self.replace_selection("");
+    }
+
+    fn copy(&self) {
+        if self.password || !self.has_selection() {
+            return;
+        }
+        if let Some(callback) = &self.on_copy {
+            (callback)(self.selected_text());
+        }
+    }
+
+    fn paste(&mut self) {
+        if let Some(text) = self.on_paste.as_ref().and_then(|callback| callback()) {
+            self.replace_selection(&text);
+        }
+    }
+
+    // Returns whether the key was used by the field.
+    fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let modifiers = key.modifiers;
+        let by_word = if cfg!(target_os = "macos") { modifiers.alt } else { modifiers.control };
+        let extend = modifiers.shift;
+        let selection = self.selection();
+
+        match key.code {
+            KeyCode::Left => {
+                let offset = if self.has_selection() && !extend {
+                    selection.start
+                } else if by_word {
+                    text::previous_word(&self.value, self.caret)
+                } else {
+                    text::previous_grapheme(&self.value, self.caret)
+                };
+                self.move_caret(offset, extend);
+            }
+            KeyCode::Right => {
+                let offset = if self.has_selection() && !extend {
+                    selection.end
+                } else if by_word {
+                    text::next_word(&self.value, self.caret)
+                } else {
+                    text::next_grapheme(&self.value, self.caret)
+                };
+                self.move_caret(offset, extend);
+            }
+            KeyCode::Home => self.move_caret(0, extend),
+            KeyCode::End => self.move_caret(self.value.len(), extend),
+            KeyCode::Backspace => self.delete(false, by_word),
+            KeyCode::Delete => self.delete(true, by_word),
+            KeyCode::Enter => {
+                if let Some(callback) = &self.on_submit {
+                    (callback)(&self.value);
+                }
+            }
+            KeyCode::A if modifiers.command() => self.select_all(),
+            KeyCode::C if modifiers.command() => self.copy(),
+            KeyCode::X if modifiers.command() => {
+                if !self.password {
+                    self.copy();
+                    self.replace_selection("");
+                }
+            }
+            KeyCode::V if modifiers.command() => self.paste(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn display_text(&self) -> String {
+        let value = if self.password {
+            MASK.to_string().repeat(text::grapheme_count(&self.value))
+        } else {
+            self.value.clone()
+        };
+        if self.preedit.is_empty() {
+            return value;
+        }
+        let caret = self.display_offset(self.caret);
+        format!("{}{}{}", &value[..caret], self.preedit, &value[caret..])
+    }
+
+    // Maps an offset in `value` to the matching offset in the displayed text,
+    // before any preedit is inserted.
+    fn display_offset(&self, offset: usize) -> usize {
+        if self.password {
+            text::grapheme_count(&self.value[..offset]) * MASK.len_utf8()
+        } else {
+            offset
+        }
+    }
+
+    // The byte offset in the displayed text, including any preedit, that
+    // lines up with `offset` in the value.
+    fn display_prefix(&self, offset: usize) -> usize {
+        let mut prefix = self.display_offset(offset);
+        if !self.preedit.is_empty() && offset >= self.caret {
+            prefix += self.preedit.len();
+        }
+        prefix
+    }
+
+    // The x position of `offset` relative to the content box, following the
+    // renderers' text alignment and the scroll offset.
+    fn offset_x(&self, offset: usize) -> f32 {
+        let shaped = font::shape_line(&self.display_text(), &self.style);
+        self.text_origin_x(shaped.width) + shaped.x_for_offset(self.display_prefix(offset))
+    }
+
+    // Text that fits is aligned like the renderers align it; wider text
+    // starts at the content box's left edge, scrolled by `scroll_x`.
+    fn text_origin_x(&self, width: f32) -> f32 {
+        let available = content_box(&self.style, &self.bounds).size.width;
+        if width + CARET_WIDTH > available {
+            -self.scroll_x
         } else {
-            &self.value
+            text::align_offset(&self.style, available - width)
         }
     }
+
+    // Scrolls the least distance that brings the caret into view, and back
+    // to the start once the text fits.
+    fn follow_caret(&mut self) {
+        let available = content_box(&self.style, &self.bounds).size.width;
+        let shaped = font::shape_line(&self.display_text(), &self.style);
+        let overflow = shaped.width + CARET_WIDTH - available;
+        if overflow <= 0.0 {
+            self.scroll_x = 0.0;
+            return;
+        }
+        let caret = shaped.x_for_offset(self.display_prefix(self.caret));
+        self.scroll_x = self.scroll_x.clamp(caret + CARET_WIDTH - available, caret).clamp(0.0, overflow);
+    }
+
+    // The grapheme boundary nearest to `point`, in the space of `bounds`.
+    fn offset_at(&self, point: Point) -> usize {
+        let shaped = font::shape_line(&self.display_text(), &self.style);
+        let x = point.x - content_box(&self.style, &self.bounds).origin.x - self.text_origin_x(shaped.width);
+        let distance = |offset: usize| (shaped.x_for_offset(self.display_prefix(offset)) - x).abs();
+        text::grapheme_boundaries(&self.value)
+            .into_iter()
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+            .unwrap_or(0)
+    }
+
+    fn line_rect(&self, x: f32, width: f32) -> Rect {
+        let content = content_box(&self.style, &self.bounds);
+        let line_height = font::line_metrics(&self.style).line_height;
+        Rect::new(content.origin.x + x, content.origin.y, width, line_height)
+    }
 }
 
 impl Component for Input {
-    fn render(&self, _renderer: &mut Renderer) {
-        // Implement rendering
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.begin_group(&self.style, &self.bounds);
+        let content = content_box(&self.style, &self.bounds);
+        renderer.push_clip(&content);
+
+        if self.focused && self.has_selection() && self.preedit.is_empty() {
+            let selection = self.selection();
+            let start = self.offset_x(selection.start);
+            let rect = self.line_rect(start, self.offset_x(selection.end) - start);
+            renderer.begin_group(&Style::default().set_background(self.selection_color), &rect);
+            renderer.end_group();
+        }
+
+        if self.value.is_empty() && self.preedit.is_empty() {
+            let color = self.placeholder_color.unwrap_or(Color { a: self.style.color.a * 0.5, ..self.style.color });
+            renderer.draw_text(&self.placeholder, &self.style.clone().set_color(color));
+        } else {
+            // The renderer aligns the text itself, so shift it from there to
+            // where scrolling puts it.
+            let display = self.display_text();
+            let style = Style { text_wrap: TextWrap::NoWrap, ..self.style.clone() };
+            let shaped = font::shape_line(&display, &style);
+            let available = content.size.width;
+            let shift = self.text_origin_x(shaped.width) - text::align_offset(&style, available - shaped.width);
+            renderer.translate(shift, 0.0);
+            renderer.draw_text(&display, &style);
+            renderer.translate(-shift, 0.0);
+        }
+
+        if self.focused {
+            let rect = self.line_rect(self.offset_x(self.caret), CARET_WIDTH);
+            renderer.begin_group(&Style::default().set_background(self.style.color), &rect);
+            renderer.end_group();
+        }
+
+        renderer.pop_clip();
+        renderer.end_group();
     }
 
-    fn handle_event(&mut self, _event: Event) {
-        // Implement event handling
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext) {
+        if context.phase() != EventPhase::Target {
+            return;
+        }
+        let position = context.position();
+        match event {
+            Event::Focus { .. } => self.focused = true,
+            Event::Blur => {
+                self.focused = false;
+                self.dragging = false;
+                self.preedit.clear();
+                self.follow_caret();
+            }
+            Event::MouseDown(_) | Event::TouchStart(_) => {
+                if let Some(point) = position {
+                    let offset = self.offset_at(point);
+                    self.move_caret(offset, false);
+                    self.dragging = true;
+                    self.pressed = true;
+                }
+            }
+            Event::MouseMove(_) if self.dragging => {
+                if let Some(point) = position {
+                    let offset = self.offset_at(point);
+                    self.move_caret(offset, true);
+                }
+            }
+            Event::MouseUp(_) | Event::TouchEnd(_) => self.dragging = false,
+            // A click that follows a press has already placed the caret.
+            Event::Click { .. } if std::mem::take(&mut self.pressed) => {}
+            Event::Click { .. } => {
+                if let Some(point) = position {
+                    let offset = self.offset_at(point);
+                    self.move_caret(offset, false);
+                }
+            }
+            Event::KeyDown(key) if self.focused && self.handle_key(key) => context.stop_propagation(),
+            Event::TextInput(text) if self.focused => {
+                self.preedit.clear();
+                self.replace_selection(text);
+                context.stop_propagation();
+            }
+            Event::ImePreedit { text, .. } if self.focused => {
+                self.preedit = text.clone();
+                self.follow_caret();
+                context.stop_propagation();
+            }
+            _ => {}
+        }
     }
 
     fn bounds(&self) -> Rect {
-        self.bounds
+        self.bounds.clone()
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |_| {
+            let insets = content_insets(&self.style);
+            let line_height = font::line_metrics(&self.style).line_height;
+            Size::new(DEFAULT_WIDTH + insets.horizontal(), line_height + insets.vertical())
+        })
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+        self.follow_caret();
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_name(&self) -> &str {
+        "input"
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+}
+
+// Single-line values cannot hold control characters such as newlines.
+fn sanitize(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Modifiers;
+
+    fn send(input: &mut Input, event: Event) {
+        input.handle_event(&event, &mut EventContext::new(EventPhase::Target, None));
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::KeyDown(KeyEvent::new(code, Modifiers::default()))
+    }
+
+    #[test]
+    fn backspace_removes_a_whole_grapheme() {
+        let mut input = Input::new("").with_value("ok👍🏽");
+        send(&mut input, Event::Focus { visible: true });
+        send(&mut input, key(KeyCode::End));
+        send(&mut input, key(KeyCode::Backspace));
+        assert_eq!(input.get_value(), "ok");
+    }
+
+    #[test]
+    fn max_length_counts_graphemes() {
+        let mut input = Input::new("").max_length(3);
+        send(&mut input, Event::Focus { visible: true });
+        send(&mut input, Event::TextInput("e\u{301}abc".to_string()));
+        assert_eq!(input.get_value(), "e\u{301}ab");
+    }
+
+    #[test]
+    fn long_values_scroll_to_keep_the_caret_visible() {
+        let mut input = Input::new("").with_value("a fairly long value that cannot fit in the field");
+        input.arrange(Rect::new(0.0, 0.0, 80.0, 30.0));
+        send(&mut input, Event::Focus { visible: true });
+        let available = content_box(&input.style, &input.bounds).size.width;
+
+        send(&mut input, key(KeyCode::End));
+        let end = input.offset_x(input.caret());
+        assert!(input.scroll_x > 0.0);
+        assert!((0.0..=available).contains(&(end + CARET_WIDTH)));
+
+        send(&mut input, key(KeyCode::Home));
+        assert_eq!(input.scroll_x, 0.0);
+        assert_eq!(input.offset_x(0), 0.0);
+    }
+
+    #[test]
+    fn selecting_and_composing_scroll_the_caret_into_view() {
+        let mut input = Input::new("").with_value("short");
+        input.arrange(Rect::new(0.0, 0.0, 80.0, 30.0));
+        send(&mut input, Event::Focus { visible: true });
+        assert_eq!(input.scroll_x, 0.0);
+
+        // A long composition pushes the caret past the right edge until the
+        // field is blurred and it is dropped.
+        let text = "a composition far too long to fit".to_string();
+        send(&mut input, Event::ImePreedit { text, cursor: None });
+        assert!(input.scroll_x > 0.0);
+        send(&mut input, Event::Blur);
+        assert_eq!(input.scroll_x, 0.0);
+
+        input.set_value("a fairly long value that cannot fit in the field");
+        input.select(0..0);
+        assert_eq!(input.scroll_x, 0.0);
+        input.select_all();
+        assert!(input.scroll_x > 0.0);
     }
 }
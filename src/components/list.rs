@@ -0,0 +1,557 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use crate::style::{Color, Style};
+use crate::renderer::Renderer;
+use crate::event::{Event, EventContext};
+use crate::geometry::{Point, Rect, Size};
+use crate::layout::{content_box, content_insets, measure_styled, Constraints};
+use super::scroller::{ScrollAxis, ScrollbarVisibility, Scroller};
+use super::Component;
+
+type RowBuilder = Arc<dyn Fn(usize) -> Box<dyn Component> + Send + Sync>;
+type RowPredicate = Arc<dyn Fn(usize) -> bool + Send + Sync>;
+
+const DEFAULT_ROW_HEIGHT: f32 = 32.0;
+// Rows are kept built this far beyond each edge of the viewport so short
+// scrolls do not rebuild them.
+const OVERSCAN: f32 = 200.0;
+// Rows measured above the viewport move the content; this bounds how often
+// the visible range is recomputed for one layout.
+const MAX_LAYOUT_PASSES: usize = 4;
+
+// A vertically scrolling list of `count` rows that only builds the rows near
+// the viewport. Rows come from the builder closure, are measured at the
+// list's width when first shown and dropped once they scroll well out of
+// view. Rows not measured yet count with the estimated height, and the
+// content is shifted as they are measured so the visible rows stay put.
+//
+// Rows marked as section headers stick to the top of the viewport until the
+// next header pushes them out. Scrolling behaves as in `ScrollView`.
+pub struct List {
+    count: usize,
+    builder: RowBuilder,
+    is_header: Option<RowPredicate>,
+    style: Style,
+    bounds: Rect,
+    scroller: Scroller,
+    estimated_height: f32,
+    // The width rows were measured at; measurements are dropped when it
+    // changes.
+    width: f32,
+    heights: Vec<Option<f32>>,
+    // The top of each row in content space, then the total height.
+    offsets: Vec<f32>,
+    // Indices of the section headers, ascending.
+    headers: Vec<usize>,
+    // The header pinned by the last layout and its height, so a header that
+    // was never measured in place is not built again to measure it.
+    pinned: Option<(usize, f32)>,
+    // Built rows in ascending index order, with the pinned header last so it
+    // paints over the rows scrolling beneath it.
+    rows: Vec<Box<dyn Component>>,
+    indices: Vec<usize>,
+}
+
+impl List {
+    pub fn new<F, C>(count: usize, builder: F) -> Self
+    where
+        F: Fn(usize) -> C + Send + Sync + 'static,
+        C: Component + 'static,
+    {
+        let mut list = Self {
+            count,
+            builder: Arc::new(move |index| Box::new(builder(index))),
+            is_header: None,
+            style: Style::default(),
+            bounds: Rect::default(),
+            scroller: Scroller::new(ScrollAxis::Vertical),
+            estimated_height: DEFAULT_ROW_HEIGHT,
+            width: 0.0,
+            heights: vec![None; count],
+            offsets: Vec::new(),
+            headers: Vec::new(),
+            pinned: None,
+            rows: Vec::new(),
+            indices: Vec::new(),
+        };
+        list.update_offsets();
+        list
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    // The height assumed for rows that have not been measured yet. The
+    // closer it is to the real average, the steadier the scrollbar.
+    pub fn estimated_row_height(mut self, height: f32) -> Self {
+        self.estimated_height = height.max(1.0);
+        self.update_offsets();
+        self
+    }
+
+    // Marks the rows for which `is_header` returns true as sticky section
+    // headers. It is called once per row whenever the count changes.
+    pub fn sticky_headers<F>(mut self, is_header: F) -> Self
+    where
+        F: Fn(usize) -> bool + Send + Sync + 'static
+    {
+        self.is_header = Some(Arc::new(is_header));
+        self.update_headers();
+        self
+    }
+
+    pub fn with_scrollbars(mut self, visibility: ScrollbarVisibility) -> Self {
+        self.scroller.scrollbars = visibility;
+        self
+    }
+
+    pub fn scrollbar_color(mut self, color: Color) -> Self {
+        self.scroller.scrollbar_color = color;
+        self
+    }
+
+    // Whether drags can pull the rows past the ends. On by default.
+    pub fn bounce(mut self, enabled: bool) -> Self {
+        self.scroller.bounce = enabled;
+        self
+    }
+
+    // Called with the new offset whenever the rows move.
+    pub fn on_scroll<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(f32) + Send + Sync + 'static
+    {
+        self.scroller.on_scroll = Some(Arc::new(move |offset: Point| callback(offset.y)));
+        self
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // Changes the number of rows, keeping what is built and measured for the
+    // rows that remain, e.g. when a log grows.
+    pub fn set_count(&mut self, count: usize) {
+        self.count = count;
+        self.heights.resize(count, None);
+        self.update_headers();
+        self.update_offsets();
+        self.layout_rows();
+    }
+
+    // Rebuilds and remeasures every row, for when the underlying data
+    // changed.
+    pub fn reload(&mut self) {
+        self.rows.clear();
+        self.indices.clear();
+        self.heights = vec![None; self.count];
+        self.pinned = None;
+        self.update_headers();
+        self.update_offsets();
+        self.layout_rows();
+    }
+
+    pub fn scroll_offset(&self) -> f32 {
+        self.scroller.offset().y
+    }
+
+    pub fn scroll_to(&mut self, offset: f32) {
+        self.scroller.scroll_to(Point::new(0.0, offset));
+        self.layout_rows();
+    }
+
+    // Scrolls so row `index` is at the top of the viewport, below the header
+    // of its section when headers stick.
+    pub fn scroll_to_index(&mut self, index: usize) {
+        if self.count == 0 {
+            return;
+        }
+        let index = index.min(self.count - 1);
+        let covered = match self.header_for(index).filter(|&header| header != index) {
+            Some(header) => self.header_height(header, None),
+            None => 0.0,
+        };
+        self.scroll_to(self.offsets[index] - covered);
+        // Measuring the rows around it can move it from where it was estimated.
+        let settled = self.offsets[index] - covered;
+        if settled != self.scroll_offset() {
+            self.scroll_to(settled);
+        }
+    }
+
+    // The rows at least partly inside the viewport.
+    pub fn visible_range(&self) -> Range<usize> {
+        if self.count == 0 {
+            return 0..0;
+        }
+        let top = self.scroller.offset().y;
+        let bottom = top + self.scroller.viewport().size.height;
+        let first = self.row_at(top);
+        let end = self.offsets.partition_point(|&offset| offset < bottom).max(first + 1);
+        first..end.min(self.count)
+    }
+
+    // Whether momentum or a bounce is still running, i.e. whether the list
+    // needs further ticks.
+    pub fn is_animating(&self) -> bool {
+        self.scroller.is_animating()
+    }
+
+    fn height_of(&self, index: usize) -> f32 {
+        self.heights[index].unwrap_or(self.estimated_height)
+    }
+
+    fn update_offsets(&mut self) {
+        let mut offsets = Vec::with_capacity(self.count + 1);
+        let mut top = 0.0;
+        for index in 0..self.count {
+            offsets.push(top);
+            top += self.height_of(index);
+        }
+        offsets.push(top);
+        self.offsets = offsets;
+    }
+
+    fn update_headers(&mut self) {
+        self.headers = match &self.is_header {
+            Some(is_header) => (0..self.count).filter(|&index| is_header(index)).collect(),
+            None => Vec::new(),
+        };
+    }
+
+    // The row covering content position `y`, clamped to the rows.
+    fn row_at(&self, y: f32) -> usize {
+        let index = self.offsets.partition_point(|&offset| offset <= y).saturating_sub(1);
+        index.min(self.count.saturating_sub(1))
+    }
+
+    // The header of the section `index` belongs to.
+    fn header_for(&self, index: usize) -> Option<usize> {
+        let position = self.headers.partition_point(|&header| header <= index);
+        position.checked_sub(1).map(|position| self.headers[position])
+    }
+
+    fn measure_row(row: &dyn Component, width: f32) -> f32 {
+        row.measure(Constraints::loose(Size::new(width, f32::INFINITY))).height
+    }
+
+    // The height of section header `index`, from the pinned header or its
+    // measurement in place where possible. Otherwise `row`, or a newly built
+    // row, is measured.
+    fn header_height(&self, index: usize, row: Option<&dyn Component>) -> f32 {
+        match (self.pinned, self.heights[index]) {
+            (Some((pinned, height)), _) if pinned == index => height,
+            (_, Some(height)) => height,
+            _ => match row {
+                Some(row) => Self::measure_row(row, self.width),
+                None => Self::measure_row((self.builder)(index).as_ref(), self.width),
+            },
+        }
+    }
+
+    // Builds and measures the rows around the viewport, reusing those that
+    // are still in range, and arranges them in content space.
+    fn layout_rows(&mut self) {
+        let viewport = self.scroller.viewport().clone();
+        let width = viewport.size.width;
+        let mut built: HashMap<usize, Box<dyn Component>> =
+            self.indices.drain(..).zip(self.rows.drain(..)).collect();
+        if self.count == 0 {
+            self.scroller.set_extent(viewport.clone(), Size::new(width, 0.0));
+            return;
+        }
+
+        for _ in 0..MAX_LAYOUT_PASSES {
+            let top = self.scroller.offset().y;
+            // Rows measured above this one must not move what is on screen.
+            let anchor = self.row_at(top);
+            let anchor_top = self.offsets[anchor];
+
+            let mut index = self.row_at(top - OVERSCAN);
+            let mut y = self.offsets[index];
+            let end = top + viewport.size.height + OVERSCAN;
+            let mut changed = false;
+            while index < self.count && y < end {
+                let row = match built.remove(&index) {
+                    Some(row) => row,
+                    None => (self.builder)(index),
+                };
+                let height = Self::measure_row(row.as_ref(), width);
+                if self.heights[index] != Some(height) {
+                    self.heights[index] = Some(height);
+                    changed = true;
+                }
+                built.insert(index, row);
+                y += height;
+                index += 1;
+            }
+            if !changed {
+                break;
+            }
+            self.update_offsets();
+            self.scroller.set_extent(viewport.clone(), Size::new(width, self.offsets[self.count]));
+            let shift = self.offsets[anchor] - anchor_top;
+            if shift == 0.0 {
+                break;
+            }
+            self.scroller.shift(Point::new(0.0, shift));
+        }
+        self.scroller.set_extent(viewport.clone(), Size::new(width, self.offsets[self.count]));
+
+        // Keep only the rows in range, dropping the rest; a pinned header
+        // keeps its row however far above it is.
+        let top = self.scroller.offset().y;
+        let header = self.header_for(self.row_at(top)).filter(|&header| self.offsets[header] < top);
+        let header_row = header.and_then(|header| built.remove(&header));
+        let range = self.row_at(top - OVERSCAN)..self.row_at(top + viewport.size.height + OVERSCAN) + 1;
+        let mut kept: Vec<(usize, Box<dyn Component>)> =
+            built.into_iter().filter(|(index, _)| range.contains(index)).collect();
+        kept.sort_by_key(|(index, _)| *index);
+
+        // A header scrolled past its top is pinned to the viewport, pushed up
+        // by the next header as it arrives.
+        let mut pinned = None;
+        self.pinned = None;
+        if let Some(header) = header {
+            let row = header_row.unwrap_or_else(|| (self.builder)(header));
+            let height = self.header_height(header, Some(row.as_ref()));
+            self.pinned = Some((header, height));
+            let next = self.headers.get(self.headers.partition_point(|&other| other <= header));
+            let y = next.map_or(top, |&next| top.min(self.offsets[next] - height));
+            pinned = Some((header, y, height, row));
+        }
+
+        for (index, mut row) in kept {
+            row.arrange(Rect::new(viewport.origin.x, viewport.origin.y + self.offsets[index], width, self.height_of(index)));
+            self.indices.push(index);
+            self.rows.push(row);
+        }
+        if let Some((header, y, height, mut row)) = pinned {
+            row.arrange(Rect::new(viewport.origin.x, viewport.origin.y + y, width, height));
+            self.indices.push(header);
+            self.rows.push(row);
+        }
+    }
+}
+
+impl Component for List {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let offset = self.scroller.offset();
+        renderer.begin_group(&self.style, &self.bounds);
+        renderer.push_clip(self.scroller.viewport());
+        renderer.translate(-offset.x, -offset.y);
+        for row in &self.rows {
+            row.render(renderer);
+        }
+        renderer.translate(offset.x, offset.y);
+        self.scroller.render_scrollbars(renderer);
+        renderer.pop_clip();
+        renderer.end_group();
+    }
+
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext) {
+        if self.scroller.handle_event(event, context) {
+            self.layout_rows();
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    // As tall as all rows together, using the estimate for those not yet
+    // measured; constrain it with the style or a flexing parent.
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let available = constraints.deflate(insets.horizontal(), insets.vertical()).max;
+            let width = if available.width.is_finite() { available.width } else { 0.0 };
+            let height = self.offsets.last().copied().unwrap_or(0.0);
+            Size::new(width + insets.horizontal(), height + insets.vertical())
+        })
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        let viewport = content_box(&self.style, &bounds);
+        if viewport.size.width != self.width {
+            self.width = viewport.size.width;
+            self.heights = vec![None; self.count];
+            self.pinned = None;
+            self.update_offsets();
+        }
+        self.scroller.set_extent(viewport, Size::new(self.width, self.offsets[self.count]));
+        self.bounds = bounds;
+        self.layout_rows();
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_name(&self) -> &str {
+        "list"
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn children(&self) -> &[Box<dyn Component>] {
+        &self.rows
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Component>] {
+        &mut self.rows
+    }
+
+    // Rows move between children as the list scrolls; their index is their key.
+    fn child_key(&self, index: usize) -> Option<u64> {
+        self.indices.get(index).map(|&row| row as u64)
+    }
+
+    fn content_offset(&self) -> Point {
+        let offset = self.scroller.offset();
+        Point::new(-offset.x, -offset.y)
+    }
+
+    fn clip_rect(&self) -> Option<Rect> {
+        Some(self.scroller.viewport().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use crate::components::mock::{take_log, EventLog, MockComponent};
+    use crate::event::{FocusManager, KeyCode, KeyEvent, Modifiers};
+
+    const ROW: f32 = 20.0;
+
+    // A list of `count` rows of `height`, estimated at `ROW`, in a 100 by 100
+    // viewport, recording every row it builds.
+    fn list(count: usize, height: f32, built: &Arc<Mutex<Vec<usize>>>) -> List {
+        let log = EventLog::default();
+        let built = built.clone();
+        let mut list = List::new(count, move |index| {
+            built.lock().unwrap().push(index);
+            MockComponent::new("row", Rect::new(0.0, 0.0, 100.0, height), &log)
+        })
+        .estimated_row_height(ROW);
+        list.arrange(Rect::new(0.0, 0.0, 100.0, 100.0));
+        list
+    }
+
+    fn top_of(list: &List, index: usize) -> f32 {
+        let position = list.indices.iter().position(|&built| built == index).unwrap();
+        list.rows[position].bounds().origin.y
+    }
+
+    #[test]
+    fn only_rows_near_the_viewport_are_built() {
+        let built = Arc::new(Mutex::new(Vec::new()));
+        let mut list = list(10_000, ROW, &built);
+        let limit = ((100.0 + 2.0 * OVERSCAN) / ROW) as usize + 2;
+        assert_eq!(list.visible_range(), 0..5);
+        assert!(list.children().len() <= limit);
+        assert!(built.lock().unwrap().len() <= limit);
+
+        built.lock().unwrap().clear();
+        list.scroll_to_index(5_000);
+        assert_eq!(list.visible_range(), 5_000..5_005);
+        assert!(list.children().len() <= limit);
+        assert!(built.lock().unwrap().iter().all(|&index| index + limit > 5_000 && index < 5_000 + limit));
+    }
+
+    #[test]
+    fn scrolling_a_little_reuses_built_rows() {
+        let built = Arc::new(Mutex::new(Vec::new()));
+        let mut list = list(1_000, ROW, &built);
+        list.scroll_to(400.0);
+        let before = std::mem::take(&mut *built.lock().unwrap());
+        list.scroll_to(410.0);
+        assert!(built.lock().unwrap().iter().all(|index| !before.contains(index)));
+        assert!(built.lock().unwrap().len() <= 1);
+    }
+
+    #[test]
+    fn measured_rows_replace_the_estimate() {
+        let built = Arc::new(Mutex::new(Vec::new()));
+        let mut list = list(1_000, 45.0, &built);
+        list.scroll_to_index(500);
+        assert_eq!(list.visible_range().start, 500);
+        assert_eq!(top_of(&list, 500), list.scroll_offset());
+        assert_eq!(list.offsets[501] - list.offsets[500], 45.0);
+    }
+
+    #[test]
+    fn headers_stick_until_the_next_pushes_them_out() {
+        let built = Arc::new(Mutex::new(Vec::new()));
+        let mut list = list(100, ROW, &built).sticky_headers(|index| index % 10 == 0);
+        list.reload();
+
+        list.scroll_to(250.0);
+        assert_eq!(list.indices.last(), Some(&10));
+        assert_eq!(top_of(&list, 10), 250.0);
+
+        // Header 20 starts at 400 and pushes header 10 up.
+        list.scroll_to(390.0);
+        assert_eq!(list.indices.last(), Some(&10));
+        assert_eq!(top_of(&list, 10), 400.0 - ROW);
+
+        list.scroll_to(30.0);
+        assert_eq!(list.indices.last(), Some(&0));
+        assert_eq!(top_of(&list, 0), 30.0);
+    }
+
+    #[test]
+    fn a_pinned_header_is_not_rebuilt_while_scrolling() {
+        let builds = Arc::new(AtomicUsize::new(0));
+        let counter = builds.clone();
+        let log = EventLog::default();
+        let mut list = List::new(1_000, move |index| {
+            if index == 0 {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+            MockComponent::new("row", Rect::new(0.0, 0.0, 100.0, ROW), &log)
+        })
+        .sticky_headers(|index| index == 0);
+        list.arrange(Rect::new(0.0, 0.0, 100.0, 100.0));
+        for step in 0..20 {
+            list.scroll_to(1_000.0 + step as f32 * 50.0);
+        }
+        assert_eq!(builds.load(Ordering::SeqCst), 1);
+        assert_eq!(list.indices.last(), Some(&0));
+    }
+
+    #[test]
+    fn a_focused_row_keeps_focus_as_the_list_scrolls() {
+        let log = EventLog::default();
+        let rows = log.clone();
+        let mut list = List::new(1_000, move |index| {
+            MockComponent::new(format!("row {index}"), Rect::new(0.0, 0.0, 100.0, ROW), &rows).focusable()
+        })
+        .estimated_row_height(ROW);
+        list.arrange(Rect::new(0.0, 0.0, 100.0, 100.0));
+        list.scroll_to(1_000.0);
+        let mut focus = FocusManager::new();
+        let slot = list.indices.iter().position(|&index| index == 52).unwrap();
+        assert!(focus.focus(&mut list, &[slot]));
+        take_log(&log);
+
+        // The rows above move down a slot as the first one is dropped.
+        list.scroll_to(1_000.0 + ROW);
+        assert_ne!(list.indices[slot], 52);
+        focus.dispatch(&mut list, &Event::KeyDown(KeyEvent::new(KeyCode::Enter, Modifiers::default())));
+        assert_eq!(take_log(&log), ["row 52 Target key"]);
+        assert_eq!(list.indices[focus.focused().unwrap()[0]], 52);
+    }
+}
@@ -0,0 +1,123 @@
+use std::sync::{Arc, Mutex};
+use crate::event::{Event, EventContext, EventPhase};
+use crate::geometry::{Rect, Size};
+use crate::layout::Constraints;
+use crate::renderer::Renderer;
+use crate::style::Style;
+use super::Component;
+
+// Shared by a tree of mocks; each entry is "<name> <phase> <event>".
+pub(crate) type EventLog = Arc<Mutex<Vec<String>>>;
+
+// A component with fixed bounds that records the events it handles.
+pub(crate) struct MockComponent {
+    name: String,
+    bounds: Rect,
+    style: Style,
+    children: Vec<Box<dyn Component>>,
+    focusable: bool,
+    stop_in: Option<EventPhase>,
+    log: EventLog,
+}
+
+impl MockComponent {
+    pub fn new(name: impl Into<String>, bounds: Rect, log: &EventLog) -> Self {
+        Self {
+            name: name.into(),
+            bounds,
+            style: Style::default(),
+            children: Vec::new(),
+            focusable: false,
+            stop_in: None,
+            log: log.clone(),
+        }
+    }
+
+    pub fn child(mut self, child: MockComponent) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn focusable(mut self) -> Self {
+        self.focusable = true;
+        self
+    }
+
+    // Stops propagation when the event reaches this component in `phase`.
+    pub fn stopping(mut self, phase: EventPhase) -> Self {
+        self.stop_in = Some(phase);
+        self
+    }
+}
+
+// Drains `log`, returning what was recorded since the last call.
+pub(crate) fn take_log(log: &EventLog) -> Vec<String> {
+    std::mem::take(&mut *log.lock().unwrap())
+}
+
+fn event_name(event: &Event) -> &'static str {
+    match event {
+        Event::KeyDown(_) => "key",
+        Event::Click { .. } => "click",
+        Event::MouseDown(_) => "down",
+        Event::MouseMove(_) => "move",
+        Event::MouseUp(_) => "up",
+        Event::Focus { visible: true } => "focus-visible",
+        Event::Focus { visible: false } => "focus",
+        Event::Blur => "blur",
+        _ => "other",
+    }
+}
+
+impl Component for MockComponent {
+    fn render(&self, _renderer: &mut dyn Renderer) {}
+
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext) {
+        let entry = format!("{} {:?} {}", self.name, context.phase(), event_name(event));
+        self.log.lock().unwrap().push(entry);
+        if self.stop_in == Some(context.phase()) {
+            context.stop_propagation();
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    fn measure(&self, _constraints: Constraints) -> Size {
+        self.bounds.size
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.focusable
+    }
+
+    fn children(&self) -> &[Box<dyn Component>] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Component>] {
+        &mut self.children
+    }
+}
@@ -1,24 +1,94 @@
 mod button;
+mod canvas;
+mod image;
+mod input;
+mod list;
+mod rich_text;
+mod scroll_view;
+mod scroller;
+mod stack;
+mod svg;
 mod text;
+mod text_area;
 mod view;
+#[cfg(test)]
+pub(crate) mod mock;
 
 pub use button::Button;
+pub use canvas::{Canvas, DrawContext};
+pub use image::{ContentFit, Image};
+pub use input::Input;
+pub use list::List;
+pub use rich_text::{RichText, Span};
+pub use scroll_view::ScrollView;
+pub use scroller::{ScrollAxis, ScrollbarVisibility};
+pub use stack::{Direction, Stack};
+pub use svg::Svg;
 pub use text::Text;
+pub use text_area::TextArea;
 pub use view::View;
 
-use crate::geometry::Rect;
+use crate::geometry::{Point, Rect, Size};
+use crate::layout::{stacking_order, Constraints};
 use crate::style::Style;
-use crate::event::Event;
+use crate::event::{Event, EventContext};
 use crate::renderer::Renderer;
 
 pub trait Component {
     fn render(&self, renderer: &mut dyn Renderer);
-    fn handle_event(&mut self, event: Event);
+    // Called once per phase the component takes part in while an event is
+    // routed through the tree; see `event::dispatch`.
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext);
     fn bounds(&self) -> Rect;
+    // Layout runs in two passes: `measure` reports the size the component
+    // wants within `constraints`, then `arrange` assigns its final rect.
+    fn measure(&self, constraints: Constraints) -> Size;
+    fn arrange(&mut self, bounds: Rect);
+    // The narrowest width the component can take without its content
+    // overflowing, such as the widest word of wrapping text. Grid columns
+    // with an automatic minimum size can shrink items down to it.
+    fn min_content_width(&self) -> f32 {
+        self.measure(Constraints::unbounded()).width
+    }
     fn apply_style(&mut self, style: Style);
     fn style_mut(&mut self) -> &mut Style;
     fn style(&self) -> &Style;
     fn style_name(&self) -> &str {
         "default"
     }
+
+    // Whether the component can take keyboard focus; see `FocusManager`.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    fn children(&self) -> &[Box<dyn Component>] {
+        &[]
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Component>] {
+        &mut []
+    }
+
+    // A key for child `index` that stays with it when the component reorders
+    // its children, such as a list reusing rows as it scrolls. Paths kept
+    // across layouts, like the focused one, follow keyed children.
+    fn child_key(&self, _index: usize) -> Option<u64> {
+        None
+    }
+
+    // Translation applied to children when rendering, e.g. a scroll offset.
+    fn content_offset(&self) -> Point {
+        Point::new(0.0, 0.0)
+    }
+
+    // The area children are clipped to, in the same space as `bounds`.
+    fn clip_rect(&self) -> Option<Rect> {
+        None
+    }
+}
+
+// Back to front; containers render their children in this order.
+pub(crate) fn paint_order(children: &[Box<dyn Component>]) -> Vec<usize> {
+    stacking_order(children.iter().map(|child| child.style()))
 }
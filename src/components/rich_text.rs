@@ -0,0 +1,261 @@
+use std::sync::Arc;
+use crate::style::{Color, SpanStyle, Style, VerticalAlign};
+use crate::renderer::Renderer;
+use crate::event::{Event, EventContext, EventPhase};
+use crate::geometry::{Point, Rect, Size};
+use crate::layout::{content_box, content_insets, measure_styled, Constraints};
+use crate::font;
+use crate::text::{self, Paragraph, StyledRun};
+use super::Component;
+
+type SpanCallback = Arc<dyn Fn(usize) + Send + Sync>;
+type LinkCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+const LINK_COLOR: Color = Color { r: 0.0, g: 0.4, b: 0.8, a: 1.0 };
+const UNDERLINE_THICKNESS: f32 = 1.0;
+
+// A run of text with its own style, optionally linking somewhere.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: SpanStyle,
+    pub link: Option<String>,
+}
+
+impl Span {
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            style: SpanStyle::default(),
+            link: None,
+        }
+    }
+
+    // Links are underlined in the link color unless their style says
+    // otherwise.
+    pub fn link<S: Into<String>, U: Into<String>>(text: S, url: U) -> Self {
+        Self {
+            text: text.into(),
+            style: SpanStyle::default(),
+            link: Some(url.into()),
+        }
+    }
+
+    pub fn with_style(mut self, style: SpanStyle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+// A paragraph made of differently styled spans, laid out as one block of
+// text with the component style's wrapping, alignment and line limits.
+pub struct RichText {
+    spans: Vec<Span>,
+    style: Style,
+    bounds: Rect,
+    paragraph: Paragraph,
+    on_span_click: Option<SpanCallback>,
+    on_link: Option<LinkCallback>,
+}
+
+impl RichText {
+    pub fn new(spans: Vec<Span>) -> Self {
+        Self {
+            spans,
+            style: Style::default(),
+            bounds: Rect::default(),
+            paragraph: Paragraph { lines: Vec::new() },
+            on_span_click: None,
+            on_link: None,
+        }
+    }
+
+    pub fn span(mut self, span: Span) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Called with the index of the clicked span.
+    pub fn on_span_click<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static
+    {
+        self.on_span_click = Some(Arc::new(callback));
+        self
+    }
+
+    // Called with the target of a clicked link span.
+    pub fn on_link<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static
+    {
+        self.on_link = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    // The index of the span under `point`, in the space of `bounds`, as of
+    // the last `arrange`.
+    pub fn span_at(&self, point: Point) -> Option<usize> {
+        let mut hit = None;
+        self.for_each_fragment(|span, _, rect, _| {
+            if rect.contains(point) {
+                hit = Some(span);
+            }
+        });
+        hit
+    }
+
+    fn span_style(&self, index: usize) -> Style {
+        let span = &self.spans[index];
+        let mut style = span.style.resolve(&self.style);
+        if span.link.is_some() && span.style.color.is_none() {
+            style.color = LINK_COLOR;
+        }
+        style
+    }
+
+    fn is_underlined(&self, index: usize) -> bool {
+        let span = &self.spans[index];
+        span.style.underline.unwrap_or(span.link.is_some())
+    }
+
+    // Breaks the spans into lines no wider than `width` where the wrap mode
+    // allows it.
+    fn layout(&self, width: f32) -> Paragraph {
+        let runs: Vec<StyledRun> = self
+            .spans
+            .iter()
+            .enumerate()
+            .map(|(index, span)| StyledRun { text: &span.text, style: self.span_style(index) })
+            .collect();
+        text::layout_runs(&runs, &self.style, width)
+    }
+
+    // Visits the text of every fragment with its span, rect and baseline in
+    // the space of `bounds`; the rect spans the full height of its line.
+    // Justified lines are visited word by word so the gaps can widen.
+    fn for_each_fragment<F>(&self, mut visit: F)
+    where
+        F: FnMut(usize, &str, Rect, f32),
+    {
+        let content = content_box(&self.style, &self.bounds);
+        let free = content.size.height - self.paragraph.size().height;
+        let mut top = content.origin.y + match self.style.vertical_align {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Center => free / 2.0,
+            VerticalAlign::Bottom => free,
+        };
+
+        for line in &self.paragraph.lines {
+            let (offset, spacing) = line.alignment(&self.style, content.size.width);
+            for fragment in &line.fragments {
+                let pieces = if spacing > 0.0 {
+                    text::word_bound_ranges(&fragment.text)
+                } else {
+                    vec![(0, fragment.text.len())]
+                };
+                for (start, end) in pieces {
+                    let x = content.origin.x + offset + line.range_x(fragment, start, end, spacing);
+                    let width = fragment.shaped.advance_between(start, end);
+                    let rect = Rect::new(x, top, width, line.height());
+                    visit(fragment.run, &fragment.text[start..end], rect, top + line.ascent);
+                }
+            }
+            top += line.height();
+        }
+    }
+}
+
+impl Component for RichText {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.begin_group(&self.style, &self.bounds);
+        self.for_each_fragment(|span, text, rect, baseline| {
+            let style = self.span_style(span);
+            let metrics = font::line_metrics(&style);
+            let mut group = Style::default();
+            if let Some(background) = self.spans[span].style.background {
+                group.background = background;
+            }
+            let text_rect = Rect::new(rect.origin.x, baseline - metrics.ascent, rect.size.width, metrics.line_height);
+            renderer.begin_group(&group, &text_rect);
+            renderer.draw_text(text, &style);
+            renderer.end_group();
+
+            if self.is_underlined(span) {
+                let width = font::text_width(text.trim_end(), &style);
+                let underline = Rect::new(rect.origin.x, baseline + UNDERLINE_THICKNESS, width, UNDERLINE_THICKNESS);
+                renderer.begin_group(&Style::default().set_background(style.color), &underline);
+                renderer.end_group();
+            }
+        });
+        renderer.end_group();
+    }
+
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext) {
+        if context.phase() != EventPhase::Target {
+            return;
+        }
+        if let (Event::Click { .. }, Some(point)) = (event, context.position()) {
+            let Some(index) = self.span_at(point) else { return };
+            if let Some(callback) = &self.on_span_click {
+                (callback)(index);
+            }
+            if let (Some(url), Some(callback)) = (&self.spans[index].link, &self.on_link) {
+                (callback)(url);
+            }
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let content = self.layout(constraints.max.width - insets.horizontal()).size();
+            Size::new(content.width + insets.horizontal(), content.height + insets.vertical())
+        })
+    }
+
+    fn min_content_width(&self) -> f32 {
+        let insets = content_insets(&self.style);
+        let width = self
+            .spans
+            .iter()
+            .enumerate()
+            .map(|(index, span)| text::min_content_width(&span.text, &self.span_style(index)))
+            .fold(0.0, f32::max);
+        measure_styled(&self.style, Constraints::unbounded(), |_| Size::new(width + insets.horizontal(), 0.0)).width
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+        self.paragraph = self.layout(content_box(&self.style, &self.bounds).size.width);
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_name(&self) -> &str {
+        "rich_text"
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+}
@@ -0,0 +1,190 @@
+use std::sync::Arc;
+use crate::style::{Color, Style};
+use crate::renderer::Renderer;
+use crate::event::{Event, EventContext};
+use crate::geometry::{Point, Rect, Size};
+use crate::layout::{content_box, content_insets, measure_styled, Constraints};
+use super::scroller::{ScrollAxis, ScrollbarVisibility, Scroller};
+use super::Component;
+
+// Clips a single child to its content box and scrolls it with the wheel,
+// trackpad, mouse drag or touch. Released drags keep their momentum, and
+// with bounce enabled the content can be pulled past its edges and springs
+// back. Momentum and bounce advance on `Event::Tick`.
+pub struct ScrollView {
+    content: Vec<Box<dyn Component>>,
+    style: Style,
+    bounds: Rect,
+    scroller: Scroller,
+}
+
+impl ScrollView {
+    pub fn new<C: Component + 'static>(content: C) -> Self {
+        Self {
+            content: vec![Box::new(content)],
+            style: Style::default(),
+            bounds: Rect::default(),
+            scroller: Scroller::new(ScrollAxis::Vertical),
+        }
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_axis(mut self, axis: ScrollAxis) -> Self {
+        self.scroller.axis = axis;
+        self
+    }
+
+    pub fn with_scrollbars(mut self, visibility: ScrollbarVisibility) -> Self {
+        self.scroller.scrollbars = visibility;
+        self
+    }
+
+    pub fn scrollbar_color(mut self, color: Color) -> Self {
+        self.scroller.scrollbar_color = color;
+        self
+    }
+
+    // Whether drags can pull the content past its edges. On by default.
+    pub fn bounce(mut self, enabled: bool) -> Self {
+        self.scroller.bounce = enabled;
+        self
+    }
+
+    // Called with the new offset whenever the content moves.
+    pub fn on_scroll<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(Point) + Send + Sync + 'static
+    {
+        self.scroller.on_scroll = Some(Arc::new(callback));
+        self
+    }
+
+    // How far the content is scrolled from its top-left corner. It lies
+    // outside `0..=max_offset()` while bouncing.
+    pub fn scroll_offset(&self) -> Point {
+        self.scroller.offset()
+    }
+
+    pub fn max_offset(&self) -> Point {
+        self.scroller.max_offset()
+    }
+
+    pub fn content_size(&self) -> Size {
+        self.scroller.content_size()
+    }
+
+    // Jumps to `offset`, clamped to the scrollable range, and stops any
+    // momentum.
+    pub fn scroll_to(&mut self, offset: Point) {
+        self.scroller.scroll_to(offset);
+    }
+
+    pub fn scroll_by(&mut self, delta: Point) {
+        self.scroller.scroll_by(delta);
+    }
+
+    // Whether momentum or a bounce is still running, i.e. whether the view
+    // needs further ticks.
+    pub fn is_animating(&self) -> bool {
+        self.scroller.is_animating()
+    }
+
+    // The content's size when it may extend without bound along the
+    // scrolling axes of `available`.
+    fn measure_content(&self, available: Size) -> Size {
+        let axis = self.scroller.axis;
+        let max = Size::new(
+            if axis.horizontal() { f32::INFINITY } else { available.width.max(0.0) },
+            if axis.vertical() { f32::INFINITY } else { available.height.max(0.0) },
+        );
+        self.content
+            .iter()
+            .map(|child| child.measure(Constraints::loose(max)))
+            .fold(Size::new(0.0, 0.0), |size, child| Size::new(size.width.max(child.width), size.height.max(child.height)))
+    }
+}
+
+impl Component for ScrollView {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let offset = self.scroller.offset();
+        renderer.begin_group(&self.style, &self.bounds);
+        renderer.push_clip(self.scroller.viewport());
+        renderer.translate(-offset.x, -offset.y);
+        for child in &self.content {
+            child.render(renderer);
+        }
+        renderer.translate(offset.x, offset.y);
+        self.scroller.render_scrollbars(renderer);
+        renderer.pop_clip();
+        renderer.end_group();
+    }
+
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext) {
+        self.scroller.handle_event(event, context);
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    // Sized by its content, which is measured without a bound along the
+    // scrolling axes; constrain it with the style or a flexing parent.
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let available = constraints.deflate(insets.horizontal(), insets.vertical()).max;
+            let content = self.measure_content(available);
+            Size::new(content.width + insets.horizontal(), content.height + insets.vertical())
+        })
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        let viewport = content_box(&self.style, &bounds);
+        let measured = self.measure_content(viewport.size);
+        // The content fills the viewport on axes it does not overflow.
+        let size = Size::new(measured.width.max(viewport.size.width), measured.height.max(viewport.size.height));
+        let rect = Rect::new(viewport.origin.x, viewport.origin.y, size.width, size.height);
+        for child in &mut self.content {
+            child.arrange(rect.clone());
+        }
+        self.scroller.set_extent(viewport, size);
+        self.bounds = bounds;
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_name(&self) -> &str {
+        "scroll_view"
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn children(&self) -> &[Box<dyn Component>] {
+        &self.content
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Component>] {
+        &mut self.content
+    }
+
+    fn content_offset(&self) -> Point {
+        let offset = self.scroller.offset();
+        Point::new(-offset.x, -offset.y)
+    }
+
+    fn clip_rect(&self) -> Option<Rect> {
+        Some(self.scroller.viewport().clone())
+    }
+}
@@ -0,0 +1,550 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::style::{Color, Style};
+use crate::renderer::Renderer;
+use crate::event::{Event, EventContext, EventPhase};
+use crate::geometry::{Point, Rect, Size};
+
+pub(crate) type ScrollCallback = Arc<dyn Fn(Point) + Send + Sync>;
+
+// Movement before a press turns into a drag, so taps still reach children.
+const DRAG_THRESHOLD: f32 = 4.0;
+// Fraction of the pointer movement applied while dragging past an edge.
+const RUBBER_BAND: f32 = 0.5;
+// Momentum decays by e^-FRICTION per second.
+const FRICTION: f32 = 3.0;
+// Below this speed, in points per second, momentum stops.
+const MIN_VELOCITY: f32 = 10.0;
+// Stiffness of the spring pulling overscrolled content back to the edge;
+// it is critically damped so it settles without oscillating.
+const SPRING: f32 = 180.0;
+// How far back pointer samples are used to estimate the release velocity.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+// Integration step for momentum and bounce.
+const STEP: f32 = 1.0 / 240.0;
+const SCROLLBAR_WIDTH: f32 = 6.0;
+const SCROLLBAR_INSET: f32 = 2.0;
+const MIN_THUMB_LENGTH: f32 = 20.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrollAxis {
+    #[default]
+    Vertical,
+    Horizontal,
+    Both,
+}
+
+impl ScrollAxis {
+    pub(crate) fn vertical(self) -> bool {
+        self != ScrollAxis::Horizontal
+    }
+
+    pub(crate) fn horizontal(self) -> bool {
+        self != ScrollAxis::Vertical
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrollbarVisibility {
+    // Shown on each axis whose content overflows.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Thumb {
+    Vertical,
+    Horizontal,
+}
+
+// A press that may turn into a drag of the content or of a scrollbar thumb.
+struct Drag {
+    start: Point,
+    last: Point,
+    dragging: bool,
+    thumb: Option<Thumb>,
+    // Recent content offsets, for the velocity at release.
+    samples: Vec<(Instant, Point)>,
+}
+
+// Scroll state and input handling shared by the scrolling components: wheel,
+// trackpad, mouse drag and touch scrolling, momentum after a released drag,
+// rubber-band overscroll that springs back, and scrollbars. Momentum and
+// bounce advance on `Event::Tick`.
+pub(crate) struct Scroller {
+    pub axis: ScrollAxis,
+    pub scrollbars: ScrollbarVisibility,
+    pub scrollbar_color: Color,
+    pub bounce: bool,
+    pub on_scroll: Option<ScrollCallback>,
+    viewport: Rect,
+    content_size: Size,
+    // Outside `0..=max_offset()` only while overscrolled.
+    offset: Point,
+    // Points per second.
+    velocity: Point,
+    drag: Option<Drag>,
+    // Set when a drag ends so the click that follows does not reach a child.
+    suppress_click: bool,
+}
+
+impl Scroller {
+    pub fn new(axis: ScrollAxis) -> Self {
+        Self {
+            axis,
+            scrollbars: ScrollbarVisibility::Auto,
+            scrollbar_color: Color::rgba(0.0, 0.0, 0.0, 0.4),
+            bounce: true,
+            on_scroll: None,
+            viewport: Rect::new(0.0, 0.0, 0.0, 0.0),
+            content_size: Size::new(0.0, 0.0),
+            offset: Point::new(0.0, 0.0),
+            velocity: Point::new(0.0, 0.0),
+            drag: None,
+            suppress_click: false,
+        }
+    }
+
+    pub fn offset(&self) -> Point {
+        self.offset
+    }
+
+    pub fn viewport(&self) -> &Rect {
+        &self.viewport
+    }
+
+    pub fn content_size(&self) -> Size {
+        self.content_size
+    }
+
+    pub fn max_offset(&self) -> Point {
+        Point::new(
+            (self.content_size.width - self.viewport.size.width).max(0.0),
+            (self.content_size.height - self.viewport.size.height).max(0.0),
+        )
+    }
+
+    // Updates the visible area and the size of what scrolls inside it,
+    // pulling the offset back into range unless it is bouncing.
+    pub fn set_extent(&mut self, viewport: Rect, content_size: Size) {
+        self.viewport = viewport;
+        self.content_size = content_size;
+        if !self.is_animating() && self.drag.is_none() {
+            let max = self.max_offset();
+            self.set_offset(Point::new(self.offset.x.clamp(0.0, max.x), self.offset.y.clamp(0.0, max.y)));
+        }
+    }
+
+    // Jumps to `offset`, clamped to the scrollable range, and stops any
+    // momentum.
+    pub fn scroll_to(&mut self, offset: Point) {
+        self.velocity = Point::new(0.0, 0.0);
+        let max = self.max_offset();
+        self.set_offset(Point::new(offset.x.clamp(0.0, max.x), offset.y.clamp(0.0, max.y)));
+    }
+
+    pub fn scroll_by(&mut self, delta: Point) {
+        self.scroll_to(Point::new(self.offset.x + delta.x, self.offset.y + delta.y));
+    }
+
+    // Moves the content without clamping or stopping momentum, e.g. to keep
+    // rows in place when the height of those above them changes.
+    pub fn shift(&mut self, delta: Point) {
+        self.set_offset(Point::new(self.offset.x + delta.x, self.offset.y + delta.y));
+    }
+
+    // Whether momentum or a bounce is still running, i.e. whether further
+    // ticks are needed.
+    pub fn is_animating(&self) -> bool {
+        let max = self.max_offset();
+        let overscrolled = |value: f32, max: f32| value < 0.0 || value > max;
+        self.velocity.x != 0.0
+            || self.velocity.y != 0.0
+            || (self.drag.is_none() && (overscrolled(self.offset.x, max.x) || overscrolled(self.offset.y, max.y)))
+    }
+
+    // Presses are watched on the way down so drags starting over any child
+    // scroll, while wheel deltas are taken on the way back up so nested
+    // scrollable components get them first. Returns whether the offset
+    // changed.
+    pub fn handle_event(&mut self, event: &Event, context: &mut EventContext) -> bool {
+        let previous = self.offset;
+        let phase = context.phase();
+        let position = context.position();
+        match event {
+            Event::Tick(elapsed) => self.tick(*elapsed),
+            Event::Scroll { delta, .. } if phase != EventPhase::Capture => self.wheel(*delta, context),
+            _ if phase == EventPhase::Bubble => {}
+            Event::MouseDown(_) | Event::TouchStart(_) => {
+                self.suppress_click = false;
+                if let Some(point) = position {
+                    self.press(point, context);
+                }
+            }
+            Event::MouseMove(_) | Event::TouchMove(_) => {
+                if let Some(point) = position {
+                    self.move_to(point, context);
+                }
+            }
+            Event::MouseUp(_) | Event::TouchEnd(_) => self.release(context),
+            Event::Click { .. } if std::mem::take(&mut self.suppress_click) => context.stop_propagation(),
+            _ => {}
+        }
+        self.offset != previous
+    }
+
+    // Draws the thumbs over the viewport; call it in the untranslated space
+    // of the component's bounds.
+    pub fn render_scrollbars(&self, renderer: &mut dyn Renderer) {
+        let style = Style::default()
+            .set_background(self.scrollbar_color)
+            .set_border_radius(SCROLLBAR_WIDTH / 2.0);
+        for thumb in [Thumb::Vertical, Thumb::Horizontal] {
+            if let Some(rect) = self.thumb_rect(thumb) {
+                renderer.begin_group(&style, &rect);
+                renderer.end_group();
+            }
+        }
+    }
+
+    fn set_offset(&mut self, offset: Point) {
+        let offset = Point::new(
+            if self.axis.horizontal() { offset.x } else { 0.0 },
+            if self.axis.vertical() { offset.y } else { 0.0 },
+        );
+        if offset != self.offset {
+            self.offset = offset;
+            if let Some(callback) = &self.on_scroll {
+                callback(offset);
+            }
+        }
+    }
+
+    // Moves the content by a drag of `delta`, resisting past the edges when
+    // bouncing and stopping at them otherwise.
+    fn drag_by(&mut self, delta: Point) {
+        let max = self.max_offset();
+        let axis = |offset: f32, delta: f32, max: f32| {
+            let next = offset + delta;
+            match self.bounce {
+                true if next < 0.0 || next > max => offset + delta * RUBBER_BAND,
+                true => next,
+                false => next.clamp(0.0, max),
+            }
+        };
+        self.set_offset(Point::new(axis(self.offset.x, delta.x, max.x), axis(self.offset.y, delta.y, max.y)));
+    }
+
+    // Advances momentum and bounce by `elapsed`.
+    fn tick(&mut self, elapsed: Duration) {
+        if self.drag.is_some() || !self.is_animating() {
+            return;
+        }
+        let max = self.max_offset();
+        let (mut x, mut vx) = (self.offset.x, self.velocity.x);
+        let (mut y, mut vy) = (self.offset.y, self.velocity.y);
+        let mut remaining = elapsed.as_secs_f32();
+        while remaining > 0.0 {
+            let step = remaining.min(STEP);
+            self.advance_axis(&mut x, &mut vx, max.x, step);
+            self.advance_axis(&mut y, &mut vy, max.y, step);
+            remaining -= step;
+        }
+        self.velocity = Point::new(vx, vy);
+        self.set_offset(Point::new(x, y));
+    }
+
+    fn advance_axis(&self, offset: &mut f32, velocity: &mut f32, max: f32, step: f32) {
+        let edge = offset.clamp(0.0, max);
+        let overscroll = *offset - edge;
+        if overscroll != 0.0 {
+            let damping = 2.0 * SPRING.sqrt();
+            *velocity += (-SPRING * overscroll - damping * *velocity) * step;
+            *offset += *velocity * step;
+            // Settled, or crossed back inside the range.
+            let crossed = (*offset - edge).signum() != overscroll.signum();
+            if crossed || (overscroll.abs() < 0.5 && velocity.abs() < MIN_VELOCITY) {
+                *offset = edge;
+                *velocity = 0.0;
+            }
+            return;
+        }
+        if *velocity == 0.0 {
+            return;
+        }
+        *offset += *velocity * step;
+        *velocity *= (-FRICTION * step).exp();
+        if *offset < 0.0 || *offset > max {
+            if !self.bounce {
+                *offset = offset.clamp(0.0, max);
+                *velocity = 0.0;
+            }
+        } else if velocity.abs() < MIN_VELOCITY {
+            *velocity = 0.0;
+        }
+    }
+
+    // The offset's rate of change over the recent samples, or zero when the
+    // pointer rested before it was released.
+    fn release_velocity(samples: &[(Instant, Point)]) -> Point {
+        let now = Instant::now();
+        let recent: Vec<_> = samples.iter().filter(|(time, _)| now - *time <= VELOCITY_WINDOW).collect();
+        match (recent.first(), recent.last()) {
+            (Some(first), Some(last)) if last.0 > first.0 => {
+                let seconds = (last.0 - first.0).as_secs_f32();
+                Point::new((last.1.x - first.1.x) / seconds, (last.1.y - first.1.y) / seconds)
+            }
+            _ => Point::new(0.0, 0.0),
+        }
+    }
+
+    fn thumb_rect(&self, thumb: Thumb) -> Option<Rect> {
+        let viewport = &self.viewport;
+        let max = self.max_offset();
+        let (shown, length, content, offset, max) = match thumb {
+            Thumb::Vertical => (self.axis.vertical(), viewport.size.height, self.content_size.height, self.offset.y, max.y),
+            Thumb::Horizontal => (self.axis.horizontal(), viewport.size.width, self.content_size.width, self.offset.x, max.x),
+        };
+        let visible = match self.scrollbars {
+            ScrollbarVisibility::Never => false,
+            ScrollbarVisibility::Always => true,
+            ScrollbarVisibility::Auto => max > 0.0,
+        };
+        if !shown || !visible || length <= 0.0 {
+            return None;
+        }
+
+        let track = length - SCROLLBAR_INSET * 2.0;
+        // The thumb shrinks while the content is pulled past an edge.
+        let overscroll = offset - offset.clamp(0.0, max);
+        let ratio = if content > 0.0 { (length / content).min(1.0) } else { 1.0 };
+        let size = (track * ratio - overscroll.abs()).max(MIN_THUMB_LENGTH).min(track);
+        let progress = if max > 0.0 { offset.clamp(0.0, max) / max } else { 0.0 };
+        let start = SCROLLBAR_INSET + (track - size) * progress;
+        Some(match thumb {
+            Thumb::Vertical => Rect::new(
+                viewport.origin.x + viewport.size.width - SCROLLBAR_WIDTH - SCROLLBAR_INSET,
+                viewport.origin.y + start,
+                SCROLLBAR_WIDTH,
+                size,
+            ),
+            Thumb::Horizontal => Rect::new(
+                viewport.origin.x + start,
+                viewport.origin.y + viewport.size.height - SCROLLBAR_WIDTH - SCROLLBAR_INSET,
+                size,
+                SCROLLBAR_WIDTH,
+            ),
+        })
+    }
+
+    fn thumb_at(&self, point: Point) -> Option<Thumb> {
+        [Thumb::Vertical, Thumb::Horizontal]
+            .into_iter()
+            .find(|&thumb| self.thumb_rect(thumb).is_some_and(|rect| rect.contains(point)))
+    }
+
+    // Scrolls so the thumb follows a pointer movement of `delta`.
+    fn drag_thumb(&mut self, thumb: Thumb, delta: Point) {
+        let Some(rect) = self.thumb_rect(thumb) else { return };
+        let viewport = self.viewport.size;
+        let max = self.max_offset();
+        match thumb {
+            Thumb::Vertical => {
+                let travel = viewport.height - SCROLLBAR_INSET * 2.0 - rect.size.height;
+                if travel > 0.0 {
+                    self.scroll_to(Point::new(self.offset.x, self.offset.y + delta.y * max.y / travel));
+                }
+            }
+            Thumb::Horizontal => {
+                let travel = viewport.width - SCROLLBAR_INSET * 2.0 - rect.size.width;
+                if travel > 0.0 {
+                    self.scroll_to(Point::new(self.offset.x + delta.x * max.x / travel, self.offset.y));
+                }
+            }
+        }
+    }
+
+    fn press(&mut self, point: Point, context: &mut EventContext) {
+        // Touching moving content stops it where it is.
+        self.velocity = Point::new(0.0, 0.0);
+        let thumb = self.thumb_at(point);
+        if thumb.is_some() {
+            context.stop_propagation();
+        }
+        self.drag = Some(Drag {
+            start: point,
+            last: point,
+            dragging: thumb.is_some(),
+            thumb,
+            samples: vec![(Instant::now(), self.offset)],
+        });
+    }
+
+    fn move_to(&mut self, point: Point, context: &mut EventContext) {
+        let Some(drag) = &mut self.drag else { return };
+        if !drag.dragging {
+            let distance = |from: f32, to: f32, enabled: bool| if enabled { (to - from).abs() } else { 0.0 };
+            let moved = distance(drag.start.x, point.x, self.axis.horizontal())
+                .max(distance(drag.start.y, point.y, self.axis.vertical()));
+            if moved < DRAG_THRESHOLD {
+                return;
+            }
+            drag.dragging = true;
+        }
+        let delta = Point::new(point.x - drag.last.x, point.y - drag.last.y);
+        drag.last = point;
+        let thumb = drag.thumb;
+
+        match thumb {
+            Some(thumb) => self.drag_thumb(thumb, delta),
+            // Dragging the content moves it with the pointer.
+            None => self.drag_by(Point::new(-delta.x, -delta.y)),
+        }
+        if let Some(drag) = &mut self.drag {
+            let now = Instant::now();
+            drag.samples.retain(|(time, _)| now - *time <= VELOCITY_WINDOW);
+            drag.samples.push((now, self.offset));
+        }
+        context.stop_propagation();
+    }
+
+    fn release(&mut self, context: &mut EventContext) {
+        let Some(drag) = self.drag.take() else { return };
+        if drag.dragging {
+            if drag.thumb.is_none() {
+                self.velocity = Self::release_velocity(&drag.samples);
+            }
+            self.suppress_click = true;
+            context.stop_propagation();
+        }
+    }
+
+    // A wheel or trackpad delta. Vertical wheels scroll horizontal-only
+    // content sideways.
+    fn wheel(&mut self, delta: Point, context: &mut EventContext) {
+        let delta = match self.axis {
+            ScrollAxis::Horizontal if delta.x == 0.0 => Point::new(delta.y, 0.0),
+            _ => delta,
+        };
+        let previous = self.offset;
+        self.scroll_by(delta);
+        if self.offset != previous {
+            context.stop_propagation();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scroller() -> Scroller {
+        let mut scroller = Scroller::new(ScrollAxis::Vertical);
+        scroller.scrollbars = ScrollbarVisibility::Never;
+        scroller.set_extent(Rect::new(0.0, 0.0, 100.0, 100.0), Size::new(100.0, 1000.0));
+        scroller
+    }
+
+    fn send(scroller: &mut Scroller, event: Event) -> EventContext {
+        let mut context = EventContext::new(EventPhase::Target, event.position());
+        scroller.handle_event(&event, &mut context);
+        context
+    }
+
+    fn settle(scroller: &mut Scroller) -> usize {
+        let mut frames = 0;
+        while scroller.is_animating() && frames < 1000 {
+            send(scroller, Event::Tick(Duration::from_millis(16)));
+            frames += 1;
+        }
+        frames
+    }
+
+    #[test]
+    fn momentum_decays_to_a_stop() {
+        let mut scroller = scroller();
+        scroller.velocity = Point::new(0.0, 600.0);
+        send(&mut scroller, Event::Tick(Duration::from_millis(100)));
+        let after = scroller.offset().y;
+        assert!(after > 0.0 && scroller.velocity.y < 600.0);
+
+        assert!(settle(&mut scroller) < 1000);
+        // The total distance is at most v0 / friction.
+        assert!(scroller.offset().y <= 600.0 / FRICTION);
+        assert!(scroller.offset().y > after);
+    }
+
+    #[test]
+    fn overscroll_springs_back_to_the_edge() {
+        let mut scroller = scroller();
+        scroller.shift(Point::new(0.0, -60.0));
+        assert!(scroller.is_animating());
+        assert!(settle(&mut scroller) < 1000);
+        assert_eq!(scroller.offset().y, 0.0);
+
+        scroller.shift(Point::new(0.0, 960.0));
+        settle(&mut scroller);
+        assert_eq!(scroller.offset().y, 900.0);
+    }
+
+    #[test]
+    fn momentum_stops_at_the_edge_without_bounce() {
+        let mut scroller = scroller();
+        scroller.bounce = false;
+        scroller.scroll_to(Point::new(0.0, 10.0));
+        scroller.velocity = Point::new(0.0, -600.0);
+        send(&mut scroller, Event::Tick(Duration::from_millis(200)));
+        assert_eq!(scroller.offset().y, 0.0);
+        assert!(!scroller.is_animating());
+    }
+
+    #[test]
+    fn small_movements_do_not_start_a_drag() {
+        let mut scroller = scroller();
+        scroller.scroll_to(Point::new(0.0, 100.0));
+        send(&mut scroller, Event::MouseDown(Point::new(50.0, 50.0)));
+        let context = send(&mut scroller, Event::MouseMove(Point::new(50.0, 50.0 - DRAG_THRESHOLD / 2.0)));
+        assert!(!context.is_propagation_stopped());
+        assert_eq!(scroller.offset().y, 100.0);
+        send(&mut scroller, Event::MouseUp(Point::new(50.0, 48.0)));
+        let click = send(&mut scroller, Event::Click { x: 50.0, y: 48.0 });
+        assert!(!click.is_propagation_stopped());
+    }
+
+    #[test]
+    fn dragging_moves_the_content_and_swallows_the_click() {
+        let mut scroller = scroller();
+        scroller.scroll_to(Point::new(0.0, 100.0));
+        send(&mut scroller, Event::MouseDown(Point::new(50.0, 50.0)));
+        let context = send(&mut scroller, Event::MouseMove(Point::new(50.0, 30.0)));
+        assert!(context.is_propagation_stopped());
+        assert_eq!(scroller.offset().y, 120.0);
+        send(&mut scroller, Event::MouseUp(Point::new(50.0, 30.0)));
+        assert!(send(&mut scroller, Event::Click { x: 50.0, y: 30.0 }).is_propagation_stopped());
+    }
+
+    #[test]
+    fn dragging_past_the_edge_resists() {
+        let mut scroller = scroller();
+        send(&mut scroller, Event::MouseDown(Point::new(50.0, 10.0)));
+        send(&mut scroller, Event::MouseMove(Point::new(50.0, 50.0)));
+        assert_eq!(scroller.offset().y, -40.0 * RUBBER_BAND);
+        assert!(!scroller.is_animating());
+        send(&mut scroller, Event::MouseUp(Point::new(50.0, 50.0)));
+        settle(&mut scroller);
+        assert_eq!(scroller.offset().y, 0.0);
+    }
+
+    #[test]
+    fn wheel_scrolling_is_clamped() {
+        let mut scroller = scroller();
+        let delta = Point::new(0.0, 5000.0);
+        let context = send(&mut scroller, Event::Scroll { position: Point::new(10.0, 10.0), delta });
+        assert!(context.is_propagation_stopped());
+        assert_eq!(scroller.offset().y, 900.0);
+        let context = send(&mut scroller, Event::Scroll { position: Point::new(10.0, 10.0), delta });
+        assert!(!context.is_propagation_stopped());
+    }
+}
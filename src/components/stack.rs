@@ -1,7 +1,12 @@
-use crate::geometry::{Rect, Point, Size};
+use crate::geometry::{Rect, Size};
+use crate::layout::{
+    absolute_constraints, absolute_rect, content_box, content_insets, is_in_flow, measure_styled, relative_rect,
+    Constraints, DEFAULT_SPACING,
+};
 use crate::renderer::Renderer;
-use crate::event::Event;
-use super::Component;
+use crate::style::{LayoutDirection, Style};
+use crate::event::{Event, EventContext};
+use super::{paint_order, Component};
 
 #[derive(Debug, Clone, Copy)]
 pub enum Direction {
@@ -9,13 +14,13 @@ pub enum Direction {
     Vertical,
 }
 
-#[derive(Clone)]
+// Places children one after another at their measured size, `spacing` apart.
 pub struct Stack {
     direction: Direction,
     children: Vec<Box<dyn Component>>,
     spacing: f32,
+    style: Style,
     bounds: Rect,
-    child_positions: Vec<Point>,
 }
 
 impl Stack {
@@ -23,75 +28,143 @@ impl Stack {
         Self {
             direction,
             children: Vec::new(),
-            spacing: 8.0,
-            bounds: Rect {
-                origin: Point::new(0.0, 0.0),
-                size: Size { width: 0.0, height: 0.0 },
-            },
-            child_positions: Vec::new(),
+            spacing: DEFAULT_SPACING,
+            style: Style::default(),
+            bounds: Rect::default(),
         }
     }
 
     pub fn spacing(mut self, spacing: f32) -> Self {
         self.spacing = spacing;
-        self.recalculate_layout();
         self
     }
 
     pub fn child<C: Component + 'static>(mut self, child: C) -> Self {
         self.children.push(Box::new(child));
-        self.recalculate_layout();
         self
     }
 
-    fn recalculate_layout(&mut self) {
-        let mut width: f32 = 0.0;
-        let mut height: f32 = 0.0;
-        self.child_positions.clear();
-        
-        let mut current_x: f32 = self.bounds.origin.x;
-        let mut current_y: f32 = self.bounds.origin.y;
-
-        for child in self.children.iter() {
-            let child_bounds = child.bounds();
-            
-            match self.direction {
-                Direction::Horizontal => {
-                    self.child_positions.push(Point::new(current_x, current_y));
-                    current_x += child_bounds.size.width + self.spacing;
-                    width = current_x - self.spacing;
-                    height = height.max(child_bounds.size.height);
-                }
-                Direction::Vertical => {
-                    self.child_positions.push(Point::new(current_x, current_y));
-                    current_y += child_bounds.size.height + self.spacing;
-                    height = current_y - self.spacing;
-                    width = width.max(child_bounds.size.width);
-                }
-            }
-        }
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
 
-        self.bounds.size = Size { width, height };
+    fn child_constraints(&self, available: Size, child: &dyn Component) -> Constraints {
+        let margin = child.style().margin;
+        let constraints = match self.direction {
+            Direction::Horizontal => Constraints::loose(Size::new(f32::INFINITY, (available.height - margin.vertical()).max(0.0))),
+            Direction::Vertical => Constraints::loose(Size::new((available.width - margin.horizontal()).max(0.0), f32::INFINITY)),
+        };
+        constraints.with_percent_basis(available)
     }
 }
 
 impl Component for Stack {
-    fn render(&self, renderer: &mut Renderer) {
-        for (child, position) in self.children.iter().zip(self.child_positions.iter()) {
-            let mut child_bounds = child.bounds();
-            child_bounds.origin = *position;
-            // Update child position before rendering
-            child.render(renderer);
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.begin_group(&self.style, &self.bounds);
+        for index in paint_order(&self.children) {
+            self.children[index].render(renderer);
         }
+        renderer.end_group();
+    }
+
+    fn handle_event(&mut self, _event: &Event, _context: &mut EventContext) {}
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let available = constraints.deflate(insets.horizontal(), insets.vertical()).max;
+            let mut main: f32 = 0.0;
+            let mut cross: f32 = 0.0;
+            let mut count = 0;
+
+            for child in self.children.iter().filter(|child| is_in_flow(child.style())) {
+                count += 1;
+                let margin = child.style().margin;
+                let size = child.measure(self.child_constraints(available, child.as_ref()));
+                let (child_main, child_cross) = match self.direction {
+                    Direction::Horizontal => (size.width + margin.horizontal(), size.height + margin.vertical()),
+                    Direction::Vertical => (size.height + margin.vertical(), size.width + margin.horizontal()),
+                };
+                main += child_main;
+                cross = cross.max(child_cross);
+            }
+            main += self.spacing * (count as f32 - 1.0).max(0.0);
+
+            let content = match self.direction {
+                Direction::Horizontal => Size::new(main, cross),
+                Direction::Vertical => Size::new(cross, main),
+            };
+            Size::new(content.width + insets.horizontal(), content.height + insets.vertical())
+        })
     }
 
-    fn handle_event(&mut self, event: Event) {
-        for child in &mut self.children {
-            child.handle_event(event);
+    fn arrange(&mut self, bounds: Rect) {
+        let content = content_box(&self.style, &bounds);
+        let mut cursor = 0.0;
+
+        for index in 0..self.children.len() {
+            let child = &self.children[index];
+            if !is_in_flow(child.style()) {
+                let size = child.measure(absolute_constraints(child.style(), &bounds));
+                let rect = absolute_rect(child.style(), size, &bounds, self.style.direction);
+                self.children[index].arrange(rect);
+                continue;
+            }
+            let margin = child.style().margin;
+            let size = child.measure(self.child_constraints(content.size, child.as_ref()));
+            let rect = match self.direction {
+                // Right-to-left stacks fill from the right edge.
+                Direction::Horizontal if self.style.direction == LayoutDirection::Rtl => Rect::new(
+                    content.origin.x + content.size.width - cursor - margin.right - size.width,
+                    content.origin.y + margin.top,
+                    size.width,
+                    size.height,
+                ),
+                Direction::Horizontal => Rect::new(
+                    content.origin.x + cursor + margin.left,
+                    content.origin.y + margin.top,
+                    size.width,
+                    size.height,
+                ),
+                Direction::Vertical => Rect::new(
+                    content.origin.x + margin.left,
+                    content.origin.y + cursor + margin.top,
+                    size.width,
+                    size.height,
+                ),
+            };
+            cursor += match self.direction {
+                Direction::Horizontal => size.width + margin.horizontal(),
+                Direction::Vertical => size.height + margin.vertical(),
+            } + self.spacing;
+            let rect = relative_rect(child.style(), rect, &bounds, self.style.direction);
+            self.children[index].arrange(rect);
         }
+        self.bounds = bounds;
     }
 
-    fn bounds(&self) -> Rect {
-        self.bounds
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn children(&self) -> &[Box<dyn Component>] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Component>] {
+        &mut self.children
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
     }
 }
@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use crate::style::Style;
+use crate::renderer::Renderer;
+use crate::event::{Event, EventContext};
+use crate::geometry::{Rect, Size};
+use crate::layout::{content_box, content_insets, measure_styled, Constraints};
+use crate::svg::SvgDocument;
+use crate::texture::ImageSource;
+use super::image::{fit_natural_size, ContentFit};
+use super::Component;
+
+// Draws an SVG document as vector paths, so it stays sharp at any size.
+// A non-transparent `Style::color` repaints every fill and stroke in that
+// color, which suits single-color icon sets. When parsing fails the
+// component draws only its style and reports the failure from `error`.
+pub struct Svg {
+    document: Option<Arc<SvgDocument>>,
+    error: Option<String>,
+    fit: ContentFit,
+    size: Option<Size>,
+    style: Style,
+    bounds: Rect,
+}
+
+impl Svg {
+    pub fn new(source: ImageSource) -> Self {
+        match SvgDocument::load(&source) {
+            Ok(document) => Self::from_document(Arc::new(document)),
+            Err(error) => {
+                let mut svg = Self::empty();
+                svg.error = Some(error.to_string());
+                svg
+            }
+        }
+    }
+
+    pub fn from_file<P: Into<PathBuf>>(path: P) -> Self {
+        Self::new(ImageSource::file(path))
+    }
+
+    pub fn from_bytes<B: Into<Arc<[u8]>>>(bytes: B) -> Self {
+        Self::new(ImageSource::bytes(bytes))
+    }
+
+    // Shares an already parsed document, e.g. one icon shown many times.
+    pub fn from_document(document: Arc<SvgDocument>) -> Self {
+        let mut svg = Self::empty();
+        svg.document = Some(document);
+        svg
+    }
+
+    fn empty() -> Self {
+        Self {
+            document: None,
+            error: None,
+            fit: ContentFit::Contain,
+            size: None,
+            style: Style::default(),
+            bounds: Rect::default(),
+        }
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    // Defaults to `ContentFit::Contain`.
+    pub fn with_fit(mut self, fit: ContentFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    // The content size to ask for instead of the document's own, e.g. the
+    // icon size.
+    pub fn with_size(mut self, size: Size) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn document(&self) -> Option<&Arc<SvgDocument>> {
+        self.document.as_ref()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+impl Component for Svg {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.begin_group(&self.style, &self.bounds);
+        if let Some(document) = &self.document {
+            let content = content_box(&self.style, &self.bounds);
+            let rect = self.fit.place(document.size(), &content);
+            let color = Some(self.style.color).filter(|color| color.a > 0.0);
+            renderer.push_clip(&content);
+            document.draw(renderer, &rect, color);
+            renderer.pop_clip();
+        }
+        renderer.end_group();
+    }
+
+    fn handle_event(&mut self, _event: &Event, _context: &mut EventContext) {}
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    // The requested or natural size, scaled down to fit the constraints
+    // while keeping the aspect ratio.
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let available = constraints.deflate(insets.horizontal(), insets.vertical()).max;
+            let natural = self.size
+                .or_else(|| self.document.as_ref().map(|document| document.size()))
+                .unwrap_or(Size::new(0.0, 0.0));
+            let size = fit_natural_size(natural, available);
+            Size::new(size.width + insets.horizontal(), size.height + insets.vertical())
+        })
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    fn style_name(&self) -> &str {
+        "svg"
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+}
@@ -1,7 +1,9 @@
 use crate::style::Style;
 use crate::renderer::Renderer;
-use crate::event::Event;
-use crate::geometry::Rect;
+use crate::event::{Event, EventContext};
+use crate::geometry::{Rect, Size};
+use crate::layout::{content_insets, measure_styled, Constraints};
+use crate::text;
 use super::Component;
 
 pub struct Text {
@@ -27,17 +29,36 @@ impl Text {
 
 impl Component for Text {
     fn render(&self, renderer: &mut dyn Renderer) {
-        renderer.begin_group(&self.style);
+        renderer.begin_group(&self.style, &self.bounds);
         renderer.draw_text(&self.content, &self.style);
         renderer.end_group();
     }
 
-    fn handle_event(&mut self, _event: Event) {}
+    fn handle_event(&mut self, _event: &Event, _context: &mut EventContext) {}
 
     fn bounds(&self) -> Rect {
         self.bounds.clone()
     }
 
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let available = constraints.max.width - insets.horizontal();
+            let text = text::layout_paragraph(&self.content, &self.style, available).size();
+            Size::new(text.width + insets.horizontal(), text.height + insets.vertical())
+        })
+    }
+
+    fn min_content_width(&self) -> f32 {
+        let insets = content_insets(&self.style);
+        let width = text::min_content_width(&self.content, &self.style);
+        measure_styled(&self.style, Constraints::unbounded(), |_| Size::new(width + insets.horizontal(), 0.0)).width
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+    }
+
     fn apply_style(&mut self, style: Style) {
         self.style = style;
     }
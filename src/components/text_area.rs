@@ -0,0 +1,809 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use crate::style::{Border, BorderSide, Color, Style, TextAlign, TextWrap};
+use crate::renderer::Renderer;
+use crate::event::{Event, EventContext, EventPhase, KeyCode, KeyEvent};
+use crate::geometry::{Point, Rect, Size};
+use crate::layout::{content_box, content_insets, measure_styled, Constraints};
+use crate::font::{self, ShapedLine};
+use crate::text::{self, Document, Edit, TextPosition};
+use super::Component;
+
+type TextCallback = Arc<dyn Fn(&str) + Send + Sync>;
+type ChangeCallback = Arc<dyn Fn(&Document) + Send + Sync>;
+type PasteCallback = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
+const DEFAULT_WIDTH: f32 = 300.0;
+const DEFAULT_ROWS: usize = 5;
+const CARET_WIDTH: f32 = 1.0;
+const HISTORY_LIMIT: usize = 1000;
+
+// A multi-line text editor. Lines are soft-wrapped to the content width and
+// only the visual rows inside the viewport are drawn, so large documents
+// cost little more than small ones to render and edit.
+pub struct TextArea {
+    document: Document,
+    style: Style,
+    selection_color: Color,
+    bounds: Rect,
+    caret: TextPosition,
+    anchor: TextPosition,
+    // The x position Up and Down aim for, kept across short lines.
+    goal_x: Option<f32>,
+    preedit: String,
+    visible_rows: usize,
+    soft_wrap: bool,
+    wrap_width: f32,
+    // Start offsets of each line's visual rows, and the index of each line's
+    // first row with the total row count as the last entry.
+    line_rows: Vec<Vec<usize>>,
+    row_offsets: Vec<usize>,
+    scroll_y: f32,
+    undo_stack: VecDeque<Edit>,
+    redo_stack: Vec<Edit>,
+    // Whether the next typed text may join the last undo step.
+    coalesce: bool,
+    focused: bool,
+    dragging: bool,
+    pressed: bool,
+    on_change: Option<ChangeCallback>,
+    on_copy: Option<TextCallback>,
+    on_paste: Option<PasteCallback>,
+}
+
+impl TextArea {
+    pub fn new() -> Self {
+        let mut area = Self {
+            document: Document::new(""),
+            style: Style::default()
+                .set_background(Color::WHITE)
+                .set_color(Color::BLACK)
+                .set_padding(8.0)
+                .set_border(Border::all(BorderSide::solid(1.0, Color::rgb(0.6, 0.6, 0.6)))),
+            selection_color: Color::rgba(0.2, 0.6, 1.0, 0.4),
+            bounds: Rect::default(),
+            caret: TextPosition::default(),
+            anchor: TextPosition::default(),
+            goal_x: None,
+            preedit: String::new(),
+            visible_rows: DEFAULT_ROWS,
+            soft_wrap: true,
+            wrap_width: f32::INFINITY,
+            line_rows: Vec::new(),
+            row_offsets: Vec::new(),
+            scroll_y: 0.0,
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            coalesce: false,
+            focused: false,
+            dragging: false,
+            pressed: false,
+            on_change: None,
+            on_copy: None,
+            on_paste: None,
+        };
+        area.rewrap_all();
+        area
+    }
+
+    pub fn with_text<S: Into<String>>(mut self, text: S) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self.rewrap_all();
+        self
+    }
+
+    // The number of rows the editor asks for when measured.
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.visible_rows = rows.max(1);
+        self
+    }
+
+    pub fn soft_wrap(mut self, enabled: bool) -> Self {
+        self.soft_wrap = enabled;
+        self.rewrap_all();
+        self
+    }
+
+    pub fn selection_color(mut self, color: Color) -> Self {
+        self.selection_color = color;
+        self
+    }
+
+    // Called with the document after every edit, undo and redo. Its lines
+    // are kept separately, so call `Document::text` only when the whole
+    // text is needed.
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Document) + Send + Sync + 'static
+    {
+        self.on_change = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_copy<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static
+    {
+        self.on_copy = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_paste<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() -> Option<String> + Send + Sync + 'static
+    {
+        self.on_paste = Some(Arc::new(callback));
+        self
+    }
+
+    // Replaces the whole text without calling `on_change` and clears the
+    // undo history.
+    pub fn set_text<S: Into<String>>(&mut self, text: S) {
+        self.document = Document::new(&sanitize(&text.into()));
+        self.caret = TextPosition::default();
+        self.anchor = self.caret;
+        self.goal_x = None;
+        self.preedit.clear();
+        self.scroll_y = 0.0;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.coalesce = false;
+        self.rewrap_all();
+    }
+
+    pub fn text(&self) -> String {
+        self.document.text()
+    }
+
+    pub fn document(&self) -> &Document {
+        &self.document
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.document.line_count()
+    }
+
+    pub fn line(&self, index: usize) -> Option<&str> {
+        self.document.lines().get(index).map(String::as_str)
+    }
+
+    pub fn caret(&self) -> TextPosition {
+        self.caret
+    }
+
+    // The selected range in document order.
+    pub fn selection(&self) -> (TextPosition, TextPosition) {
+        (self.caret.min(self.anchor), self.caret.max(self.anchor))
+    }
+
+    pub fn selected_text(&self) -> String {
+        let (start, end) = self.selection();
+        self.document.slice(start, end)
+    }
+
+    // Selects from `anchor` to `caret`, clamped into the document.
+    pub fn select(&mut self, anchor: TextPosition, caret: TextPosition) {
+        self.anchor = self.clamp(anchor);
+        self.move_to(self.clamp(caret), true);
+    }
+
+    pub fn select_all(&mut self) {
+        self.select(TextPosition::default(), self.document.end());
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(edit) = self.undo_stack.pop_back() else { return false };
+        let replaced = edit.inserted_end().line - edit.start.line + 1;
+        self.document.revert(&edit);
+        self.rewrap_lines(edit.start.line, replaced, edit.removed_end().line - edit.start.line + 1);
+        self.move_to(edit.removed_end(), false);
+        self.redo_stack.push(edit);
+        self.changed();
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(edit) = self.redo_stack.pop() else { return false };
+        let replaced = edit.removed_end().line - edit.start.line + 1;
+        self.document.reapply(&edit);
+        self.rewrap_lines(edit.start.line, replaced, edit.inserted_end().line - edit.start.line + 1);
+        self.move_to(edit.inserted_end(), false);
+        self.undo_stack.push_back(edit);
+        self.changed();
+        true
+    }
+
+    pub fn scroll_offset(&self) -> f32 {
+        self.scroll_y
+    }
+
+    pub fn scroll_to(&mut self, offset: f32) {
+        self.scroll_y = offset.clamp(0.0, self.max_scroll());
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn line_height(&self) -> f32 {
+        font::line_metrics(&self.style).line_height
+    }
+
+    fn content(&self) -> Rect {
+        content_box(&self.style, &self.bounds)
+    }
+
+    fn clamp(&self, position: TextPosition) -> TextPosition {
+        let line = position.line.min(self.document.line_count() - 1);
+        let text = self.document.line(line);
+        let column = text::grapheme_boundaries(text)
+            .into_iter()
+            .rev()
+            .find(|&boundary| boundary <= position.column)
+            .unwrap_or(0);
+        TextPosition::new(line, column)
+    }
+
+    fn wrap(&self, line: &str) -> Vec<usize> {
+        let wrap = if self.soft_wrap { TextWrap::Word } else { TextWrap::NoWrap };
+        text::wrap_line(line, self.wrap_width, &self.style, wrap)
+    }
+
+    fn rewrap_all(&mut self) {
+        self.line_rows = self.document.lines().iter().map(|line| self.wrap(line)).collect();
+        self.update_row_offsets();
+    }
+
+    // Rewraps after `removed` lines starting at `start` became `added` lines.
+    fn rewrap_lines(&mut self, start: usize, removed: usize, added: usize) {
+        let rows: Vec<_> = (start..start + added).map(|line| self.wrap(self.document.line(line))).collect();
+        self.line_rows.splice(start..start + removed, rows);
+        self.update_row_offsets();
+    }
+
+    fn update_row_offsets(&mut self) {
+        self.row_offsets.clear();
+        let mut total = 0;
+        for rows in &self.line_rows {
+            self.row_offsets.push(total);
+            total += rows.len();
+        }
+        self.row_offsets.push(total);
+    }
+
+    fn row_count(&self) -> usize {
+        self.row_offsets.last().copied().unwrap_or(0)
+    }
+
+    // The visual row holding `position`; a position at a wrap point belongs
+    // to the row that starts there.
+    fn row_of(&self, position: TextPosition) -> usize {
+        let rows = &self.line_rows[position.line];
+        self.row_offsets[position.line] + rows.partition_point(|&start| start <= position.column) - 1
+    }
+
+    // The line a visual row belongs to and the byte range it covers.
+    fn row_span(&self, row: usize) -> (usize, usize, usize) {
+        let line = self.row_offsets.partition_point(|&offset| offset <= row) - 1;
+        let rows = &self.line_rows[line];
+        let index = row - self.row_offsets[line];
+        let end = rows.get(index + 1).copied().unwrap_or(self.document.line(line).len());
+        (line, rows[index], end)
+    }
+
+    fn is_last_row_of_line(&self, row: usize) -> bool {
+        let (line, _, _) = self.row_span(row);
+        row + 1 == self.row_offsets[line + 1]
+    }
+
+    // Shapes a row as the renderers draw it.
+    fn shape_row(&self, row: usize) -> (usize, usize, usize, ShapedLine) {
+        let (line, start, end) = self.row_span(row);
+        let shaped = font::shape_line(&self.document.line(line)[start..end], &self.style);
+        (line, start, end, shaped)
+    }
+
+    // Where the renderers start drawing a row of `width`, given the alignment.
+    fn row_origin_x(&self, width: f32) -> f32 {
+        let available = self.content().size.width;
+        text::align_offset(&self.style, available - width)
+    }
+
+    // The x position of `position` relative to the content box.
+    fn position_x(&self, position: TextPosition) -> f32 {
+        let (_, start, _, shaped) = self.shape_row(self.row_of(position));
+        self.row_origin_x(shaped.width) + shaped.x_for_offset(position.column - start)
+    }
+
+    // The grapheme boundary in `row` nearest to `x`. The end of a wrapped row
+    // is excluded since that offset belongs to the next row.
+    fn position_in_row(&self, row: usize, x: f32) -> TextPosition {
+        let (line, start, end, shaped) = self.shape_row(row);
+        let text = self.document.line(line);
+        let mut column = start + shaped.offset_for_x(x - self.row_origin_x(shaped.width));
+        if column == end && !self.is_last_row_of_line(row) {
+            column = text::previous_grapheme(text, end).max(start);
+        }
+        TextPosition::new(line, column)
+    }
+
+    // The position nearest to `point`, in the space of `bounds`.
+    fn position_at(&self, point: Point) -> TextPosition {
+        let content = self.content();
+        let row = ((point.y - content.origin.y + self.scroll_y) / self.line_height()).floor().max(0.0) as usize;
+        self.position_in_row(row.min(self.row_count() - 1), point.x - content.origin.x)
+    }
+
+    fn move_to(&mut self, position: TextPosition, extend: bool) {
+        self.caret = position;
+        if !extend {
+            self.anchor = position;
+        }
+        self.goal_x = None;
+        self.coalesce = false;
+        self.scroll_caret_into_view();
+    }
+
+    fn previous_position(&self, position: TextPosition, by_word: bool) -> TextPosition {
+        if position.column == 0 {
+            return match position.line {
+                0 => position,
+                line => TextPosition::new(line - 1, self.document.line(line - 1).len()),
+            };
+        }
+        let line = self.document.line(position.line);
+        let column = if by_word {
+            text::previous_word(line, position.column)
+        } else {
+            text::previous_grapheme(line, position.column)
+        };
+        TextPosition::new(position.line, column)
+    }
+
+    fn next_position(&self, position: TextPosition, by_word: bool) -> TextPosition {
+        let line = self.document.line(position.line);
+        if position.column == line.len() {
+            return match position.line + 1 < self.document.line_count() {
+                true => TextPosition::new(position.line + 1, 0),
+                false => position,
+            };
+        }
+        let column = if by_word {
+            text::next_word(line, position.column)
+        } else {
+            text::next_grapheme(line, position.column)
+        };
+        TextPosition::new(position.line, column)
+    }
+
+    // Moves the caret `rows` visual rows up (negative) or down, keeping its
+    // x position; past the first or last row it goes to the document edge.
+    fn move_vertically(&mut self, rows: isize, extend: bool) {
+        let x = self.goal_x.unwrap_or_else(|| self.position_x(self.caret));
+        let row = self.row_of(self.caret) as isize + rows;
+        let position = if row < 0 {
+            TextPosition::default()
+        } else if row as usize >= self.row_count() {
+            self.document.end()
+        } else {
+            self.position_in_row(row as usize, x)
+        };
+        self.move_to(position, extend);
+        self.goal_x = Some(x);
+    }
+
+    fn row_boundary(&self, end: bool) -> TextPosition {
+        let row = self.row_of(self.caret);
+        let (line, start, row_end) = self.row_span(row);
+        match (end, self.is_last_row_of_line(row)) {
+            (false, _) => TextPosition::new(line, start),
+            (true, true) => TextPosition::new(line, row_end),
+            (true, false) => TextPosition::new(line, text::previous_grapheme(self.document.line(line), row_end)),
+        }
+    }
+
+    fn max_scroll(&self) -> f32 {
+        (self.row_count() as f32 * self.line_height() - self.content().size.height).max(0.0)
+    }
+
+    fn scroll_caret_into_view(&mut self) {
+        let line_height = self.line_height();
+        let top = self.row_of(self.caret) as f32 * line_height;
+        let height = self.content().size.height;
+        if top < self.scroll_y {
+            self.scroll_y = top;
+        } else if top + line_height > self.scroll_y + height {
+            self.scroll_y = top + line_height - height;
+        }
+        self.scroll_to(self.scroll_y);
+    }
+
+    // Replaces the selection with `insert`. Consecutive typing is merged into
+    // one undo step until whitespace is typed or the caret moves.
+    fn replace_selection(&mut self, insert: &str, typing: bool) {
+        let (start, end) = self.selection();
+        let insert = sanitize(insert);
+        if start == end && insert.is_empty() {
+            return;
+        }
+        let edit = self.document.replace(start, end, &insert);
+        let inserted_end = edit.inserted_end();
+        self.rewrap_lines(start.line, end.line - start.line + 1, inserted_end.line - start.line + 1);
+        let coalesce = self.coalesce && typing;
+        self.move_to(inserted_end, false);
+        self.record(edit, coalesce);
+        self.coalesce = typing && !insert.chars().any(char::is_whitespace);
+        self.changed();
+    }
+
+    fn record(&mut self, edit: Edit, coalesce: bool) {
+        self.redo_stack.clear();
+        if let Some(last) = self.undo_stack.back_mut() {
+            if coalesce && last.removed.is_empty() && edit.removed.is_empty() && last.inserted_end() == edit.start {
+                last.inserted.push_str(&edit.inserted);
+                return;
+            }
+        }
+        self.undo_stack.push_back(edit);
+        if self.undo_stack.len() > HISTORY_LIMIT {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    fn changed(&mut self) {
+        self.scroll_caret_into_view();
+        if let Some(callback) = &self.on_change {
+            (callback)(&self.document);
+        }
+    }
+
+    fn delete(&mut self, forward: bool, by_word: bool) {
+        if self.caret == self.anchor {
+            self.anchor = if forward {
+                self.next_position(self.caret, by_word)
+            } else {
+                self.previous_position(self.caret, by_word)
+            };
+        }
+        self.replace_selection("", false);
+    }
+
+    fn copy(&self) {
+        if self.caret == self.anchor {
+            return;
+        }
+        if let Some(callback) = &self.on_copy {
+            (callback)(&self.selected_text());
+        }
+    }
+
+    fn paste(&mut self) {
+        if let Some(text) = self.on_paste.as_ref().and_then(|callback| callback()) {
+            self.replace_selection(&text, false);
+        }
+    }
+
+    // Returns whether the key was used by the editor.
+    fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let modifiers = key.modifiers;
+        let by_word = if cfg!(target_os = "macos") { modifiers.alt } else { modifiers.control };
+        let extend = modifiers.shift;
+        let (start, end) = self.selection();
+        let has_selection = start != end;
+
+        match key.code {
+            KeyCode::Left if has_selection && !extend => self.move_to(start, false),
+            KeyCode::Left => self.move_to(self.previous_position(self.caret, by_word), extend),
+            KeyCode::Right if has_selection && !extend => self.move_to(end, false),
+            KeyCode::Right => self.move_to(self.next_position(self.caret, by_word), extend),
+            KeyCode::Up => self.move_vertically(-1, extend),
+            KeyCode::Down => self.move_vertically(1, extend),
+            KeyCode::PageUp => self.move_vertically(-(self.page_rows() as isize), extend),
+            KeyCode::PageDown => self.move_vertically(self.page_rows() as isize, extend),
+            KeyCode::Home if modifiers.command() => self.move_to(TextPosition::default(), extend),
+            KeyCode::Home => self.move_to(self.row_boundary(false), extend),
+            KeyCode::End if modifiers.command() => self.move_to(self.document.end(), extend),
+            KeyCode::End => self.move_to(self.row_boundary(true), extend),
+            KeyCode::Backspace => self.delete(false, by_word),
+            KeyCode::Delete => self.delete(true, by_word),
+            KeyCode::Enter => self.replace_selection("\n", false),
+            KeyCode::A if modifiers.command() => self.select_all(),
+            KeyCode::C if modifiers.command() => self.copy(),
+            KeyCode::X if modifiers.command() => {
+                self.copy();
+                self.replace_selection("", false);
+            }
+            KeyCode::V if modifiers.command() => self.paste(),
+            KeyCode::Z if modifiers.command() && modifiers.shift => {
+                self.redo();
+            }
+            KeyCode::Z if modifiers.command() => {
+                self.undo();
+            }
+            KeyCode::Y if modifiers.command() => {
+                self.redo();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn page_rows(&self) -> usize {
+        ((self.content().size.height / self.line_height()).floor() as usize).max(1)
+    }
+
+    fn highlight(&self, renderer: &mut dyn Renderer, rect: Rect, color: Color) {
+        renderer.begin_group(&Style::default().set_background(color), &rect);
+        renderer.end_group();
+    }
+}
+
+impl Default for TextArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for TextArea {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        renderer.begin_group(&self.style, &self.bounds);
+
+        let content = self.content();
+        let line_height = self.line_height();
+        let first = (self.scroll_y / line_height).floor() as usize;
+        let last = (((self.scroll_y + content.size.height) / line_height).ceil() as usize).min(self.row_count());
+        let (selection_start, selection_end) = self.selection();
+        let row_style = Style::default();
+        // Rows at the edges are only partly inside the viewport.
+        renderer.push_clip(&content);
+
+        for row in first..last {
+            let (line, start, end) = self.row_span(row);
+            let text = &self.document.line(line)[start..end];
+            let y = content.origin.y + row as f32 * line_height - self.scroll_y;
+
+            let from = TextPosition::new(line, start).max(selection_start);
+            let to = TextPosition::new(line, end).min(selection_end);
+            if self.focused && from < to {
+                let x = self.position_x(from);
+                let rect = Rect::new(content.origin.x + x, y, self.position_x(to) - x, line_height);
+                self.highlight(renderer, rect, self.selection_color);
+            }
+
+            renderer.begin_group(&row_style, &Rect::new(content.origin.x, y, content.size.width, line_height));
+            renderer.draw_text(text, &self.style);
+            renderer.end_group();
+        }
+
+        if self.focused {
+            let y = content.origin.y + self.row_of(self.caret) as f32 * line_height - self.scroll_y;
+            let mut x = content.origin.x + self.position_x(self.caret);
+            if !self.preedit.is_empty() {
+                let width = font::text_width(&self.preedit, &self.style);
+                let preedit_style = Style::default().set_background(self.style.background);
+                renderer.begin_group(&preedit_style, &Rect::new(x, y, width, line_height));
+                renderer.draw_text(&self.preedit, &self.style.clone().set_text_align(TextAlign::Left));
+                renderer.end_group();
+                x += width;
+            }
+            self.highlight(renderer, Rect::new(x, y, CARET_WIDTH, line_height), self.style.color);
+        }
+
+        renderer.pop_clip();
+        renderer.end_group();
+    }
+
+    fn handle_event(&mut self, event: &Event, context: &mut EventContext) {
+        if context.phase() != EventPhase::Target {
+            return;
+        }
+        let position = context.position();
+        match event {
+            Event::Focus { .. } => self.focused = true,
+            Event::Blur => {
+                self.focused = false;
+                self.dragging = false;
+                self.preedit.clear();
+            }
+            Event::MouseDown(_) | Event::TouchStart(_) => {
+                if let Some(point) = position {
+                    self.move_to(self.position_at(point), false);
+                    self.dragging = true;
+                    self.pressed = true;
+                }
+            }
+            Event::MouseMove(_) if self.dragging => {
+                if let Some(point) = position {
+                    self.move_to(self.position_at(point), true);
+                }
+            }
+            Event::MouseUp(_) | Event::TouchEnd(_) => self.dragging = false,
+            // A click that follows a press has already placed the caret.
+            Event::Click { .. } if std::mem::take(&mut self.pressed) => {}
+            Event::Click { .. } => {
+                if let Some(point) = position {
+                    self.move_to(self.position_at(point), false);
+                }
+            }
+            Event::Scroll { delta, .. } => {
+                let previous = self.scroll_y;
+                self.scroll_to(self.scroll_y + delta.y);
+                if self.scroll_y != previous {
+                    context.stop_propagation();
+                }
+            }
+            Event::KeyDown(key) if self.focused && self.handle_key(key) => context.stop_propagation(),
+            Event::TextInput(text) if self.focused => {
+                // A composition is committed as its own undo step rather
+                // than merged with the typing around it.
+                let composed = !self.preedit.is_empty();
+                self.preedit.clear();
+                self.replace_selection(text, !composed);
+                context.stop_propagation();
+            }
+            Event::ImePreedit { text, .. } if self.focused => {
+                self.preedit = text.clone();
+                context.stop_propagation();
+            }
+            _ => {}
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds.clone()
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |_| {
+            let insets = content_insets(&self.style);
+            let height = self.visible_rows as f32 * self.line_height();
+            Size::new(DEFAULT_WIDTH + insets.horizontal(), height + insets.vertical())
+        })
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+        let width = self.content().size.width;
+        if width != self.wrap_width {
+            self.wrap_width = width;
+            self.rewrap_all();
+        }
+        self.scroll_to(self.scroll_y);
+    }
+
+    fn apply_style(&mut self, style: Style) {
+        self.style = style;
+        self.rewrap_all();
+    }
+
+    fn style_name(&self) -> &str {
+        "text_area"
+    }
+
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn style_mut(&mut self) -> &mut Style {
+        &mut self.style
+    }
+
+    fn style(&self) -> &Style {
+        &self.style
+    }
+
+    fn clip_rect(&self) -> Option<Rect> {
+        Some(self.content())
+    }
+}
+
+// Keeps newlines and tabs but drops other control characters.
+fn sanitize(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .chars()
+        .filter(|&c| c == '\n' || c == '\t' || !c.is_control())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::event::Modifiers;
+
+    fn send(area: &mut TextArea, event: Event) {
+        area.handle_event(&event, &mut EventContext::new(EventPhase::Target, None));
+    }
+
+    fn focused(text: &str) -> TextArea {
+        let mut area = TextArea::new().with_text(text);
+        area.arrange(Rect::new(0.0, 0.0, 300.0, 100.0));
+        send(&mut area, Event::Focus { visible: true });
+        area
+    }
+
+    fn type_text(area: &mut TextArea, text: &str) {
+        for c in text.chars() {
+            send(area, Event::TextInput(c.to_string()));
+        }
+    }
+
+    #[test]
+    fn typing_is_undone_a_word_at_a_time() {
+        let mut area = focused("");
+        type_text(&mut area, "hello world");
+        assert_eq!(area.text(), "hello world");
+
+        assert!(area.undo());
+        assert_eq!(area.text(), "hello ");
+        assert!(area.undo());
+        assert_eq!(area.text(), "");
+        assert!(!area.can_undo());
+
+        assert!(area.redo());
+        assert_eq!(area.text(), "hello ");
+        assert_eq!(area.caret(), TextPosition::new(0, 6));
+    }
+
+    #[test]
+    fn moving_the_caret_starts_a_new_undo_step() {
+        let mut area = focused("");
+        type_text(&mut area, "ab");
+        send(&mut area, Event::KeyDown(KeyEvent::new(KeyCode::Left, Modifiers::default())));
+        type_text(&mut area, "c");
+        assert_eq!(area.text(), "acb");
+        area.undo();
+        assert_eq!(area.text(), "ab");
+    }
+
+    #[test]
+    fn a_new_edit_clears_redo() {
+        let mut area = focused("");
+        type_text(&mut area, "a");
+        area.undo();
+        type_text(&mut area, "b");
+        assert!(!area.can_redo());
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut area = focused("");
+        for _ in 0..HISTORY_LIMIT + 10 {
+            type_text(&mut area, "\n");
+        }
+        let mut steps = 0;
+        while area.undo() {
+            steps += 1;
+        }
+        assert_eq!(steps, HISTORY_LIMIT);
+        assert_eq!(area.line_count(), 11);
+    }
+
+    #[test]
+    fn on_change_sees_the_document() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+        let mut area = TextArea::new().on_change(move |document: &Document| {
+            recorder.lock().unwrap().push(document.line_count());
+        });
+        area.arrange(Rect::new(0.0, 0.0, 300.0, 100.0));
+        send(&mut area, Event::Focus { visible: true });
+        send(&mut area, Event::TextInput("one\ntwo".to_string()));
+        send(&mut area, Event::KeyDown(KeyEvent::new(KeyCode::Backspace, Modifiers::default())));
+        assert_eq!(*seen.lock().unwrap(), [2, 2]);
+        assert_eq!(area.document().line(1), "tw");
+    }
+}
@@ -1,9 +1,12 @@
-use crate::style::Style;
-use crate::layout::Layout;
+use crate::style::{FlexWrap, LayoutDirection, Style};
+use crate::layout::{
+    absolute_constraints, absolute_rect, content_box, flex_layout, flex_size, grid_layout, grid_size, is_in_flow,
+    content_insets, measure_styled, relative_rect, Constraints, GridMeasure, Layout, LayoutItem,
+};
 use crate::renderer::Renderer;
-use crate::event::Event;
-use crate::geometry::Rect;
-use super::Component;
+use crate::event::{Event, EventContext};
+use crate::geometry::{Rect, Size};
+use super::{paint_order, Component};
 
 pub struct View {
     children: Vec<Box<dyn Component>>,
@@ -37,6 +40,11 @@ impl View {
         self
     }
 
+    pub fn with_direction(mut self, direction: LayoutDirection) -> Self {
+        self.style.direction = direction;
+        self
+    }
+
     pub fn style(&self) -> &Style {
         &self.style
     }
@@ -44,44 +52,140 @@ impl View {
     pub fn style_mut(&mut self) -> &mut Style {
         &mut self.style
     }
+
+    // In-flow children are measured without a bound along the main axis so
+    // flexing starts from their natural size; grid tracks size from unbounded
+    // content. Percentages still resolve against `available`. Layout measures
+    // them again through `measure_cross` once their main size is settled.
+    // Absolutely positioned children are skipped.
+    fn measure_children(&self, available: Size) -> Vec<Size> {
+        self.children
+            .iter()
+            .filter(|child| is_in_flow(child.style()))
+            .map(|child| {
+                let margin = child.style().margin;
+                let max = match &self.layout {
+                    Layout::Grid(_) => Size::new(f32::INFINITY, f32::INFINITY),
+                    layout if layout.is_horizontal() => Size::new(f32::INFINITY, available.height - margin.vertical()),
+                    _ => Size::new(available.width - margin.horizontal(), f32::INFINITY),
+                };
+                let constraints = Constraints::loose(Size::new(max.width.max(0.0), max.height.max(0.0)));
+                child.measure(constraints.with_percent_basis(available))
+            })
+            .collect()
+    }
+
+    // Measures `child` again once layout has fixed its extent along the main
+    // axis (its width, in a grid) and returns its size along the other one.
+    fn measure_cross(&self, child: &dyn Component, extent: f32, available: Size) -> f32 {
+        let margin = child.style().margin;
+        let constraints = match &self.layout {
+            Layout::Grid(_) => Constraints::new(Size::new(extent, 0.0), Size::new(extent, f32::INFINITY)),
+            layout if layout.is_horizontal() => Constraints::new(
+                Size::new(extent, 0.0),
+                Size::new(extent, (available.height - margin.vertical()).max(0.0)),
+            ),
+            _ => Constraints::new(
+                Size::new(0.0, extent),
+                Size::new((available.width - margin.horizontal()).max(0.0), extent),
+            ),
+        };
+        let size = child.measure(constraints.with_percent_basis(available));
+        if matches!(self.layout, Layout::Column | Layout::ColumnReverse) { size.width } else { size.height }
+    }
+
+    fn in_flow_children(&self) -> Vec<&dyn Component> {
+        self.children.iter().filter(|child| is_in_flow(child.style())).map(|child| child.as_ref()).collect()
+    }
+
+    fn layout_items(&self, sizes: &[Size]) -> Vec<LayoutItem<'_>> {
+        self.children
+            .iter()
+            .filter(|child| is_in_flow(child.style()))
+            .zip(sizes)
+            .map(|(child, size)| LayoutItem { style: child.style(), size: *size })
+            .collect()
+    }
 }
 
 impl Component for View {
     fn render(&self, renderer: &mut dyn Renderer) {
-        renderer.begin_group(&self.style);
-        
-        match self.layout {
-            Layout::Row => {
-                let mut x = 0.0;
-                for child in &self.children {
-                    renderer.translate(x, 0.0);
-                    child.render(renderer);
-                    x += self.style.get_gap();
-                }
-            }
-            Layout::Column => {
-                let mut y = 0.0;
-                for child in &self.children {
-                    renderer.translate(0.0, y);
-                    child.render(renderer);
-                    y += self.style.get_gap();
-                }
-            }
+        renderer.begin_group(&self.style, &self.bounds);
+        for index in paint_order(&self.children) {
+            self.children[index].render(renderer);
         }
-        
         renderer.end_group();
     }
 
-    fn handle_event(&mut self, event: Event) {
-        for child in &mut self.children {
-            child.handle_event(event.clone());
-        }
-    }
+    fn handle_event(&mut self, _event: &Event, _context: &mut EventContext) {}
 
     fn bounds(&self) -> Rect {
         self.bounds.clone()
     }
 
+    fn measure(&self, constraints: Constraints) -> Size {
+        measure_styled(&self.style, constraints, |constraints| {
+            let insets = content_insets(&self.style);
+            let available = constraints.deflate(insets.horizontal(), insets.vertical()).max;
+            let sizes = self.measure_children(available);
+            let items = self.layout_items(&sizes);
+            let children = self.in_flow_children();
+            let cross = |index: usize, extent: f32| self.measure_cross(children[index], extent, available);
+            let content = match &self.layout {
+                Layout::Grid(template) => {
+                    let min_width = |index: usize| children[index].min_content_width();
+                    let measure = GridMeasure { min_width_for: min_width, height_for: cross };
+                    grid_size(template, &self.style, &items, available, measure)
+                }
+                layout => flex_size(layout, &self.style, &items, available, cross),
+            };
+            Size::new(content.width + insets.horizontal(), content.height + insets.vertical())
+        })
+    }
+
+    // Rows that do not wrap need their children side by side; columns,
+    // grids and wrapping rows only need the widest child.
+    fn min_content_width(&self) -> f32 {
+        let children = self.in_flow_children();
+        let widths = children.iter().map(|child| child.min_content_width() + child.style().margin.horizontal());
+        let content = if self.layout.is_horizontal() && self.style.flex_wrap == FlexWrap::NoWrap {
+            widths.sum::<f32>() + self.style.gap * (children.len() as f32 - 1.0).max(0.0)
+        } else {
+            widths.fold(0.0, f32::max)
+        };
+        let insets = content_insets(&self.style);
+        measure_styled(&self.style, Constraints::unbounded(), |_| Size::new(content + insets.horizontal(), 0.0)).width
+    }
+
+    fn arrange(&mut self, bounds: Rect) {
+        let available = content_box(&self.style, &bounds).size;
+        let sizes = self.measure_children(available);
+        let items = self.layout_items(&sizes);
+        let children = self.in_flow_children();
+        let cross = |index: usize, extent: f32| self.measure_cross(children[index], extent, available);
+        let rects = match &self.layout {
+            Layout::Grid(template) => {
+                let min_width = |index: usize| children[index].min_content_width();
+                let measure = GridMeasure { min_width_for: min_width, height_for: cross };
+                grid_layout(template, &self.style, &bounds, &items, measure)
+            }
+            layout => flex_layout(layout, &self.style, &bounds, &items, cross),
+        };
+
+        let mut rects = rects.into_iter();
+        for child in &mut self.children {
+            let rect = if is_in_flow(child.style()) {
+                let rect = rects.next().unwrap_or_default();
+                relative_rect(child.style(), rect, &bounds, self.style.direction)
+            } else {
+                let size = child.measure(absolute_constraints(child.style(), &bounds));
+                absolute_rect(child.style(), size, &bounds, self.style.direction)
+            };
+            child.arrange(rect);
+        }
+        self.bounds = bounds;
+    }
+
     fn apply_style(&mut self, style: Style) {
         self.style = style;
     }
@@ -93,4 +197,44 @@ impl Component for View {
     fn style_mut(&mut self) -> &mut Style {
         &mut self.style
     }
+
+    fn children(&self) -> &[Box<dyn Component>] {
+        &self.children
+    }
+
+    fn children_mut(&mut self) -> &mut [Box<dyn Component>] {
+        &mut self.children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Text;
+    use crate::style::{Dimension, TextWrap};
+
+    #[test]
+    fn wrapped_text_in_a_row_gets_the_height_of_its_lines() {
+        let text = Text::new("one two three four five six seven eight")
+            .with_style(Style::default().set_text_wrap(TextWrap::Word).set_font_size(16.0));
+        let line_height = text.measure(Constraints::unbounded()).height;
+        let mut row = View::new().with_layout(Layout::Row).child(text);
+
+        let size = row.measure(Constraints::loose(Size::new(100.0, 1000.0)));
+        row.arrange(Rect::new(0.0, 0.0, size.width, size.height));
+        let child = row.children()[0].bounds();
+        assert!(child.size.width <= 100.0);
+        assert!(child.size.height > line_height * 2.0);
+        assert_eq!(size.height, child.size.height);
+    }
+
+    #[test]
+    fn percent_sizes_resolve_against_the_parent() {
+        let half = || View::new().with_style(Style::default().set_width(Dimension::Percent(50.0)).set_height(Dimension::Points(10.0)));
+        for layout in [Layout::Row, Layout::Column] {
+            let mut parent = View::new().with_layout(layout).child(half());
+            parent.arrange(Rect::new(0.0, 0.0, 200.0, 100.0));
+            assert_eq!(parent.children()[0].bounds().size, Size::new(100.0, 10.0));
+        }
+    }
 }
@@ -0,0 +1,320 @@
+use crate::components::Component;
+use crate::style::LayoutDirection;
+use super::{component_at_path, dispatch, dispatch_to, hit_test, Event, KeyCode};
+
+// Tracks which component has keyboard focus, by its path of child indices
+// from the root. Key events go to the focused component; Tab and Shift-Tab
+// move focus through focusable components in reading order: top to bottom by
+// their arranged bounds, then along the root's inline direction. The path
+// follows children that move within a parent that keys them, such as the
+// rows of a scrolling `List`.
+//
+// It also captures the pointer: after a press, moves and the release go to
+// the pressed component even once the pointer has left it, so drags always
+// see their end.
+#[derive(Clone, Debug, Default)]
+pub struct FocusManager {
+    focused: Option<TrackedPath>,
+    focus_visible: bool,
+    // The component under the last press, until it is released.
+    pressed: Option<TrackedPath>,
+}
+
+// A path of child indices from the root, with the key of each step whose
+// parent reports one, so the path can follow children that move.
+#[derive(Clone, Debug)]
+struct TrackedPath {
+    path: Vec<usize>,
+    keys: Vec<Option<u64>>,
+}
+
+impl TrackedPath {
+    fn new(root: &dyn Component, path: &[usize]) -> Self {
+        let mut keys = Vec::with_capacity(path.len());
+        let mut component = Some(root);
+        for &index in path {
+            keys.push(component.and_then(|component| component.child_key(index)));
+            component = component.and_then(|component| component.children().get(index)).map(|child| child.as_ref());
+        }
+        Self { path: path.to_vec(), keys }
+    }
+
+    // The path with each keyed step moved to wherever its child is now, or
+    // `None` if a step no longer leads anywhere.
+    fn resolve(&self, root: &dyn Component) -> Option<Self> {
+        let mut path = Vec::with_capacity(self.path.len());
+        let mut component = root;
+        for (&index, &key) in self.path.iter().zip(&self.keys) {
+            let index = match key {
+                Some(key) if component.child_key(index) != Some(key) => {
+                    (0..component.children().len()).find(|&other| component.child_key(other) == Some(key))?
+                }
+                _ => index,
+            };
+            component = component.children().get(index)?.as_ref();
+            path.push(index);
+        }
+        Some(Self { path, keys: self.keys.clone() })
+    }
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn focused(&self) -> Option<&[usize]> {
+        self.focused.as_ref().map(|focused| focused.path.as_slice())
+    }
+
+    // Whether the current focus came from the keyboard and should be drawn.
+    pub fn is_focus_visible(&self) -> bool {
+        self.focused.is_some() && self.focus_visible
+    }
+
+    // Moves focus to the component at `path`. Returns false, leaving focus
+    // unchanged, if there is no focusable component there.
+    pub fn focus(&mut self, root: &mut dyn Component, path: &[usize]) -> bool {
+        self.move_focus(root, path, true)
+    }
+
+    pub fn blur(&mut self, root: &mut dyn Component) {
+        if let Some(previous) = self.focused.take() {
+            dispatch_to(root, &previous.path, &Event::Blur);
+        }
+        self.focus_visible = false;
+    }
+
+    pub fn focus_next(&mut self, root: &mut dyn Component) -> bool {
+        self.step(root, true)
+    }
+
+    pub fn focus_previous(&mut self, root: &mut dyn Component) -> bool {
+        self.step(root, false)
+    }
+
+    // Routes `event` through the tree: Tab and Shift-Tab move focus, other
+    // keyboard and text events go to the focused component (or everywhere if
+    // nothing is focused), a press focuses the nearest focusable component
+    // under the pointer before being dispatched, and pointer moves and
+    // releases during a press go to the pressed component. Returns whether
+    // propagation was stopped.
+    pub fn dispatch(&mut self, root: &mut dyn Component, event: &Event) -> bool {
+        self.forget_stale(root);
+        match event {
+            Event::KeyDown(key) if key.code == KeyCode::Tab => {
+                if key.modifiers.shift {
+                    self.focus_previous(root)
+                } else {
+                    self.focus_next(root)
+                }
+            }
+            _ if event.is_keyboard() => match self.focused.clone() {
+                Some(focused) => dispatch_to(root, &focused.path, event),
+                None => dispatch(root, event),
+            },
+            Event::Click { .. } | Event::MouseDown(_) | Event::TouchStart(_) => {
+                let hit = event.position().and_then(|point| hit_test(root, point));
+                if !matches!(event, Event::Click { .. }) {
+                    self.pressed = hit.as_ref().map(|path| TrackedPath::new(root, path));
+                }
+                match hit.and_then(|path| focusable_ancestor(root, &path)) {
+                    Some(path) => {
+                        self.move_focus(root, &path, false);
+                    }
+                    None => self.blur(root),
+                }
+                dispatch(root, event)
+            }
+            Event::MouseMove(_) | Event::TouchMove(_) => match self.pressed.clone() {
+                Some(pressed) => dispatch_to(root, &pressed.path, event),
+                None => dispatch(root, event),
+            },
+            Event::MouseUp(_) | Event::TouchEnd(_) => match self.pressed.take() {
+                Some(pressed) => dispatch_to(root, &pressed.path, event),
+                None => dispatch(root, event),
+            },
+            _ => dispatch(root, event),
+        }
+    }
+
+    fn step(&mut self, root: &mut dyn Component, forward: bool) -> bool {
+        let order = focusable_paths(root);
+        if order.is_empty() {
+            return false;
+        }
+        let current = self
+            .focused
+            .as_ref()
+            .and_then(|focused| order.iter().position(|path| *path == focused.path));
+        let next = match (current, forward) {
+            (Some(index), true) => (index + 1) % order.len(),
+            (Some(index), false) => (index + order.len() - 1) % order.len(),
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+        };
+        self.move_focus(root, &order[next], true)
+    }
+
+    fn move_focus(&mut self, root: &mut dyn Component, path: &[usize], visible: bool) -> bool {
+        if !component_at_path(root, path).is_some_and(|component| component.is_focusable()) {
+            return false;
+        }
+        if self.focused() != Some(path) {
+            if let Some(previous) = self.focused.take() {
+                dispatch_to(root, &previous.path, &Event::Blur);
+            }
+        }
+        self.focused = Some(TrackedPath::new(root, path));
+        self.focus_visible = visible;
+        dispatch_to(root, path, &Event::Focus { visible });
+        true
+    }
+
+    // Follows the focused and pressed paths to where their components moved,
+    // dropping them if the tree changed under them.
+    fn forget_stale(&mut self, root: &dyn Component) {
+        self.pressed = self.pressed.take().and_then(|pressed| pressed.resolve(root));
+        self.focused = self.focused.take().and_then(|focused| focused.resolve(root)).filter(|focused| {
+            component_at_path(root, &focused.path).is_some_and(|component| component.is_focusable())
+        });
+    }
+}
+
+// Paths of every focusable component under `root`, in reading order.
+// Components at the same position keep their tree order.
+pub(crate) fn focusable_paths(root: &dyn Component) -> Vec<Vec<usize>> {
+    let mut paths = Vec::new();
+    collect_focusable(root, &mut Vec::new(), &mut paths);
+
+    let rtl = root.style().direction == LayoutDirection::Rtl;
+    let mut keyed: Vec<(f32, f32, Vec<usize>)> = paths
+        .into_iter()
+        .map(|path| {
+            let bounds = component_at_path(root, &path).map(|component| component.bounds()).unwrap_or_default();
+            let inline = if rtl { -(bounds.origin.x + bounds.size.width) } else { bounds.origin.x };
+            (bounds.origin.y, inline, path)
+        })
+        .collect();
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    keyed.into_iter().map(|(_, _, path)| path).collect()
+}
+
+fn collect_focusable(component: &dyn Component, path: &mut Vec<usize>, paths: &mut Vec<Vec<usize>>) {
+    if component.is_focusable() {
+        paths.push(path.clone());
+    }
+    for (index, child) in component.children().iter().enumerate() {
+        path.push(index);
+        collect_focusable(child.as_ref(), path, paths);
+        path.pop();
+    }
+}
+
+fn focusable_ancestor(root: &dyn Component, path: &[usize]) -> Option<Vec<usize>> {
+    (0..=path.len())
+        .rev()
+        .map(|depth| &path[..depth])
+        .find(|prefix| component_at_path(root, prefix).is_some_and(|component| component.is_focusable()))
+        .map(|prefix| prefix.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::mock::{take_log, EventLog, MockComponent};
+    use crate::event::{KeyEvent, Modifiers};
+    use crate::geometry::{Point, Rect};
+    use crate::style::Style;
+
+    // Three focusable children whose tree order differs from reading order.
+    fn form(log: &EventLog, direction: LayoutDirection) -> MockComponent {
+        MockComponent::new("root", Rect::new(0.0, 0.0, 100.0, 100.0), log)
+            .with_style(Style::default().set_direction(direction))
+            .child(MockComponent::new("a", Rect::new(50.0, 0.0, 40.0, 20.0), log).focusable())
+            .child(MockComponent::new("b", Rect::new(0.0, 0.0, 40.0, 20.0), log).focusable())
+            .child(MockComponent::new("c", Rect::new(0.0, 50.0, 40.0, 20.0), log).focusable())
+            .child(MockComponent::new("label", Rect::new(0.0, 80.0, 40.0, 20.0), log))
+    }
+
+    fn tab(shift: bool) -> Event {
+        Event::KeyDown(KeyEvent::new(KeyCode::Tab, Modifiers { shift, ..Modifiers::default() }))
+    }
+
+    #[test]
+    fn tab_follows_reading_order_and_wraps() {
+        let log = EventLog::default();
+        let mut root = form(&log, LayoutDirection::Ltr);
+        let mut focus = FocusManager::new();
+        let mut order = Vec::new();
+        for _ in 0..4 {
+            focus.dispatch(&mut root, &tab(false));
+            order.push(focus.focused().unwrap().to_vec());
+        }
+        assert_eq!(order, [vec![1], vec![0], vec![2], vec![1]]);
+        assert!(focus.is_focus_visible());
+
+        focus.dispatch(&mut root, &tab(true));
+        assert_eq!(focus.focused(), Some(&[2][..]));
+    }
+
+    #[test]
+    fn rtl_roots_read_from_the_right() {
+        let log = EventLog::default();
+        let root = form(&log, LayoutDirection::Rtl);
+        assert_eq!(focusable_paths(&root), [vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn moving_focus_blurs_the_previous_component() {
+        let log = EventLog::default();
+        let mut root = form(&log, LayoutDirection::Ltr);
+        let mut focus = FocusManager::new();
+        focus.focus_next(&mut root);
+        take_log(&log);
+
+        focus.dispatch(&mut root, &Event::Click { x: 10.0, y: 60.0 });
+        assert_eq!(focus.focused(), Some(&[2][..]));
+        assert!(!focus.is_focus_visible());
+        let targets: Vec<String> = take_log(&log).into_iter().filter(|entry| entry.contains("Target")).collect();
+        assert_eq!(targets, ["b Target blur", "c Target focus", "c Target click"]);
+    }
+
+    #[test]
+    fn keys_go_to_the_focused_component() {
+        let log = EventLog::default();
+        let mut root = form(&log, LayoutDirection::Ltr);
+        let mut focus = FocusManager::new();
+        focus.focus(&mut root, &[2]);
+        take_log(&log);
+
+        focus.dispatch(&mut root, &Event::KeyDown(KeyEvent::new(KeyCode::Enter, Modifiers::default())));
+        assert_eq!(take_log(&log), ["root Capture key", "c Target key", "root Bubble key"]);
+    }
+
+    #[test]
+    fn a_press_captures_the_pointer_until_release() {
+        let log = EventLog::default();
+        let mut root = form(&log, LayoutDirection::Ltr);
+        let mut focus = FocusManager::new();
+        focus.dispatch(&mut root, &Event::MouseDown(Point::new(10.0, 10.0)));
+        take_log(&log);
+
+        focus.dispatch(&mut root, &Event::MouseMove(Point::new(10.0, 60.0)));
+        focus.dispatch(&mut root, &Event::MouseUp(Point::new(10.0, 60.0)));
+        assert_eq!(
+            take_log(&log),
+            [
+                "root Capture move",
+                "b Target move",
+                "root Bubble move",
+                "root Capture up",
+                "b Target up",
+                "root Bubble up",
+            ]
+        );
+
+        focus.dispatch(&mut root, &Event::MouseMove(Point::new(10.0, 60.0)));
+        assert!(take_log(&log).contains(&"c Target move".to_string()));
+    }
+}
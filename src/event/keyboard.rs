@@ -0,0 +1,186 @@
+// Physical keys, independent of layout and modifiers: `KeyCode::A` is reported
+// for both "a" and "A". Characters typed arrive separately as `Event::TextInput`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    A, B, C, D, E, F, G, H, I, J, K, L, M,
+    N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Digit0, Digit1, Digit2, Digit3, Digit4,
+    Digit5, Digit6, Digit7, Digit8, Digit9,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    F13, F14, F15, F16, F17, F18, F19, F20, F21, F22, F23, F24,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    Backspace,
+    Tab,
+    Enter,
+    Escape,
+    Space,
+    Minus,
+    Plus,
+    Equal,
+    Comma,
+    Period,
+    Slash,
+    Backslash,
+    Semicolon,
+    Quote,
+    Backquote,
+    BracketLeft,
+    BracketRight,
+    Shift,
+    Control,
+    Alt,
+    Meta,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    PrintScreen,
+    Pause,
+    Menu,
+    Unknown,
+}
+
+const LETTERS: [KeyCode; 26] = [
+    KeyCode::A, KeyCode::B, KeyCode::C, KeyCode::D, KeyCode::E, KeyCode::F, KeyCode::G,
+    KeyCode::H, KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L, KeyCode::M, KeyCode::N,
+    KeyCode::O, KeyCode::P, KeyCode::Q, KeyCode::R, KeyCode::S, KeyCode::T, KeyCode::U,
+    KeyCode::V, KeyCode::W, KeyCode::X, KeyCode::Y, KeyCode::Z,
+];
+
+const DIGITS: [KeyCode; 10] = [
+    KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+    KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+];
+
+const FUNCTION_KEYS: [KeyCode; 24] = [
+    KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+    KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+    KeyCode::F13, KeyCode::F14, KeyCode::F15, KeyCode::F16, KeyCode::F17, KeyCode::F18,
+    KeyCode::F19, KeyCode::F20, KeyCode::F21, KeyCode::F22, KeyCode::F23, KeyCode::F24,
+];
+
+impl KeyCode {
+    // The key that types `c` on a US layout, ignoring shift.
+    pub fn from_char(c: char) -> Self {
+        match c {
+            'a'..='z' => LETTERS[c as usize - 'a' as usize],
+            'A'..='Z' => LETTERS[c as usize - 'A' as usize],
+            '0'..='9' => DIGITS[c as usize - '0' as usize],
+            ' ' => KeyCode::Space,
+            '-' | '_' => KeyCode::Minus,
+            '+' => KeyCode::Plus,
+            '=' => KeyCode::Equal,
+            ',' | '<' => KeyCode::Comma,
+            '.' | '>' => KeyCode::Period,
+            '/' | '?' => KeyCode::Slash,
+            '\\' | '|' => KeyCode::Backslash,
+            ';' | ':' => KeyCode::Semicolon,
+            '\'' | '"' => KeyCode::Quote,
+            '`' | '~' => KeyCode::Backquote,
+            '[' | '{' => KeyCode::BracketLeft,
+            ']' | '}' => KeyCode::BracketRight,
+            '\t' => KeyCode::Tab,
+            '\n' | '\r' => KeyCode::Enter,
+            _ => KeyCode::Unknown,
+        }
+    }
+
+    // `number` is 1-based, as in F1.
+    pub fn function(number: u8) -> Self {
+        match number {
+            1..=24 => FUNCTION_KEYS[number as usize - 1],
+            _ => KeyCode::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    // Command on macOS, the Windows key elsewhere.
+    pub meta: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers { shift: false, control: false, alt: false, meta: false };
+    pub const SHIFT: Modifiers = Modifiers { shift: true, ..Modifiers::NONE };
+    pub const CONTROL: Modifiers = Modifiers { control: true, ..Modifiers::NONE };
+    pub const ALT: Modifiers = Modifiers { alt: true, ..Modifiers::NONE };
+    pub const META: Modifiers = Modifiers { meta: true, ..Modifiers::NONE };
+
+    pub fn is_empty(&self) -> bool {
+        *self == Modifiers::NONE
+    }
+
+    // Control on most platforms, Command on macOS: the modifier for shortcuts
+    // such as copy and paste.
+    pub fn command(&self) -> bool {
+        if cfg!(target_os = "macos") { self.meta } else { self.control }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub code: KeyCode,
+    pub modifiers: Modifiers,
+    // Set on the key-down events a held key generates after the first.
+    pub repeat: bool,
+}
+
+impl KeyEvent {
+    pub fn new(code: KeyCode, modifiers: Modifiers) -> Self {
+        Self { code, modifiers, repeat: false }
+    }
+}
+
+impl From<KeyCode> for KeyEvent {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, Modifiers::NONE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn characters_map_to_the_key_that_types_them() {
+        assert_eq!(KeyCode::from_char('a'), KeyCode::A);
+        assert_eq!(KeyCode::from_char('Z'), KeyCode::Z);
+        assert_eq!(KeyCode::from_char('7'), KeyCode::Digit7);
+        assert_eq!(KeyCode::from_char('?'), KeyCode::Slash);
+        assert_eq!(KeyCode::from_char('{'), KeyCode::BracketLeft);
+        assert_eq!(KeyCode::from_char('\r'), KeyCode::Enter);
+        assert_eq!(KeyCode::from_char('é'), KeyCode::Unknown);
+    }
+
+    #[test]
+    fn function_keys_are_numbered_from_one() {
+        assert_eq!(KeyCode::function(1), KeyCode::F1);
+        assert_eq!(KeyCode::function(24), KeyCode::F24);
+        assert_eq!(KeyCode::function(0), KeyCode::Unknown);
+        assert_eq!(KeyCode::function(25), KeyCode::Unknown);
+    }
+
+    #[test]
+    fn modifiers_combine_and_pick_the_command_key() {
+        assert!(Modifiers::NONE.is_empty());
+        assert!(!Modifiers::SHIFT.is_empty());
+        let both = Modifiers { shift: true, ..Modifiers::CONTROL };
+        assert!(both.shift && both.control && !both.alt && !both.meta);
+        let command = if cfg!(target_os = "macos") { Modifiers::META } else { Modifiers::CONTROL };
+        assert!(command.command());
+        assert!(!Modifiers::ALT.command());
+        assert_eq!(KeyEvent::from(KeyCode::Tab), KeyEvent::new(KeyCode::Tab, Modifiers::NONE));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::geometry::Point;
+use std::time::Duration;
+
+mod focus;
+mod keyboard;
+mod routing;
+
+pub use focus::FocusManager;
+pub use keyboard::{KeyCode, KeyEvent, Modifiers};
+pub use routing::{component_at_path, dispatch, dispatch_to, hit_test, EventContext, EventPhase};
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    KeyDown(KeyEvent),
+    KeyUp(KeyEvent),
+    // Text committed by typing or by an input method, after any KeyDown
+    // that produced it.
+    TextInput(String),
+    // In-progress IME composition, shown in place but not yet committed.
+    // `cursor` is a byte range within `text`; an empty `text` ends it, and
+    // the composed text then arrives as `TextInput`.
+    ImePreedit { text: String, cursor: Option<(usize, usize)> },
+    Click { x: f32, y: f32 },
+    MouseDown(Point),
+    MouseUp(Point),
+    MouseMove(Point),
+    // A wheel or trackpad scroll at `position`. A positive `delta` moves the
+    // content up and left, revealing what lies below and to the right.
+    Scroll { position: Point, delta: Point },
+    TouchStart(Point),
+    TouchMove(Point),
+    TouchEnd(Point),
+    // Sent by `FocusManager` to the component gaining focus; `visible` is
+    // set when focus moved by keyboard and should be drawn.
+    Focus { visible: bool },
+    Blur,
+    // Sent to every component once per frame with the time since the last
+    // one, so running animations such as scroll momentum can advance. The
+    // app sends it, e.g. with `dispatch` from `Application::update`.
+    Tick(Duration),
+}
+
+impl Event {
+    // Keyboard and text events, which follow focus rather than the pointer.
+    pub fn is_keyboard(&self) -> bool {
+        matches!(
+            self,
+            Event::KeyDown(_)
+                | Event::KeyUp(_)
+                | Event::TextInput(_)
+                | Event::ImePreedit { .. }
+        )
+    }
+
+    // Where a pointer or touch event happened; `None` for everything else.
+    pub fn position(&self) -> Option<Point> {
+        match self {
+            Event::Click { x, y } => Some(Point::new(*x, *y)),
+            Event::MouseDown(point)
+            | Event::MouseUp(point)
+            | Event::MouseMove(point)
+            | Event::TouchStart(point)
+            | Event::TouchMove(point)
+            | Event::TouchEnd(point)
+            | Event::Scroll { position: point, .. } => Some(*point),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::components::{paint_order, Component};
+use crate::geometry::Point;
+use super::Event;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventPhase {
+    // Travelling from the root down to the target's parent.
+    Capture,
+    Target,
+    // Travelling from the target's parent back up to the root.
+    Bubble,
+}
+
+// Passed to `Component::handle_event` alongside the event. `position` is the
+// pointer location in the coordinate space of the component's own bounds.
+#[derive(Clone, Debug)]
+pub struct EventContext {
+    phase: EventPhase,
+    position: Option<Point>,
+    propagation_stopped: bool,
+}
+
+impl EventContext {
+    pub fn new(phase: EventPhase, position: Option<Point>) -> Self {
+        Self { phase, position, propagation_stopped: false }
+    }
+
+    pub fn phase(&self) -> EventPhase {
+        self.phase
+    }
+
+    pub fn position(&self) -> Option<Point> {
+        self.position
+    }
+
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+}
+
+// The path of child indices from `root` to the topmost component under
+// `point`, which is in the coordinate space of `root`'s bounds. Children are
+// tested front to back, after their parent's content offset and clip; a
+// child may be hit outside its parent's bounds unless the parent clips.
+pub fn hit_test(root: &dyn Component, point: Point) -> Option<Vec<usize>> {
+    let clipped = root.clip_rect().is_some_and(|clip| !clip.contains(point));
+    if !clipped {
+        let offset = root.content_offset();
+        let inner = Point::new(point.x - offset.x, point.y - offset.y);
+        let children = root.children();
+        for index in paint_order(children).into_iter().rev() {
+            if let Some(mut path) = hit_test(children[index].as_ref(), inner) {
+                path.insert(0, index);
+                return Some(path);
+            }
+        }
+    }
+    root.bounds().contains(point).then(Vec::new)
+}
+
+// Routes `event` through the tree under `root` and returns whether some
+// component stopped its propagation.
+//
+// Pointer events go to the component under the pointer: first a capture pass
+// over its ancestors from the root down, then the target itself, then a
+// bubble pass back up. Other events are delivered to every component, front
+// to back. Either way delivery ends as soon as propagation is stopped.
+pub fn dispatch(root: &mut dyn Component, event: &Event) -> bool {
+    let Some(point) = event.position() else {
+        return broadcast(root, event);
+    };
+    match hit_test(root, point) {
+        Some(path) => dispatch_to(root, &path, event),
+        None => false,
+    }
+}
+
+// Routes `event` to the component at `path` below `root` through the capture,
+// target and bubble phases, and returns whether propagation was stopped.
+pub fn dispatch_to(root: &mut dyn Component, path: &[usize], event: &Event) -> bool {
+    if component_at_path(root, path).is_none() {
+        return false;
+    }
+
+    // The pointer position in the space of each component along the path.
+    let positions = event.position().map(|point| {
+        let mut positions = vec![point];
+        for depth in 0..path.len() {
+            let offset = component_at(root, &path[..depth]).content_offset();
+            let previous = positions[depth];
+            positions.push(Point::new(previous.x - offset.x, previous.y - offset.y));
+        }
+        positions
+    });
+
+    let mut deliver = |depth: usize, phase: EventPhase| {
+        let position = positions.as_ref().map(|positions| positions[depth]);
+        let mut context = EventContext::new(phase, position);
+        component_at(root, &path[..depth]).handle_event(event, &mut context);
+        context.is_propagation_stopped()
+    };
+
+    for depth in 0..path.len() {
+        if deliver(depth, EventPhase::Capture) {
+            return true;
+        }
+    }
+    if deliver(path.len(), EventPhase::Target) {
+        return true;
+    }
+    for depth in (0..path.len()).rev() {
+        if deliver(depth, EventPhase::Bubble) {
+            return true;
+        }
+    }
+    false
+}
+
+// The component at `path` below `root`, if the path is still valid.
+pub fn component_at_path<'a>(root: &'a dyn Component, path: &[usize]) -> Option<&'a dyn Component> {
+    path.iter()
+        .try_fold(root, |component, &index| component.children().get(index).map(|child| child.as_ref()))
+}
+
+fn broadcast(component: &mut dyn Component, event: &Event) -> bool {
+    let mut context = EventContext::new(EventPhase::Target, None);
+    component.handle_event(event, &mut context);
+    if context.is_propagation_stopped() {
+        return true;
+    }
+    let order = paint_order(component.children());
+    let children = component.children_mut();
+    order.into_iter().rev().any(|index| broadcast(children[index].as_mut(), event))
+}
+
+fn component_at<'a>(root: &'a mut dyn Component, path: &[usize]) -> &'a mut dyn Component {
+    path.iter().fold(root, |component, &index| component.children_mut()[index].as_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::mock::{take_log, EventLog, MockComponent};
+    use crate::event::{KeyCode, KeyEvent, Modifiers};
+    use crate::geometry::Rect;
+    use crate::style::Style;
+
+    fn tree(log: &EventLog, leaf: MockComponent) -> MockComponent {
+        MockComponent::new("root", Rect::new(0.0, 0.0, 100.0, 100.0), log)
+            .child(MockComponent::new("parent", Rect::new(10.0, 10.0, 50.0, 50.0), log).child(leaf))
+    }
+
+    #[test]
+    fn pointer_events_capture_then_target_then_bubble() {
+        let log = EventLog::default();
+        let mut root = tree(&log, MockComponent::new("leaf", Rect::new(20.0, 20.0, 20.0, 20.0), &log));
+        assert!(!dispatch(&mut root, &Event::Click { x: 30.0, y: 30.0 }));
+        assert_eq!(
+            take_log(&log),
+            [
+                "root Capture click",
+                "parent Capture click",
+                "leaf Target click",
+                "parent Bubble click",
+                "root Bubble click",
+            ]
+        );
+    }
+
+    #[test]
+    fn stopping_propagation_ends_delivery() {
+        let log = EventLog::default();
+        let mut root = MockComponent::new("root", Rect::new(0.0, 0.0, 100.0, 100.0), &log).child(
+            MockComponent::new("parent", Rect::new(10.0, 10.0, 50.0, 50.0), &log)
+                .stopping(EventPhase::Capture)
+                .child(MockComponent::new("leaf", Rect::new(20.0, 20.0, 20.0, 20.0), &log)),
+        );
+        assert!(dispatch(&mut root, &Event::Click { x: 30.0, y: 30.0 }));
+        assert_eq!(take_log(&log), ["root Capture click", "parent Capture click"]);
+    }
+
+    #[test]
+    fn the_topmost_child_is_hit() {
+        let log = EventLog::default();
+        let overlapping = |z_index| {
+            MockComponent::new("root", Rect::new(0.0, 0.0, 100.0, 100.0), &log)
+                .child(
+                    MockComponent::new("below", Rect::new(0.0, 0.0, 50.0, 50.0), &log)
+                        .with_style(Style::default().set_z_index(z_index)),
+                )
+                .child(MockComponent::new("above", Rect::new(0.0, 0.0, 50.0, 50.0), &log))
+        };
+        let point = Point::new(10.0, 10.0);
+        assert_eq!(hit_test(&overlapping(0), point), Some(vec![1]));
+        assert_eq!(hit_test(&overlapping(1), point), Some(vec![0]));
+        assert_eq!(hit_test(&overlapping(0), Point::new(200.0, 10.0)), None);
+    }
+
+    #[test]
+    fn other_events_reach_every_component() {
+        let log = EventLog::default();
+        let mut root = tree(&log, MockComponent::new("leaf", Rect::new(20.0, 20.0, 20.0, 20.0), &log));
+        dispatch(&mut root, &Event::KeyDown(KeyEvent::new(KeyCode::Enter, Modifiers::default())));
+        let mut delivered = take_log(&log);
+        delivered.sort();
+        assert_eq!(delivered, ["leaf Target key", "parent Target key", "root Target key"]);
+    }
+}
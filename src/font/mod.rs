@@ -0,0 +1,251 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use ttf_parser::{name_id, OutlineBuilder};
+use crate::style::{FontStyle, FontWeight, Style};
+
+mod shape;
+
+pub use shape::{measure_text, shape_line, PositionedGlyph, ShapedLine};
+pub(crate) use shape::text_width;
+
+// Bundled so text renders identically on every machine, including CI hosts
+// without any system fonts installed.
+static DEFAULT_FONT_DATA: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+
+pub const DEFAULT_FONT_SIZE: f32 = 16.0;
+
+// Generic family names, which always resolve to the bundled font.
+const GENERIC_FAMILIES: [&str; 3] = ["sans-serif", "system-ui", "default"];
+
+// One face of a loaded font file.
+pub struct Font {
+    family: String,
+    weight: FontWeight,
+    style: FontStyle,
+    face: rustybuzz::Face<'static>,
+}
+
+impl Font {
+    pub fn family(&self) -> &str {
+        &self.family
+    }
+
+    pub fn weight(&self) -> FontWeight {
+        self.weight
+    }
+
+    pub fn style(&self) -> FontStyle {
+        self.style
+    }
+
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.face.glyph_index(c).is_some()
+    }
+
+    pub(crate) fn face(&self) -> &rustybuzz::Face<'static> {
+        &self.face
+    }
+
+    // Font units to points at `font_size`.
+    pub(crate) fn scale(&self, font_size: f32) -> f32 {
+        font_size / self.face.units_per_em() as f32
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("family", &self.family)
+            .field("weight", &self.weight)
+            .field("style", &self.style)
+            .finish()
+    }
+}
+
+struct Registry {
+    // The bundled font is always first.
+    fonts: Vec<&'static Font>,
+    fallbacks: Vec<String>,
+}
+
+impl Registry {
+    // The face of `family` closest to the requested style and weight.
+    fn select(&self, family: &str, weight: FontWeight, style: FontStyle) -> Option<&'static Font> {
+        if GENERIC_FAMILIES.iter().any(|generic| generic.eq_ignore_ascii_case(family)) {
+            return Some(self.fonts[0]);
+        }
+        self.fonts
+            .iter()
+            .filter(|font| font.family.eq_ignore_ascii_case(family))
+            .min_by_key(|font| (font.style != style, font.weight.0.abs_diff(weight.0)))
+            .copied()
+    }
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let default = parse_font(DEFAULT_FONT_DATA, 0).expect("bundled font is a valid TrueType file");
+        RwLock::new(Registry { fonts: vec![default], fallbacks: Vec::new() })
+    })
+}
+
+// Nothing panics while the lock is held, so a poisoned lock is still usable.
+fn read() -> RwLockReadGuard<'static, Registry> {
+    registry().read().unwrap_or_else(|error| error.into_inner())
+}
+
+fn write() -> RwLockWriteGuard<'static, Registry> {
+    registry().write().unwrap_or_else(|error| error.into_inner())
+}
+
+fn parse_font(data: &'static [u8], index: u32) -> Result<&'static Font, ttf_parser::FaceParsingError> {
+    let face = ttf_parser::Face::parse(data, index)?;
+    let family = family_name(&face).unwrap_or_default();
+    let weight = FontWeight(face.weight().to_number());
+    let style = match face.style() {
+        ttf_parser::Style::Normal => FontStyle::Normal,
+        ttf_parser::Style::Italic => FontStyle::Italic,
+        ttf_parser::Style::Oblique => FontStyle::Oblique,
+    };
+    let face = rustybuzz::Face::from_face(face);
+    Ok(Box::leak(Box::new(Font { family, weight, style, face })))
+}
+
+// The typographic family when present, so "Inter Bold" files group under
+// "Inter" with the regular face.
+fn family_name(face: &ttf_parser::Face) -> Option<String> {
+    [name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY].into_iter().find_map(|id| {
+        face.names()
+            .into_iter()
+            .filter(|name| name.name_id == id && name.is_unicode())
+            .find_map(|name| name.to_string())
+    })
+}
+
+// Registers every face in a TTF, OTF or collection file and returns them.
+// Fonts stay loaded for the rest of the program, so load each file once,
+// typically at startup.
+pub fn load_font_data(data: Vec<u8>) -> Result<Vec<&'static Font>, Box<dyn std::error::Error>> {
+    let data: &'static [u8] = Box::leak(data.into_boxed_slice());
+    let count = ttf_parser::fonts_in_collection(data).unwrap_or(1);
+    let fonts = (0..count).map(|index| parse_font(data, index)).collect::<Result<Vec<_>, _>>()?;
+    write().fonts.extend(&fonts);
+    Ok(fonts)
+}
+
+pub fn load_font_file<P: AsRef<Path>>(path: P) -> Result<Vec<&'static Font>, Box<dyn std::error::Error>> {
+    load_font_data(std::fs::read(path)?)
+}
+
+// Families tried after a style's own `font_family` list and before the
+// bundled font, e.g. for emoji or CJK coverage.
+pub fn set_fallback_families<I, S>(families: I)
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    write().fallbacks = families.into_iter().map(Into::into).collect();
+}
+
+pub fn fonts() -> Vec<&'static Font> {
+    read().fonts.clone()
+}
+
+// The fonts `style` draws with, in fallback order, ending with the bundled
+// font.
+pub(crate) fn font_chain(style: &Style) -> Vec<&'static Font> {
+    let registry = read();
+    let mut chain: Vec<&'static Font> = Vec::new();
+    for family in style.font_family.iter().chain(&registry.fallbacks) {
+        if let Some(font) = registry.select(family, style.font_weight, style.font_style) {
+            if !chain.iter().any(|chosen| std::ptr::eq(*chosen, font)) {
+                chain.push(font);
+            }
+        }
+    }
+    if !chain.iter().any(|chosen| std::ptr::eq(*chosen, registry.fonts[0])) {
+        chain.push(registry.fonts[0]);
+    }
+    chain
+}
+
+pub(crate) fn resolve_font_size(font_size: f32) -> f32 {
+    if font_size > 0.0 {
+        font_size
+    } else {
+        DEFAULT_FONT_SIZE
+    }
+}
+
+pub(crate) struct LineMetrics {
+    pub ascent: f32,
+    pub line_height: f32,
+}
+
+// Metrics of the first font in the style's chain. An explicit line height
+// splits the extra leading evenly above and below the glyphs.
+pub(crate) fn line_metrics(style: &Style) -> LineMetrics {
+    let font = font_chain(style)[0];
+    let font_size = resolve_font_size(style.font_size);
+    let scale = font.scale(font_size);
+    let face = font.face();
+    let ascent = face.ascender() as f32 * scale;
+    let descent = -(face.descender() as f32) * scale;
+    if style.line_height > 0.0 {
+        let line_height = style.line_height * font_size;
+        return LineMetrics {
+            ascent: ascent + (line_height - ascent - descent) / 2.0,
+            line_height,
+        };
+    }
+    LineMetrics {
+        ascent,
+        line_height: ascent + descent + face.line_gap() as f32 * scale,
+    }
+}
+
+// Adapts ttf-parser's font-unit outline callbacks into a tiny-skia path,
+// flipping the y axis and placing the glyph at the given pen position.
+pub(crate) struct GlyphPath<'a> {
+    pub builder: &'a mut tiny_skia::PathBuilder,
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+}
+
+impl GlyphPath<'_> {
+    fn map(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.x + x * self.scale, self.y - y * self.scale)
+    }
+}
+
+impl OutlineBuilder for GlyphPath<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.map(x, y);
+        self.builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.map(x, y);
+        self.builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x1, y1) = self.map(x1, y1);
+        let (x, y) = self.map(x, y);
+        self.builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.map(x1, y1);
+        let (x2, y2) = self.map(x2, y2);
+        let (x, y) = self.map(x, y);
+        self.builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+    }
+}
@@ -0,0 +1,279 @@
+use std::ops::Range;
+use rustybuzz::{Direction, UnicodeBuffer};
+use unicode_bidi::{BidiInfo, Level};
+use crate::geometry::Size;
+use crate::style::{LayoutDirection, Style};
+use crate::text::{grapheme_boundaries, layout_paragraph};
+use super::{font_chain, resolve_font_size, Font};
+
+// A glyph placed on a line. `x` and `y` are the pen position relative to
+// the start of the line's baseline, with y growing downwards.
+#[derive(Clone, Debug)]
+pub struct PositionedGlyph {
+    pub font: &'static Font,
+    pub glyph_id: u16,
+    // Byte offset of the first character the glyph was shaped from.
+    pub cluster: usize,
+    pub x: f32,
+    pub y: f32,
+    pub advance: f32,
+    pub font_size: f32,
+}
+
+// One line of text shaped with kerning, ligatures and font fallback, with
+// mixed-direction runs put in visual order by the Unicode bidi algorithm.
+#[derive(Clone, Debug, Default)]
+pub struct ShapedLine {
+    pub glyphs: Vec<PositionedGlyph>,
+    pub width: f32,
+    // Clusters in logical order.
+    clusters: Vec<Cluster>,
+    // The x position of every grapheme boundary, in offset order.
+    carets: Vec<(usize, f32)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Cluster {
+    // Byte offset of the first character.
+    start: usize,
+    // Left edge and width on the line.
+    x: f32,
+    advance: f32,
+    rtl: bool,
+}
+
+impl ShapedLine {
+    // The caret x position at byte `offset`; offsets inside a grapheme use
+    // the boundary before them.
+    pub fn x_for_offset(&self, offset: usize) -> f32 {
+        let index = self.carets.partition_point(|&(boundary, _)| boundary <= offset);
+        index.checked_sub(1).map_or(0.0, |index| self.carets[index].1)
+    }
+
+    // The grapheme boundary nearest to `x`.
+    pub fn offset_for_x(&self, x: f32) -> usize {
+        self.carets
+            .iter()
+            .min_by(|a, b| (a.1 - x).abs().total_cmp(&(b.1 - x).abs()))
+            .map_or(0, |&(offset, _)| offset)
+    }
+
+    // The total advance of the text between two byte offsets, whatever
+    // direction it runs in. A ligature counts towards the range holding its
+    // first character.
+    pub fn advance_between(&self, start: usize, end: usize) -> f32 {
+        let first = self.clusters.partition_point(|cluster| cluster.start < start);
+        self.clusters[first..]
+            .iter()
+            .take_while(|cluster| cluster.start < end)
+            .map(|cluster| cluster.advance)
+            .sum()
+    }
+}
+
+// Shapes a single line; newlines are not treated specially.
+pub fn shape_line(text: &str, style: &Style) -> ShapedLine {
+    let font_size = resolve_font_size(style.font_size);
+    let chain = font_chain(style);
+    let boundaries = grapheme_boundaries(text);
+
+    let mut glyphs = Vec::new();
+    // In visual order until sorted below.
+    let mut clusters: Vec<Cluster> = Vec::new();
+    let mut x = 0.0;
+    for (range, rtl) in bidi_runs(text, style.direction) {
+        let mut runs = font_runs(text, &boundaries, range, &chain);
+        // Fallback runs inside a right-to-left run are placed right to left.
+        if rtl {
+            runs.reverse();
+        }
+        for (start, end, font) in runs {
+            let mut buffer = UnicodeBuffer::new();
+            buffer.push_str(&text[start..end]);
+            buffer.guess_segment_properties();
+            buffer.set_direction(if rtl { Direction::RightToLeft } else { Direction::LeftToRight });
+            let output = rustybuzz::shape(font.face(), &[], buffer);
+            let scale = font.scale(font_size);
+
+            for (info, position) in output.glyph_infos().iter().zip(output.glyph_positions()) {
+                let cluster = start + info.cluster as usize;
+                let advance = position.x_advance as f32 * scale;
+                match clusters.last_mut() {
+                    Some(last) if last.start == cluster => last.advance += advance,
+                    _ => clusters.push(Cluster { start: cluster, x, advance, rtl }),
+                }
+                glyphs.push(PositionedGlyph {
+                    font,
+                    glyph_id: info.glyph_id as u16,
+                    cluster,
+                    x: x + position.x_offset as f32 * scale,
+                    y: -(position.y_offset as f32) * scale,
+                    advance,
+                    font_size,
+                });
+                x += advance;
+            }
+        }
+    }
+
+    if style.letter_spacing != 0.0 {
+        apply_letter_spacing(&mut glyphs, &mut clusters, style.letter_spacing);
+        x += clusters.len() as f32 * style.letter_spacing;
+    }
+
+    clusters.sort_by_key(|cluster| cluster.start);
+    ShapedLine {
+        carets: caret_positions(text, &boundaries, &clusters),
+        clusters,
+        glyphs,
+        width: x,
+    }
+}
+
+// The byte ranges of `text` in visual order, each flagged right to left or
+// not, for a paragraph whose base direction is `direction`.
+fn bidi_runs(text: &str, direction: LayoutDirection) -> Vec<(Range<usize>, bool)> {
+    let base = match direction {
+        LayoutDirection::Ltr => Level::ltr(),
+        LayoutDirection::Rtl => Level::rtl(),
+    };
+    let bidi = BidiInfo::new(text, Some(base));
+    if !bidi.has_rtl() {
+        return vec![(0..text.len(), false)];
+    }
+    bidi.paragraphs
+        .iter()
+        .flat_map(|paragraph| {
+            let (levels, runs) = bidi.visual_runs(paragraph, paragraph.range.clone());
+            runs.into_iter().map(move |run| {
+                let rtl = levels[run.start].is_rtl();
+                (run, rtl)
+            })
+        })
+        .collect()
+}
+
+// Splits `text` into runs drawn with the same font: each grapheme uses the
+// first font in the chain that covers it, and whitespace stays in the
+// current run so kerning is not broken up needlessly.
+fn font_runs(
+    text: &str,
+    boundaries: &[usize],
+    range: Range<usize>,
+    chain: &[&'static Font],
+) -> Vec<(usize, usize, &'static Font)> {
+    let mut runs: Vec<(usize, usize, &'static Font)> = Vec::new();
+    let inside = boundaries.iter().copied().filter(|offset| range.contains(offset));
+    let ends = inside.clone().skip(1).chain(std::iter::once(range.end));
+    for (start, end) in inside.zip(ends) {
+        let c = text[start..].chars().next().unwrap_or(' ');
+        let font = match runs.last() {
+            Some(&(_, _, current)) if c.is_whitespace() && current.has_glyph(c) => current,
+            _ => chain.iter().copied().find(|font| font.has_glyph(c)).unwrap_or(chain[0]),
+        };
+        match runs.last_mut() {
+            Some(run) if std::ptr::eq(run.2, font) => run.1 = end,
+            _ => runs.push((start, end, font)),
+        }
+    }
+    runs
+}
+
+// Adds `spacing` after every cluster, with clusters in visual order.
+fn apply_letter_spacing(glyphs: &mut [PositionedGlyph], clusters: &mut [Cluster], spacing: f32) {
+    for (index, cluster) in clusters.iter_mut().enumerate() {
+        cluster.x += index as f32 * spacing;
+        cluster.advance += spacing;
+    }
+    let mut shift = 0.0;
+    for index in 0..glyphs.len() {
+        glyphs[index].x += shift;
+        if glyphs.get(index + 1).is_none_or(|next| next.cluster != glyphs[index].cluster) {
+            glyphs[index].advance += spacing;
+            shift += spacing;
+        }
+    }
+}
+
+// Places grapheme boundaries at the leading edge of the grapheme that
+// follows them, spreading those inside a ligature evenly across its
+// advance. The end of the text sits at the trailing edge of the last
+// cluster. `clusters` are in logical order.
+fn caret_positions(text: &str, boundaries: &[usize], clusters: &[Cluster]) -> Vec<(usize, f32)> {
+    let mut carets = Vec::with_capacity(boundaries.len());
+    for (index, cluster) in clusters.iter().enumerate() {
+        let end = clusters.get(index + 1).map_or(text.len(), |next| next.start);
+        let first = boundaries.partition_point(|&b| b < cluster.start);
+        let inside = &boundaries[first..first + boundaries[first..].partition_point(|&b| b < end)];
+        let count = inside.len().max(1) as f32;
+        for (position, &boundary) in inside.iter().enumerate() {
+            let along = cluster.advance * position as f32 / count;
+            let x = if cluster.rtl { cluster.x + cluster.advance - along } else { cluster.x + along };
+            carets.push((boundary, x));
+        }
+    }
+    let end = match clusters.last() {
+        Some(cluster) if cluster.rtl => cluster.x,
+        Some(cluster) => cluster.x + cluster.advance,
+        None => 0.0,
+    };
+    carets.push((text.len(), end));
+    carets
+}
+
+pub(crate) fn text_width(text: &str, style: &Style) -> f32 {
+    shape_line(text, style).width
+}
+
+// The size of `text` laid out as the renderers draw it with unbounded
+// width, so it only breaks at `\n`.
+pub fn measure_text(text: &str, style: &Style) -> Size {
+    layout_paragraph(text, style, f32::INFINITY).size()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style() -> Style {
+        Style::default().set_font_size(16.0)
+    }
+
+    #[test]
+    fn carets_run_left_to_right() {
+        let shaped = shape_line("abc", &style());
+        assert_eq!(shaped.x_for_offset(0), 0.0);
+        assert!(shaped.x_for_offset(1) < shaped.x_for_offset(2));
+        assert_eq!(shaped.x_for_offset(3), shaped.width);
+        assert_eq!(shaped.offset_for_x(shaped.width + 10.0), 3);
+        assert_eq!(shaped.advance_between(0, 3), shaped.width);
+    }
+
+    #[test]
+    fn hebrew_runs_right_to_left() {
+        // Each Hebrew letter is two bytes.
+        let shaped = shape_line("שלום", &style());
+        assert_eq!(shaped.x_for_offset(0), shaped.width);
+        assert!(shaped.x_for_offset(2) > shaped.x_for_offset(4));
+        assert!(shaped.x_for_offset(8) < 1.0);
+        let glyph = shaped.glyphs.iter().min_by(|a, b| a.x.total_cmp(&b.x)).unwrap();
+        assert_eq!(glyph.cluster, 6);
+    }
+
+    #[test]
+    fn mixed_text_is_reordered_visually() {
+        // The Hebrew word sits after "ab " with its first letter on the right.
+        let shaped = shape_line("ab שלום", &style());
+        let latin = shaped.advance_between(0, 3);
+        assert_eq!(shaped.x_for_offset(3).min(shaped.x_for_offset(11)), latin);
+        assert!(shaped.x_for_offset(5) > shaped.x_for_offset(7));
+        assert_eq!(shaped.advance_between(3, 11) + latin, shaped.width);
+    }
+
+    #[test]
+    fn advance_between_counts_whole_clusters() {
+        let shaped = shape_line("e\u{301}x", &style());
+        assert_eq!(shaped.advance_between(0, 3), shaped.width - shaped.advance_between(3, 4));
+        assert_eq!(shaped.x_for_offset(1), 0.0);
+    }
+}
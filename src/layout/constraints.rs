@@ -0,0 +1,65 @@
+use crate::geometry::Size;
+
+// The sizes a parent allows a child to take during measurement. An axis with
+// no upper bound has `f32::INFINITY` as its maximum.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Constraints {
+    pub min: Size,
+    pub max: Size,
+    // What percentage sizes resolve against, usually the parent's content
+    // box. It defaults to `max`, but stays finite when a flex container
+    // measures its children without a bound along the main axis.
+    pub percent_basis: Size,
+}
+
+impl Constraints {
+    pub fn new(min: Size, max: Size) -> Self {
+        Self { min, max, percent_basis: max }
+    }
+
+    pub fn tight(size: Size) -> Self {
+        Self::new(size, size)
+    }
+
+    pub fn loose(max: Size) -> Self {
+        Self::new(Size::new(0.0, 0.0), max)
+    }
+
+    pub fn with_percent_basis(mut self, basis: Size) -> Self {
+        self.percent_basis = basis;
+        self
+    }
+
+    pub fn unbounded() -> Self {
+        Self::loose(Size::new(f32::INFINITY, f32::INFINITY))
+    }
+
+    pub fn has_bounded_width(&self) -> bool {
+        self.max.width.is_finite()
+    }
+
+    pub fn has_bounded_height(&self) -> bool {
+        self.max.height.is_finite()
+    }
+
+    // Clamps `size` into the allowed range; the minimum wins if the two conflict.
+    pub fn constrain(&self, size: Size) -> Size {
+        Size::new(
+            size.width.min(self.max.width).max(self.min.width),
+            size.height.min(self.max.height).max(self.min.height),
+        )
+    }
+
+    // Shrinks both bounds by the given total horizontal and vertical insets,
+    // e.g. to get the constraints for a container's content box.
+    pub fn deflate(&self, horizontal: f32, vertical: f32) -> Self {
+        Self {
+            min: Size::new((self.min.width - horizontal).max(0.0), (self.min.height - vertical).max(0.0)),
+            max: Size::new((self.max.width - horizontal).max(0.0), (self.max.height - vertical).max(0.0)),
+            percent_basis: Size::new(
+                (self.percent_basis.width - horizontal).max(0.0),
+                (self.percent_basis.height - vertical).max(0.0),
+            ),
+        }
+    }
+}
@@ -0,0 +1,464 @@
+use std::ops::Range;
+use crate::geometry::{Rect, Size};
+use crate::style::{AlignItems, AlignSelf, Dimension, EdgeInsets, FlexWrap, JustifyContent, LayoutDirection, Style};
+use super::{content_box, size_range, Layout, LayoutItem};
+
+#[derive(Clone, Copy)]
+struct Axis {
+    horizontal: bool,
+}
+
+impl Axis {
+    fn main(&self, size: Size) -> f32 {
+        if self.horizontal { size.width } else { size.height }
+    }
+
+    fn cross(&self, size: Size) -> f32 {
+        if self.horizontal { size.height } else { size.width }
+    }
+
+    fn main_start(&self, insets: &EdgeInsets) -> f32 {
+        if self.horizontal { insets.left } else { insets.top }
+    }
+
+    fn main_insets(&self, insets: &EdgeInsets) -> f32 {
+        if self.horizontal { insets.horizontal() } else { insets.vertical() }
+    }
+
+    fn cross_start(&self, insets: &EdgeInsets) -> f32 {
+        if self.horizontal { insets.top } else { insets.left }
+    }
+
+    fn cross_insets(&self, insets: &EdgeInsets) -> f32 {
+        if self.horizontal { insets.vertical() } else { insets.horizontal() }
+    }
+
+    fn main_range(&self, style: &Style, reference: f32) -> (f32, f32) {
+        if self.horizontal {
+            size_range(style.min_width, style.max_width, reference)
+        } else {
+            size_range(style.min_height, style.max_height, reference)
+        }
+    }
+
+    fn cross_range(&self, style: &Style, reference: f32) -> (f32, f32) {
+        Axis { horizontal: !self.horizontal }.main_range(style, reference)
+    }
+
+    fn main_dimension(&self, style: &Style) -> Dimension {
+        if self.horizontal { style.width } else { style.height }
+    }
+
+    fn cross_dimension(&self, style: &Style) -> Dimension {
+        if self.horizontal { style.height } else { style.width }
+    }
+
+    fn rect(&self, origin: &Rect, main_pos: f32, cross_pos: f32, main_size: f32, cross_size: f32) -> Rect {
+        if self.horizontal {
+            Rect::new(origin.origin.x + main_pos, origin.origin.y + cross_pos, main_size, cross_size)
+        } else {
+            Rect::new(origin.origin.x + cross_pos, origin.origin.y + main_pos, cross_size, main_size)
+        }
+    }
+}
+
+// Lays `items` out inside `bounds` following the CSS flexbox algorithm and
+// returns one rect per item, in the same coordinate space as `bounds`.
+// `LayoutItem::size` is the item's measured size before flexing. Items whose
+// main size changes while flexing are measured again through
+// `cross_size(index, main_size)`, so e.g. text that wraps once a row
+// shrinks it gets taller.
+pub fn flex_layout<F>(layout: &Layout, container: &Style, bounds: &Rect, items: &[LayoutItem], cross_size: F) -> Vec<Rect>
+where
+    F: Fn(usize, f32) -> f32,
+{
+    let content = content_box(container, bounds);
+    let axis = Axis { horizontal: layout.is_horizontal() };
+    let container_main = axis.main(content.size);
+    let container_cross = axis.cross(content.size);
+    let gap = container.gap;
+    // A right-to-left row runs from the right edge, like a reversed one.
+    let reverse = layout.is_reverse() != (axis.horizontal && container.direction == LayoutDirection::Rtl);
+
+    let bases = flex_bases(axis, items, container_main);
+    let lines = collect_lines(axis, container, items, &bases, container_main);
+    let single_line = container.flex_wrap == FlexWrap::NoWrap;
+    let mut rects = vec![Rect::new(0.0, 0.0, 0.0, 0.0); items.len()];
+    let mut line_offset = 0.0;
+
+    for line in lines {
+        let line_items = &items[line.clone()];
+        let sizes = resolve_flexible_lengths(axis, line_items, &bases[line.clone()], container_main, gap);
+        let crosses = cross_sizes(axis, items, line.clone(), &sizes, &cross_size);
+
+        let used: f32 = line_items
+            .iter()
+            .zip(&sizes)
+            .map(|(item, size)| size + axis.main_insets(&item.style.margin))
+            .sum::<f32>()
+            + gap * (line_items.len() as f32 - 1.0);
+        let (mut cursor, between) = distribute(container.justify_content, container_main - used, line_items.len());
+
+        let line_cross = if single_line {
+            container_cross
+        } else {
+            line_items
+                .iter()
+                .zip(&crosses)
+                .map(|(item, cross)| cross + axis.cross_insets(&item.style.margin))
+                .fold(0.0, f32::max)
+        };
+
+        for (offset, ((item, main_size), item_cross)) in line_items.iter().zip(sizes).zip(crosses).enumerate() {
+            let margin = &item.style.margin;
+            let main_margins = axis.main_insets(margin);
+            // In a reversed layout the item's trailing margin faces main-start.
+            let leading = if reverse {
+                main_margins - axis.main_start(margin)
+            } else {
+                axis.main_start(margin)
+            };
+            let mut main_pos = cursor + leading;
+            cursor += main_size + main_margins + gap + between;
+            if reverse {
+                main_pos = container_main - main_pos - main_size;
+            }
+
+            let cross_start = axis.cross_start(margin);
+            let cross_margins = axis.cross_insets(margin);
+            let (cross_pos, cross_size) = match effective_align(container, item.style) {
+                // Items with a cross size or aspect ratio of their own keep it.
+                AlignItems::Stretch
+                    if axis.cross_dimension(item.style) == Dimension::Auto && item.style.aspect_ratio.is_none() =>
+                {
+                    let (min, max) = axis.cross_range(item.style, container_cross);
+                    (cross_start, (line_cross - cross_margins).clamp(min, max))
+                }
+                AlignItems::Stretch | AlignItems::Start => (cross_start, item_cross),
+                AlignItems::End => (line_cross - item_cross - (cross_margins - cross_start), item_cross),
+                AlignItems::Center => (cross_start + (line_cross - cross_margins - item_cross) / 2.0, item_cross),
+            };
+            let mut cross_pos = line_offset + cross_pos;
+            if container.flex_wrap == FlexWrap::WrapReverse {
+                cross_pos = container_cross - cross_pos - cross_size;
+            }
+
+            rects[line.start + offset] = axis.rect(&content, main_pos, cross_pos, main_size, cross_size);
+        }
+
+        line_offset += line_cross + gap;
+    }
+
+    rects
+}
+
+// The content size a flex container needs to fit `items` without flexing
+// them, given the space available to its content box. Cross sizes are those
+// the items take once flexed into `available`, measured through
+// `cross_size` as in `flex_layout`.
+pub fn flex_size<F>(layout: &Layout, container: &Style, items: &[LayoutItem], available: Size, cross_size: F) -> Size
+where
+    F: Fn(usize, f32) -> f32,
+{
+    let axis = Axis { horizontal: layout.is_horizontal() };
+    let available_main = axis.main(available);
+    let bases = flex_bases(axis, items, available_main);
+
+    let mut main: f32 = 0.0;
+    let mut cross = 0.0;
+    let lines = collect_lines(axis, container, items, &bases, available_main);
+    let line_count = lines.len();
+    for line in lines {
+        let line_main = line
+            .clone()
+            .map(|index| bases[index] + axis.main_insets(&items[index].style.margin))
+            .sum::<f32>()
+            + container.gap * (line.len() as f32 - 1.0);
+        let sizes = if available_main.is_finite() {
+            resolve_flexible_lengths(axis, &items[line.clone()], &bases[line.clone()], available_main, container.gap)
+        } else {
+            bases[line.clone()].to_vec()
+        };
+        let crosses = cross_sizes(axis, items, line.clone(), &sizes, &cross_size);
+        let line_cross = items[line]
+            .iter()
+            .zip(crosses)
+            .map(|(item, cross)| cross + axis.cross_insets(&item.style.margin))
+            .fold(0.0, f32::max);
+        main = main.max(line_main);
+        cross += line_cross;
+    }
+    cross += container.gap * (line_count as f32 - 1.0).max(0.0);
+
+    if axis.horizontal {
+        Size::new(main, cross)
+    } else {
+        Size::new(cross, main)
+    }
+}
+
+// An `auto` basis falls back to the item's main size property, so a
+// percentage width in a row resolves against the row, and then to its
+// measured size.
+fn flex_bases(axis: Axis, items: &[LayoutItem], container_main: f32) -> Vec<f32> {
+    items
+        .iter()
+        .map(|item| {
+            let main_size = match axis.main_dimension(item.style) {
+                Dimension::Percent(_) => axis.main_dimension(item.style),
+                _ => Dimension::Auto,
+            };
+            let basis = match item.style.flex_basis {
+                Dimension::Auto => main_size,
+                basis => basis,
+            };
+            basis
+                .resolve(container_main)
+                .filter(|basis| basis.is_finite())
+                .map(|basis| {
+                    let (min, max) = axis.main_range(item.style, container_main);
+                    basis.clamp(min, max)
+                })
+                .unwrap_or_else(|| axis.main(item.size))
+                .max(0.0)
+        })
+        .collect()
+}
+
+// The cross size of each item in `line` at its flexed main size. Items whose
+// main size is unchanged keep their measured cross size.
+fn cross_sizes<F>(axis: Axis, items: &[LayoutItem], line: Range<usize>, sizes: &[f32], cross_size: &F) -> Vec<f32>
+where
+    F: Fn(usize, f32) -> f32,
+{
+    line.zip(sizes)
+        .map(|(index, &main)| {
+            let measured = items[index].size;
+            if (main - axis.main(measured)).abs() < 0.01 {
+                axis.cross(measured)
+            } else {
+                cross_size(index, main)
+            }
+        })
+        .collect()
+}
+
+fn collect_lines(axis: Axis, container: &Style, items: &[LayoutItem], bases: &[f32], container_main: f32) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut line_main = 0.0;
+
+    for (index, item) in items.iter().enumerate() {
+        let outer = bases[index] + axis.main_insets(&item.style.margin);
+        if index == start {
+            line_main = outer;
+        } else if container.flex_wrap != FlexWrap::NoWrap && line_main + container.gap + outer > container_main {
+            lines.push(start..index);
+            start = index;
+            line_main = outer;
+        } else {
+            line_main += container.gap + outer;
+        }
+    }
+    if start < items.len() {
+        lines.push(start..items.len());
+    }
+    lines
+}
+
+// Grows items into positive free space by `flex_grow`, or shrinks them out of
+// negative free space in proportion to `flex_shrink * basis`. An item pushed
+// past its min or max main size is frozen there and the rest share what it
+// could not take, repeating until no item is clamped.
+fn resolve_flexible_lengths(axis: Axis, items: &[LayoutItem], bases: &[f32], container_main: f32, gap: f32) -> Vec<f32> {
+    let margins: f32 = items.iter().map(|item| axis.main_insets(&item.style.margin)).sum();
+    let space = container_main - margins - gap * (items.len() as f32 - 1.0);
+    let growing = bases.iter().sum::<f32>() < space;
+    let factor = |index: usize| {
+        let style = items[index].style;
+        if growing { style.flex_grow.max(0.0) } else { style.flex_shrink.max(0.0) * bases[index] }
+    };
+    let ranges: Vec<(f32, f32)> = items.iter().map(|item| axis.main_range(item.style, container_main)).collect();
+    let mut sizes: Vec<f32> = bases.iter().zip(&ranges).map(|(basis, &(min, max))| basis.clamp(min, max)).collect();
+    // Items that cannot flex, or whose range already pushes them the other
+    // way, keep their clamped basis.
+    let mut frozen: Vec<bool> = (0..items.len())
+        .map(|index| factor(index) == 0.0 || (growing && bases[index] > sizes[index]) || (!growing && bases[index] < sizes[index]))
+        .collect();
+
+    while frozen.contains(&false) {
+        let unfrozen: Vec<usize> = (0..items.len()).filter(|&index| !frozen[index]).collect();
+        let used: f32 = (0..items.len()).map(|index| if frozen[index] { sizes[index] } else { bases[index] }).sum();
+        let free = space - used;
+        let total: f32 = unfrozen.iter().map(|&index| factor(index)).sum();
+
+        let mut targets = Vec::with_capacity(unfrozen.len());
+        let mut violation = 0.0;
+        for &index in &unfrozen {
+            let target = if total > 0.0 { bases[index] + free * factor(index) / total } else { bases[index] };
+            let (min, max) = ranges[index];
+            sizes[index] = target.max(0.0).clamp(min, max);
+            violation += sizes[index] - target;
+            targets.push(target);
+        }
+        // Freeze every item clamped in the direction that dominates, or all
+        // of them once nothing is clamped overall.
+        for (&index, target) in unfrozen.iter().zip(targets) {
+            let clamped = sizes[index] - target;
+            frozen[index] = violation == 0.0 || (violation > 0.0 && clamped > 0.0) || (violation < 0.0 && clamped < 0.0);
+        }
+    }
+    sizes
+}
+
+// Returns the leading offset and the extra space between consecutive items.
+fn distribute(justify: JustifyContent, free: f32, count: usize) -> (f32, f32) {
+    let count = count as f32;
+    let positive = free.max(0.0);
+    match justify {
+        JustifyContent::Start => (0.0, 0.0),
+        JustifyContent::End => (free, 0.0),
+        JustifyContent::Center => (free / 2.0, 0.0),
+        JustifyContent::SpaceBetween if count > 1.0 => (0.0, positive / (count - 1.0)),
+        JustifyContent::SpaceBetween => (0.0, 0.0),
+        JustifyContent::SpaceAround => (positive / count / 2.0, positive / count),
+        JustifyContent::SpaceEvenly => (positive / (count + 1.0), positive / (count + 1.0)),
+    }
+}
+
+pub(super) fn effective_align(container: &Style, item: &Style) -> AlignItems {
+    match item.align_self {
+        AlignSelf::Auto => container.align_items,
+        AlignSelf::Stretch => AlignItems::Stretch,
+        AlignSelf::Start => AlignItems::Start,
+        AlignSelf::End => AlignItems::End,
+        AlignSelf::Center => AlignItems::Center,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+    use crate::style::Dimension;
+
+    fn layout(layout: Layout, container: &Style, width: f32, height: f32, items: &[(Style, Size)]) -> Vec<Rect> {
+        let items: Vec<LayoutItem> = items.iter().map(|(style, size)| LayoutItem { style, size: *size }).collect();
+        flex_layout(&layout, container, &Rect::new(0.0, 0.0, width, height), &items, |index, _| items[index].size.height)
+    }
+
+    fn item(style: Style, width: f32, height: f32) -> (Style, Size) {
+        (style, Size::new(width, height))
+    }
+
+    #[test]
+    fn grow_shares_free_space_by_factor() {
+        let items = [
+            item(Style::default().set_flex_grow(1.0), 0.0, 10.0),
+            item(Style::default().set_flex_grow(3.0), 0.0, 10.0),
+        ];
+        let rects = layout(Layout::Row, &Style::default(), 200.0, 10.0, &items);
+        assert_eq!(rects[0], Rect::new(0.0, 0.0, 50.0, 10.0));
+        assert_eq!(rects[1], Rect::new(50.0, 0.0, 150.0, 10.0));
+    }
+
+    #[test]
+    fn shrink_is_weighted_by_basis() {
+        let items = [
+            item(Style::default().set_flex_shrink(1.0), 100.0, 10.0),
+            item(Style::default().set_flex_shrink(1.0), 300.0, 10.0),
+        ];
+        let rects = layout(Layout::Row, &Style::default(), 200.0, 10.0, &items);
+        assert_eq!(rects[0].size.width, 50.0);
+        assert_eq!(rects[1].size.width, 150.0);
+    }
+
+    #[test]
+    fn clamped_items_freeze_and_siblings_take_the_rest() {
+        // Equal shares would be 100 each; the first stops at its max and
+        // the second takes the space it left.
+        let items = [
+            item(Style::default().set_flex_grow(1.0).set_max_width(Dimension::Points(40.0)), 0.0, 10.0),
+            item(Style::default().set_flex_grow(1.0), 0.0, 10.0),
+        ];
+        let rects = layout(Layout::Row, &Style::default(), 200.0, 10.0, &items);
+        assert_eq!(rects[0].size.width, 40.0);
+        assert_eq!(rects[1], Rect::new(40.0, 0.0, 160.0, 10.0));
+
+        // Shrinking evenly would take both to 100; the first stops at its
+        // min and the second gives up the rest.
+        let items = [
+            item(Style::default().set_min_width(Dimension::Points(140.0)), 150.0, 10.0),
+            item(Style::default(), 150.0, 10.0),
+        ];
+        let rects = layout(Layout::Row, &Style::default(), 200.0, 10.0, &items);
+        assert_eq!(rects[0].size.width, 140.0);
+        assert_eq!(rects[1], Rect::new(140.0, 0.0, 60.0, 10.0));
+    }
+
+    #[test]
+    fn wrap_moves_overflowing_items_to_a_new_line() {
+        let container = Style::default().set_flex_wrap(FlexWrap::Wrap).set_gap(10.0);
+        let items = [
+            item(Style::default(), 60.0, 20.0),
+            item(Style::default(), 60.0, 30.0),
+            item(Style::default(), 60.0, 20.0),
+        ];
+        let rects = layout(Layout::Row, &container, 150.0, 100.0, &items);
+        assert_eq!(rects[1].origin, Point::new(70.0, 0.0));
+        assert_eq!(rects[2].origin, Point::new(0.0, 40.0));
+    }
+
+    #[test]
+    fn justify_content_distributes_free_space() {
+        let items = [item(Style::default(), 20.0, 10.0), item(Style::default(), 20.0, 10.0)];
+        let starts = |justify| {
+            let container = Style::default().set_justify_content(justify);
+            layout(Layout::Row, &container, 100.0, 10.0, &items)
+                .iter()
+                .map(|rect| rect.origin.x)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(starts(JustifyContent::End), [60.0, 80.0]);
+        assert_eq!(starts(JustifyContent::Center), [30.0, 50.0]);
+        assert_eq!(starts(JustifyContent::SpaceBetween), [0.0, 80.0]);
+        assert_eq!(starts(JustifyContent::SpaceAround), [15.0, 65.0]);
+        assert_eq!(starts(JustifyContent::SpaceEvenly), [20.0, 60.0]);
+    }
+
+    #[test]
+    fn align_items_places_items_on_the_cross_axis() {
+        let items = [
+            item(Style::default(), 20.0, 10.0),
+            item(Style::default().set_align_self(AlignSelf::Center), 20.0, 10.0),
+            item(Style::default().set_align_self(AlignSelf::End), 20.0, 10.0),
+        ];
+        let rects = layout(Layout::Row, &Style::default(), 100.0, 50.0, &items);
+        assert_eq!(rects[0].size.height, 50.0);
+        assert_eq!(rects[1].origin.y, 20.0);
+        assert_eq!(rects[2].origin.y, 40.0);
+    }
+
+    #[test]
+    fn rtl_rows_start_at_the_right_edge() {
+        let container = Style::default().set_direction(LayoutDirection::Rtl);
+        let items = [item(Style::default(), 20.0, 10.0), item(Style::default(), 30.0, 10.0)];
+        let rects = layout(Layout::Row, &container, 100.0, 10.0, &items);
+        assert_eq!(rects[0].origin.x, 80.0);
+        assert_eq!(rects[1].origin.x, 50.0);
+    }
+
+    #[test]
+    fn percent_basis_resolves_against_the_container() {
+        let items = [item(Style::default().set_flex_basis(Dimension::Percent(25.0)), 0.0, 10.0)];
+        let rects = layout(Layout::Row, &Style::default(), 200.0, 10.0, &items);
+        assert_eq!(rects[0].size.width, 50.0);
+    }
+
+    #[test]
+    fn percent_widths_resolve_against_the_row() {
+        let items = [item(Style::default().set_width(Dimension::Percent(25.0)), 0.0, 10.0)];
+        let rects = layout(Layout::Row, &Style::default(), 200.0, 10.0, &items);
+        assert_eq!(rects[0].size.width, 50.0);
+    }
+}
@@ -0,0 +1,719 @@
+use crate::geometry::{Rect, Size};
+use crate::style::{AlignItems, AlignSelf, Dimension, Style};
+use super::{content_box, flex::effective_align, size_range, LayoutItem};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrackBreadth {
+    Fixed(f32),
+    Fraction(f32),
+    Auto,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrackSize {
+    Fixed(f32),
+    Fraction(f32),
+    Auto,
+    MinMax(TrackBreadth, TrackBreadth),
+}
+
+impl TrackSize {
+    pub fn fr(fraction: f32) -> Self {
+        TrackSize::Fraction(fraction)
+    }
+
+    pub fn minmax(min: TrackBreadth, max: TrackBreadth) -> Self {
+        TrackSize::MinMax(min, max)
+    }
+
+    // As in CSS, a bare `1fr` behaves like `minmax(auto, 1fr)` and a flexible
+    // minimum is treated as `auto`.
+    fn min_breadth(&self) -> TrackBreadth {
+        match self {
+            TrackSize::Fixed(points) => TrackBreadth::Fixed(*points),
+            TrackSize::MinMax(TrackBreadth::Fixed(points), _) => TrackBreadth::Fixed(*points),
+            _ => TrackBreadth::Auto,
+        }
+    }
+
+    fn max_breadth(&self) -> TrackBreadth {
+        match self {
+            TrackSize::Fixed(points) => TrackBreadth::Fixed(*points),
+            TrackSize::Fraction(fraction) => TrackBreadth::Fraction(*fraction),
+            TrackSize::Auto => TrackBreadth::Auto,
+            TrackSize::MinMax(_, max) => *max,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridArea {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridTemplate {
+    pub columns: Vec<TrackSize>,
+    pub rows: Vec<TrackSize>,
+    pub auto_columns: TrackSize,
+    pub auto_rows: TrackSize,
+    pub row_gap: Option<f32>,
+    pub column_gap: Option<f32>,
+    areas: Vec<(String, GridArea)>,
+}
+
+impl Default for GridTemplate {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            auto_columns: TrackSize::Auto,
+            auto_rows: TrackSize::Auto,
+            row_gap: None,
+            column_gap: None,
+            areas: Vec::new(),
+        }
+    }
+}
+
+impl GridTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_columns(mut self, columns: Vec<TrackSize>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn with_rows(mut self, rows: Vec<TrackSize>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    pub fn with_auto_columns(mut self, track: TrackSize) -> Self {
+        self.auto_columns = track;
+        self
+    }
+
+    pub fn with_auto_rows(mut self, track: TrackSize) -> Self {
+        self.auto_rows = track;
+        self
+    }
+
+    pub fn with_gaps(mut self, row_gap: f32, column_gap: f32) -> Self {
+        self.row_gap = Some(row_gap);
+        self.column_gap = Some(column_gap);
+        self
+    }
+
+    // Named areas in `grid-template-areas` form, one string per row with
+    // whitespace-separated cell names; `.` marks an unnamed cell. Each name
+    // covers the bounding box of the cells that carry it.
+    pub fn with_areas(mut self, rows: &[&str]) -> Self {
+        self.areas.clear();
+        for (row, line) in rows.iter().enumerate() {
+            for (column, name) in line.split_whitespace().enumerate() {
+                if name.chars().all(|c| c == '.') {
+                    continue;
+                }
+                match self.areas.iter_mut().find(|(existing, _)| existing == name) {
+                    Some((_, area)) => {
+                        let row_end = (area.row + area.row_span).max(row + 1);
+                        let column_end = (area.column + area.column_span).max(column + 1);
+                        area.row = area.row.min(row);
+                        area.column = area.column.min(column);
+                        area.row_span = row_end - area.row;
+                        area.column_span = column_end - area.column;
+                    }
+                    None => self.areas.push((name.to_string(), GridArea {
+                        row,
+                        column,
+                        row_span: 1,
+                        column_span: 1,
+                    })),
+                }
+            }
+        }
+        self
+    }
+
+    pub fn area(&self, name: &str) -> Option<GridArea> {
+        self.areas.iter().find(|(existing, _)| existing == name).map(|(_, area)| *area)
+    }
+}
+
+struct ResolvedGrid {
+    areas: Vec<GridArea>,
+    column_sizes: Vec<f32>,
+    row_sizes: Vec<f32>,
+    column_gap: f32,
+    row_gap: f32,
+    // Each item's height at the width its column span gives it.
+    heights: Vec<f32>,
+}
+
+impl ResolvedGrid {
+    // Columns are sized first, from each item's min-content width as given by
+    // `min_width_for(index)` up to its measured width. Items whose width then
+    // differs from their measured one are measured again through
+    // `height_for(index, width)` before the rows are sized.
+    fn new<F, G>(
+        template: &GridTemplate,
+        container: &Style,
+        items: &[LayoutItem],
+        available: Size,
+        measure: &GridMeasure<F, G>,
+    ) -> Self
+    where
+        F: Fn(usize) -> f32,
+        G: Fn(usize, f32) -> f32,
+    {
+        let column_gap = template.column_gap.unwrap_or(container.gap);
+        let row_gap = template.row_gap.unwrap_or(container.gap);
+
+        let areas = place_items(template, items);
+        let column_count = areas.iter().map(|a| a.column + a.column_span).max().unwrap_or(0).max(template.columns.len());
+        let row_count = areas.iter().map(|a| a.row + a.row_span).max().unwrap_or(0).max(template.rows.len());
+        let columns = tracks(&template.columns, template.auto_columns, column_count);
+        let rows = tracks(&template.rows, template.auto_rows, row_count);
+
+        let column_sizes = size_tracks(
+            &columns,
+            available.width,
+            column_gap,
+            &areas.iter().zip(items).enumerate().map(|(index, (area, item))| {
+                let margin = item.style.margin.horizontal();
+                let min_size = (measure.min_width_for)(index).min(item.size.width) + margin;
+                Contribution {
+                    start: area.column,
+                    span: area.column_span,
+                    min_size,
+                    size: item.size.width + margin,
+                }
+            }).collect::<Vec<_>>(),
+        );
+        let heights: Vec<f32> = areas
+            .iter()
+            .zip(items)
+            .enumerate()
+            .map(|(index, (area, item))| {
+                let area_width = span_size(&column_sizes, area.column, area.column_span, column_gap);
+                let (_, width) = justify_item(container, item, area_width, available.width);
+                if (width - item.size.width).abs() < 0.01 {
+                    item.size.height
+                } else {
+                    (measure.height_for)(index, width)
+                }
+            })
+            .collect();
+        let row_sizes = size_tracks(
+            &rows,
+            available.height,
+            row_gap,
+            &areas.iter().zip(items).zip(&heights).map(|((area, item), height)| Contribution {
+                start: area.row,
+                span: area.row_span,
+                min_size: height + item.style.margin.vertical(),
+                size: height + item.style.margin.vertical(),
+            }).collect::<Vec<_>>(),
+        );
+
+        Self { areas, column_sizes, row_sizes, column_gap, row_gap, heights }
+    }
+}
+
+// The max-content size of a grid container's content box: every track at the
+// size its items need, with `fr` tracks kept in proportion. When that is
+// wider than `available`, the columns are sized to fit it instead.
+pub fn grid_size<F, G>(
+    template: &GridTemplate,
+    container: &Style,
+    items: &[LayoutItem],
+    available: Size,
+    measure: GridMeasure<F, G>,
+) -> Size
+where
+    F: Fn(usize) -> f32,
+    G: Fn(usize, f32) -> f32,
+{
+    let unbounded = Size::new(f32::INFINITY, f32::INFINITY);
+    let mut grid = ResolvedGrid::new(template, container, items, unbounded, &measure);
+    let width = span_size(&grid.column_sizes, 0, grid.column_sizes.len(), grid.column_gap);
+    if available.width.is_finite() && width > available.width {
+        let bounded = Size::new(available.width, f32::INFINITY);
+        grid = ResolvedGrid::new(template, container, items, bounded, &measure);
+    }
+    Size::new(
+        span_size(&grid.column_sizes, 0, grid.column_sizes.len(), grid.column_gap).max(0.0),
+        span_size(&grid.row_sizes, 0, grid.row_sizes.len(), grid.row_gap).max(0.0),
+    )
+}
+
+// Places and sizes `items` on the grid described by `template` and returns one
+// rect per item, in the same coordinate space as `bounds`. Columns can shrink
+// items to their min-content width, and items are measured again once their
+// columns are sized, so wrapping text gets the height it needs at its final
+// width.
+pub fn grid_layout<F, G>(
+    template: &GridTemplate,
+    container: &Style,
+    bounds: &Rect,
+    items: &[LayoutItem],
+    measure: GridMeasure<F, G>,
+) -> Vec<Rect>
+where
+    F: Fn(usize) -> f32,
+    G: Fn(usize, f32) -> f32,
+{
+    let content = content_box(container, bounds);
+    let ResolvedGrid { areas, column_sizes, row_sizes, column_gap, row_gap, heights } =
+        ResolvedGrid::new(template, container, items, content.size, &measure);
+
+    let column_offsets = offsets(&column_sizes, column_gap);
+    let row_offsets = offsets(&row_sizes, row_gap);
+
+    areas
+        .iter()
+        .zip(items)
+        .zip(heights)
+        .map(|((area, item), item_height)| {
+            let margin = item.style.margin;
+            let x = content.origin.x + column_offsets[area.column];
+            let y = content.origin.y + row_offsets[area.row];
+            let width = span_size(&column_sizes, area.column, area.column_span, column_gap);
+            let height = span_size(&row_sizes, area.row, area.row_span, row_gap);
+
+            let item_height = item_height.min(height - margin.vertical()).max(0.0);
+            let (y, height) = match effective_align(container, item.style) {
+                AlignItems::Stretch if item.style.height == Dimension::Auto && item.style.aspect_ratio.is_none() => {
+                    let (min, max) = size_range(item.style.min_height, item.style.max_height, content.size.height);
+                    (y + margin.top, (height - margin.vertical()).clamp(min, max))
+                }
+                AlignItems::Stretch | AlignItems::Start => (y + margin.top, item_height),
+                AlignItems::End => (y + height - margin.bottom - item_height, item_height),
+                AlignItems::Center => (y + margin.top + (height - margin.vertical() - item_height) / 2.0, item_height),
+            };
+
+            let (offset, width) = justify_item(container, item, width, content.size.width);
+
+            Rect::new(x + offset, y, width, height.max(0.0))
+        })
+        .collect()
+}
+
+// The horizontal offset of an item within its area of `area_width`, and its
+// width. Percentages in min and max widths resolve against `content_width`.
+fn justify_item(container: &Style, item: &LayoutItem, area_width: f32, content_width: f32) -> (f32, f32) {
+    let margin = item.style.margin;
+    let item_width = item.size.width.min(area_width - margin.horizontal()).max(0.0);
+    let (offset, width) = match effective_justify(container, item.style) {
+        AlignItems::Stretch if item.style.width == Dimension::Auto => {
+            let (min, max) = size_range(item.style.min_width, item.style.max_width, content_width);
+            (margin.left, (area_width - margin.horizontal()).clamp(min, max))
+        }
+        AlignItems::Stretch | AlignItems::Start => (margin.left, item_width),
+        AlignItems::End => (area_width - margin.right - item_width, item_width),
+        AlignItems::Center => (margin.left + (area_width - margin.horizontal() - item_width) / 2.0, item_width),
+    };
+    (offset, width.max(0.0))
+}
+
+fn effective_justify(container: &Style, item: &Style) -> AlignItems {
+    match item.justify_self {
+        AlignSelf::Auto => container.justify_items,
+        AlignSelf::Stretch => AlignItems::Stretch,
+        AlignSelf::Start => AlignItems::Start,
+        AlignSelf::End => AlignItems::End,
+        AlignSelf::Center => AlignItems::Center,
+    }
+}
+
+// Resolves every item to a grid area using the CSS "sparse" auto-placement
+// order: fully positioned items first, then row-locked items, then the rest.
+fn place_items(template: &GridTemplate, items: &[LayoutItem]) -> Vec<GridArea> {
+    let named_columns = template.areas.iter().map(|(_, a)| a.column + a.column_span).max().unwrap_or(0);
+    let explicit_columns = items
+        .iter()
+        .filter_map(|item| item.style.grid_column.start.map(|start| start + item.style.grid_column.span.max(1)))
+        .max()
+        .unwrap_or(0);
+    let column_count = template.columns.len().max(named_columns).max(explicit_columns).max(1);
+
+    let mut occupancy = Occupancy { columns: column_count, cells: Vec::new() };
+    let mut areas: Vec<Option<GridArea>> = vec![None; items.len()];
+
+    for (index, item) in items.iter().enumerate() {
+        let style = item.style;
+        let named = style.grid_area.as_deref().and_then(|name| template.area(name));
+        let area = match (named, style.grid_row.start, style.grid_column.start) {
+            (Some(area), _, _) => area,
+            (None, Some(row), Some(column)) => GridArea {
+                row,
+                column,
+                row_span: style.grid_row.span.max(1),
+                column_span: style.grid_column.span.max(1),
+            },
+            _ => continue,
+        };
+        occupancy.mark(&area);
+        areas[index] = Some(area);
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        let style = item.style;
+        let Some(row) = style.grid_row.start else { continue };
+        if areas[index].is_some() {
+            continue;
+        }
+        let row_span = style.grid_row.span.max(1);
+        let column_span = style.grid_column.span.max(1).min(column_count);
+        // When the row is full the item goes past the last column, adding
+        // implicit columns rather than overlapping another item.
+        let column = (0..)
+            .find(|&column| occupancy.is_free(row, column, row_span, column_span))
+            .expect("columns past the occupied ones are free");
+        let area = GridArea { row, column, row_span, column_span };
+        occupancy.mark(&area);
+        areas[index] = Some(area);
+    }
+
+    let (mut cursor_row, mut cursor_column) = (0, 0);
+    for (index, item) in items.iter().enumerate() {
+        if areas[index].is_some() {
+            continue;
+        }
+        let style = item.style;
+        let row_span = style.grid_row.span.max(1);
+        let column_span = style.grid_column.span.max(1).min(column_count);
+
+        let area = match style.grid_column.start {
+            Some(column) => {
+                if column < cursor_column {
+                    cursor_row += 1;
+                }
+                let mut row = cursor_row;
+                while !occupancy.is_free(row, column, row_span, column_span) {
+                    row += 1;
+                }
+                GridArea { row, column, row_span, column_span }
+            }
+            None => loop {
+                if cursor_column + column_span > column_count {
+                    cursor_row += 1;
+                    cursor_column = 0;
+                    continue;
+                }
+                if occupancy.is_free(cursor_row, cursor_column, row_span, column_span) {
+                    break GridArea { row: cursor_row, column: cursor_column, row_span, column_span };
+                }
+                cursor_column += 1;
+            },
+        };
+
+        cursor_row = area.row;
+        cursor_column = area.column + area.column_span;
+        occupancy.mark(&area);
+        areas[index] = Some(area);
+    }
+
+    areas.into_iter().map(|area| area.expect("every grid item is placed")).collect()
+}
+
+struct Occupancy {
+    columns: usize,
+    cells: Vec<Vec<bool>>,
+}
+
+impl Occupancy {
+    fn is_free(&self, row: usize, column: usize, row_span: usize, column_span: usize) -> bool {
+        (row..row + row_span).all(|r| {
+            (column..column + column_span).all(|c| {
+                self.cells.get(r).and_then(|cells| cells.get(c)).map(|taken| !taken).unwrap_or(true)
+            })
+        })
+    }
+
+    fn mark(&mut self, area: &GridArea) {
+        for row in area.row..area.row + area.row_span {
+            while self.cells.len() <= row {
+                self.cells.push(vec![false; self.columns]);
+            }
+            let cells = &mut self.cells[row];
+            if cells.len() < area.column + area.column_span {
+                cells.resize(area.column + area.column_span, false);
+            }
+            for taken in &mut cells[area.column..area.column + area.column_span] {
+                *taken = true;
+            }
+        }
+    }
+}
+
+fn tracks(explicit: &[TrackSize], implicit: TrackSize, count: usize) -> Vec<TrackSize> {
+    let mut tracks = explicit.to_vec();
+    while tracks.len() < count {
+        tracks.push(implicit);
+    }
+    tracks
+}
+
+// Measures grid items again once the grid knows more about their space:
+// `min_width_for(index)` gives an item's min-content width, the narrowest it
+// can wrap to, and `height_for(index, width)` its height at a column width.
+pub struct GridMeasure<F, G> {
+    pub min_width_for: F,
+    pub height_for: G,
+}
+
+// An item's min-content and max-content size along one axis, margins
+// included.
+struct Contribution {
+    start: usize,
+    span: usize,
+    min_size: f32,
+    size: f32,
+}
+
+// A reduced form of the CSS track sizing algorithm: resolve fixed and
+// content-based minimums, grow tracks towards their limits, then hand the
+// remaining space to `fr` tracks (or stretch `auto` tracks if there are none).
+fn size_tracks(tracks: &[TrackSize], available: f32, gap: f32, contributions: &[Contribution]) -> Vec<f32> {
+    let count = tracks.len();
+    let mut base: Vec<f32> = tracks
+        .iter()
+        .map(|track| match track.min_breadth() {
+            TrackBreadth::Fixed(points) => points,
+            _ => 0.0,
+        })
+        .collect();
+    let mut content_max = vec![0.0f32; count];
+
+    for contribution in contributions.iter().filter(|c| c.span == 1) {
+        let index = contribution.start;
+        if tracks[index].min_breadth() == TrackBreadth::Auto {
+            base[index] = base[index].max(contribution.min_size);
+        }
+        content_max[index] = content_max[index].max(contribution.size);
+    }
+
+    let mut spanning: Vec<&Contribution> = contributions.iter().filter(|c| c.span > 1).collect();
+    spanning.sort_by_key(|c| c.span);
+    for contribution in spanning {
+        let range = contribution.start..contribution.start + contribution.span;
+        let gaps = gap * (contribution.span as f32 - 1.0);
+        let mut targets: Vec<usize> = range
+            .clone()
+            .filter(|&i| tracks[i].min_breadth() == TrackBreadth::Auto && !is_flexible(&tracks[i]))
+            .collect();
+        if targets.is_empty() {
+            targets = range.clone().filter(|&i| tracks[i].min_breadth() == TrackBreadth::Auto).collect();
+        }
+        if targets.is_empty() {
+            continue;
+        }
+        let needed = contribution.min_size - base[range.clone()].iter().sum::<f32>() - gaps;
+        if needed > 0.0 {
+            for &i in &targets {
+                base[i] += needed / targets.len() as f32;
+            }
+        }
+        for &i in &targets {
+            content_max[i] = content_max[i].max(base[i]);
+        }
+        let needed = contribution.size - content_max[range].iter().sum::<f32>() - gaps;
+        if needed > 0.0 {
+            for &i in &targets {
+                content_max[i] += needed / targets.len() as f32;
+            }
+        }
+    }
+
+    let limits: Vec<f32> = tracks
+        .iter()
+        .enumerate()
+        .map(|(i, track)| match track.max_breadth() {
+            TrackBreadth::Fixed(points) => points.max(base[i]),
+            TrackBreadth::Auto => content_max[i].max(base[i]),
+            TrackBreadth::Fraction(_) => base[i],
+        })
+        .collect();
+
+    let gaps = gap * (count.saturating_sub(1)) as f32;
+    let mut sizes = base;
+
+    // Grow towards growth limits, sharing the free space equally.
+    let mut free = available - sizes.iter().sum::<f32>() - gaps;
+    while free > 0.01 {
+        let growable: Vec<usize> = (0..count).filter(|&i| sizes[i] < limits[i]).collect();
+        if growable.is_empty() {
+            break;
+        }
+        let share = free / growable.len() as f32;
+        for i in growable {
+            let grow = share.min(limits[i] - sizes[i]);
+            sizes[i] += grow;
+            free -= grow;
+        }
+    }
+
+    let flexible: Vec<usize> = (0..count).filter(|&i| is_flexible(&tracks[i])).collect();
+    let factor = |i: usize| match tracks[i].max_breadth() {
+        TrackBreadth::Fraction(fraction) => fraction.max(0.0),
+        _ => 0.0,
+    };
+    if !available.is_finite() {
+        // Measuring with unbounded space: size `fr` tracks so that each gets
+        // at least its content while keeping their ratios.
+        let unit = flexible
+            .iter()
+            .filter(|&&i| factor(i) > 0.0)
+            .map(|&i| sizes[i].max(content_max[i]) / factor(i))
+            .fold(0.0, f32::max);
+        for &i in &flexible {
+            sizes[i] = sizes[i].max(unit * factor(i));
+        }
+    } else if !flexible.is_empty() {
+        let fixed: f32 = (0..count).filter(|i| !flexible.contains(i)).map(|i| sizes[i]).sum();
+        let mut frozen = vec![false; count];
+
+        // A track whose minimum exceeds its share of the flexible space keeps
+        // its minimum; the rest split what is left.
+        loop {
+            let frozen_size: f32 = flexible.iter().filter(|&&i| frozen[i]).map(|&i| sizes[i]).sum();
+            let leftover = (available - fixed - gaps - frozen_size).max(0.0);
+            let total: f32 = flexible.iter().filter(|&&i| !frozen[i]).map(|&i| factor(i)).sum::<f32>().max(1.0);
+            let unit = leftover / total;
+
+            let overflowing: Vec<usize> = flexible
+                .iter()
+                .copied()
+                .filter(|&i| !frozen[i] && sizes[i] > unit * factor(i))
+                .collect();
+            for &i in &overflowing {
+                frozen[i] = true;
+            }
+            if overflowing.is_empty() {
+                for &i in flexible.iter().filter(|&&i| !frozen[i]) {
+                    sizes[i] = unit * factor(i);
+                }
+                break;
+            }
+        }
+    } else {
+        let free = available - sizes.iter().sum::<f32>() - gaps;
+        let auto: Vec<usize> = (0..count).filter(|&i| tracks[i].max_breadth() == TrackBreadth::Auto).collect();
+        if free > 0.0 && !auto.is_empty() {
+            for &i in &auto {
+                sizes[i] += free / auto.len() as f32;
+            }
+        }
+    }
+
+    sizes
+}
+
+fn is_flexible(track: &TrackSize) -> bool {
+    matches!(track.max_breadth(), TrackBreadth::Fraction(_))
+}
+
+fn offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+    let mut offsets = Vec::with_capacity(sizes.len());
+    let mut cursor = 0.0;
+    for size in sizes {
+        offsets.push(cursor);
+        cursor += size + gap;
+    }
+    offsets
+}
+
+fn span_size(sizes: &[f32], start: usize, span: usize, gap: f32) -> f32 {
+    sizes[start..start + span].iter().sum::<f32>() + gap * (span as f32 - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::{Point, Size};
+    use crate::style::GridPlacement;
+
+    fn layout(template: &GridTemplate, container: &Style, width: f32, styles: &[Style], size: Size) -> Vec<Rect> {
+        let items: Vec<LayoutItem> = styles.iter().map(|style| LayoutItem { style, size }).collect();
+        let bounds = Rect::new(0.0, 0.0, width, 0.0);
+        let measure = GridMeasure { min_width_for: |_| 0.0, height_for: |_, _| size.height };
+        grid_layout(template, container, &bounds, &items, measure)
+    }
+
+    #[test]
+    fn fr_columns_split_the_free_space() {
+        let template = GridTemplate::new()
+            .with_columns(vec![TrackSize::Fixed(40.0), TrackSize::fr(1.0), TrackSize::fr(2.0)])
+            .with_gaps(0.0, 10.0);
+        let styles = vec![Style::default(); 3];
+        let rects = layout(&template, &Style::default(), 330.0, &styles, Size::new(0.0, 20.0));
+        let columns: Vec<(f32, f32)> = rects.iter().map(|rect| (rect.origin.x, rect.size.width)).collect();
+        assert_eq!(columns, [(0.0, 40.0), (50.0, 90.0), (150.0, 180.0)]);
+    }
+
+    #[test]
+    fn auto_placement_fills_rows_in_order() {
+        let template = GridTemplate::new().with_columns(vec![TrackSize::fr(1.0); 2]);
+        let styles = vec![Style::default(); 3];
+        let rects = layout(&template, &Style::default(), 100.0, &styles, Size::new(0.0, 20.0));
+        assert_eq!(rects[1].origin, Point::new(50.0, 0.0));
+        assert_eq!(rects[2].origin, Point::new(0.0, 20.0));
+    }
+
+    #[test]
+    fn items_past_the_explicit_columns_add_implicit_ones() {
+        let template = GridTemplate::new()
+            .with_columns(vec![TrackSize::Fixed(50.0)])
+            .with_auto_columns(TrackSize::Fixed(30.0));
+        let styles = [
+            Style::default().set_grid_column(GridPlacement::at(0)).set_grid_row(GridPlacement::at(0)),
+            Style::default().set_grid_column(GridPlacement::at(2)).set_grid_row(GridPlacement::at(0)),
+        ];
+        let rects = layout(&template, &Style::default(), 200.0, &styles, Size::new(0.0, 20.0));
+        assert_eq!(rects[1], Rect::new(80.0, 0.0, 30.0, 20.0));
+        assert_eq!(rects[0], Rect::new(0.0, 0.0, 50.0, 20.0));
+    }
+
+    #[test]
+    fn named_areas_place_items() {
+        let template = GridTemplate::new()
+            .with_columns(vec![TrackSize::Fixed(40.0), TrackSize::fr(1.0)])
+            .with_areas(&["side main", "side footer"]);
+        let styles = [
+            Style::default().set_grid_area("footer"),
+            Style::default().set_grid_area("side"),
+            Style::default().set_grid_area("main"),
+        ];
+        let rects = layout(&template, &Style::default(), 100.0, &styles, Size::new(0.0, 20.0));
+        assert_eq!(rects[0], Rect::new(40.0, 20.0, 60.0, 20.0));
+        assert_eq!(rects[1], Rect::new(0.0, 0.0, 40.0, 40.0));
+    }
+
+    #[test]
+    fn row_locked_items_skip_occupied_cells() {
+        let template = GridTemplate::new().with_columns(vec![TrackSize::fr(1.0); 2]);
+        let styles = [
+            Style::default().set_grid_column(GridPlacement::at(0)).set_grid_row(GridPlacement::at(0)),
+            Style::default().set_grid_row(GridPlacement::at(0)),
+        ];
+        let rects = layout(&template, &Style::default(), 100.0, &styles, Size::new(0.0, 20.0));
+        assert_eq!(rects[1].origin, Point::new(50.0, 0.0));
+    }
+
+    #[test]
+    fn justify_items_aligns_within_the_area() {
+        let template = GridTemplate::new().with_columns(vec![TrackSize::Fixed(100.0)]);
+        let container = Style::default().set_justify_items(AlignItems::Center);
+        let styles = [Style::default(), Style::default().set_justify_self(AlignSelf::End)];
+        let rects = layout(&template, &container, 100.0, &styles, Size::new(20.0, 10.0));
+        assert_eq!((rects[0].origin.x, rects[0].size.width), (40.0, 20.0));
+        assert_eq!(rects[1].origin.x, 80.0);
+    }
+}
@@ -1,12 +1,26 @@
+mod constraints;
+mod flex;
+mod grid;
+mod position;
 mod responsive;
+mod sizing;
 
+pub use constraints::*;
+pub use flex::*;
+pub use grid::*;
+pub use position::*;
 pub use responsive::*;
-use crate::geometry::Size;
+pub use sizing::*;
+use crate::geometry::{Rect, Size};
+use crate::style::{EdgeInsets, LayoutDirection, Style};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Layout {
     Row,
     Column,
+    RowReverse,
+    ColumnReverse,
+    Grid(GridTemplate),
 }
 
 impl Default for Layout {
@@ -20,14 +34,50 @@ pub const DEFAULT_SPACING: f32 = 8.0;
 impl Layout {
     pub fn calculate_size(&self, available_space: Size, spacing: f32) -> Size {
         match self {
-            Layout::Row => Size {
+            Layout::Row | Layout::RowReverse => Size {
                 width: available_space.width - spacing,
                 height: available_space.height,
             },
-            Layout::Column => Size {
+            Layout::Column | Layout::ColumnReverse => Size {
                 width: available_space.width,
                 height: available_space.height - spacing,
             },
+            Layout::Grid(_) => available_space,
         }
     }
+
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, Layout::Row | Layout::RowReverse)
+    }
+
+    pub fn is_reverse(&self) -> bool {
+        matches!(self, Layout::RowReverse | Layout::ColumnReverse)
+    }
+}
+
+// A child as seen by a layout algorithm: its style and measured size.
+pub struct LayoutItem<'a> {
+    pub style: &'a Style,
+    pub size: Size,
+}
+
+// The space between a container's bounds and its content box. Padding
+// sides follow the layout direction.
+pub(crate) fn content_insets(style: &Style) -> EdgeInsets {
+    let padding = match style.direction {
+        LayoutDirection::Ltr => style.padding,
+        LayoutDirection::Rtl => style.padding.mirrored(),
+    };
+    style.border.widths() + padding
+}
+
+// The area children are laid out in: the bounds minus border and padding.
+pub(crate) fn content_box(style: &Style, bounds: &Rect) -> Rect {
+    let insets = content_insets(style);
+    Rect::new(
+        bounds.origin.x + insets.left,
+        bounds.origin.y + insets.top,
+        (bounds.size.width - insets.horizontal()).max(0.0),
+        (bounds.size.height - insets.vertical()).max(0.0),
+    )
 }
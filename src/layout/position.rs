@@ -0,0 +1,157 @@
+use crate::geometry::{Rect, Size};
+use crate::style::{Dimension, LayoutDirection, Position, Style};
+use super::Constraints;
+
+pub fn is_in_flow(style: &Style) -> bool {
+    style.position != Position::Absolute
+}
+
+// Constraints for measuring an absolutely positioned child inside `container`.
+// Setting both opposing offsets fixes the size along that axis; margins
+// come out of the space either way.
+pub fn absolute_constraints(style: &Style, container: &Rect) -> Constraints {
+    let margin = style.margin;
+    let (min_width, max_width) = axis_extent(style.left, style.right, margin.horizontal(), container.size.width);
+    let (min_height, max_height) = axis_extent(style.top, style.bottom, margin.vertical(), container.size.height);
+    Constraints::new(Size::new(min_width, min_height), Size::new(max_width, max_height)).with_percent_basis(container.size)
+}
+
+// Places an absolutely positioned child of measured `size` and its margins
+// against the edges of `container`; with no offsets on an axis it sits at
+// the leading edge. In a right-to-left container `left` and `right` swap,
+// like padding, so `left` is the start side and wins over `right`.
+pub fn absolute_rect(style: &Style, size: Size, container: &Rect, direction: LayoutDirection) -> Rect {
+    let width = container.size.width;
+    let height = container.size.height;
+    let margin = style.margin;
+    let (left, right) = (style.left.resolve(width), style.right.resolve(width));
+    let x = match direction {
+        LayoutDirection::Ltr => inset_offset(left, right, margin.left, margin.right, size.width, width),
+        LayoutDirection::Rtl => width - inset_offset(left, right, margin.right, margin.left, size.width, width) - size.width,
+    };
+    let y = inset_offset(style.top.resolve(height), style.bottom.resolve(height), margin.top, margin.bottom, size.height, height);
+    Rect::new(container.origin.x + x, container.origin.y + y, size.width, size.height)
+}
+
+// Shifts a relatively positioned child from its laid-out `rect`. `left` wins
+// over `right` and `top` over `bottom`, with `left` and `right` swapped in a
+// right-to-left container; percentages resolve against `container`.
+pub fn relative_rect(style: &Style, rect: Rect, container: &Rect, direction: LayoutDirection) -> Rect {
+    if style.position != Position::Relative {
+        return rect;
+    }
+    let dx = match (style.left.resolve(container.size.width), style.right.resolve(container.size.width)) {
+        (Some(start), _) => start,
+        (None, Some(end)) => -end,
+        (None, None) => 0.0,
+    };
+    let dx = match direction {
+        LayoutDirection::Ltr => dx,
+        LayoutDirection::Rtl => -dx,
+    };
+    let dy = match (style.top.resolve(container.size.height), style.bottom.resolve(container.size.height)) {
+        (Some(top), _) => top,
+        (None, Some(bottom)) => -bottom,
+        (None, None) => 0.0,
+    };
+    Rect::new(rect.origin.x + dx, rect.origin.y + dy, rect.size.width, rect.size.height)
+}
+
+// Indices of `styles` in paint order, back to front: by `z_index`, then
+// positioned children above static ones, then document order.
+pub fn stacking_order<'a, I>(styles: I) -> Vec<usize>
+where
+    I: IntoIterator<Item = &'a Style>,
+{
+    let mut order: Vec<(usize, i32, bool)> = styles
+        .into_iter()
+        .enumerate()
+        .map(|(index, style)| (index, style.z_index, style.position != Position::Static))
+        .collect();
+    order.sort_by_key(|&(_, z_index, positioned)| (z_index, positioned));
+    order.into_iter().map(|(index, _, _)| index).collect()
+}
+
+fn axis_extent(start: Dimension, end: Dimension, margins: f32, available: f32) -> (f32, f32) {
+    let space = available - margins;
+    match (start.resolve(available), end.resolve(available)) {
+        (Some(start), Some(end)) => {
+            let extent = (space - start - end).max(0.0);
+            (extent, extent)
+        }
+        (Some(offset), None) | (None, Some(offset)) => (0.0, (space - offset).max(0.0)),
+        (None, None) => (0.0, space.max(0.0)),
+    }
+}
+
+// Where a box of `extent` starts along an axis of `available` length, from
+// its insets and margins on the start and end sides.
+fn inset_offset(start: Option<f32>, end: Option<f32>, margin_start: f32, margin_end: f32, extent: f32, available: f32) -> f32 {
+    match (start, end) {
+        (Some(start), _) => start + margin_start,
+        (None, Some(end)) => available - end - margin_end - extent,
+        (None, None) => margin_start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point;
+    use crate::style::EdgeInsets;
+
+    const CONTAINER: Rect = Rect { origin: Point { x: 10.0, y: 0.0 }, size: Size { width: 200.0, height: 100.0 } };
+    const SIZE: Size = Size { width: 50.0, height: 20.0 };
+
+    fn absolute() -> Style {
+        Style::default().set_position(Position::Absolute)
+    }
+
+    fn x(style: &Style, direction: LayoutDirection) -> f32 {
+        absolute_rect(style, SIZE, &CONTAINER, direction).origin.x
+    }
+
+    #[test]
+    fn left_and_right_swap_in_a_right_to_left_container() {
+        let start = absolute().set_left(Dimension::Points(10.0));
+        assert_eq!(x(&start, LayoutDirection::Ltr), 20.0);
+        assert_eq!(x(&start, LayoutDirection::Rtl), 150.0);
+
+        let end = absolute().set_right(Dimension::Points(10.0));
+        assert_eq!(x(&end, LayoutDirection::Ltr), 150.0);
+        assert_eq!(x(&end, LayoutDirection::Rtl), 20.0);
+
+        // The start side wins when both are set, and without either the
+        // child sits at the leading edge.
+        let both = start.clone().set_right(Dimension::Points(30.0));
+        assert_eq!(x(&both, LayoutDirection::Ltr), 20.0);
+        assert_eq!(x(&both, LayoutDirection::Rtl), 150.0);
+        assert_eq!(x(&absolute(), LayoutDirection::Ltr), 10.0);
+        assert_eq!(x(&absolute(), LayoutDirection::Rtl), 160.0);
+
+        let relative = Style::default().set_position(Position::Relative).set_left(Dimension::Points(10.0));
+        let rect = Rect::new(10.0, 0.0, 50.0, 20.0);
+        assert_eq!(relative_rect(&relative, rect.clone(), &CONTAINER, LayoutDirection::Ltr).origin.x, 20.0);
+        assert_eq!(relative_rect(&relative, rect, &CONTAINER, LayoutDirection::Rtl).origin.x, 0.0);
+    }
+
+    #[test]
+    fn margins_are_kept_clear_of_the_container_edges() {
+        let style = absolute().set_margin(EdgeInsets::new(4.0, 6.0, 8.0, 2.0));
+        let top_left = style.clone().set_left(Dimension::Points(10.0)).set_top(Dimension::Points(5.0));
+        assert_eq!(absolute_rect(&top_left, SIZE, &CONTAINER, LayoutDirection::Ltr), Rect::new(22.0, 9.0, 50.0, 20.0));
+        // In RTL `left` measures from the right edge, past the right margin.
+        assert_eq!(x(&top_left, LayoutDirection::Rtl), 144.0);
+
+        let bottom_right = style.clone().set_right(Dimension::Points(10.0)).set_bottom(Dimension::Points(5.0));
+        assert_eq!(absolute_rect(&bottom_right, SIZE, &CONTAINER, LayoutDirection::Ltr), Rect::new(144.0, 67.0, 50.0, 20.0));
+        assert_eq!(x(&style, LayoutDirection::Ltr), 12.0);
+
+        // Stretching between both offsets leaves room for the margins.
+        let stretched = style.set_left(Dimension::Points(10.0)).set_right(Dimension::Points(30.0));
+        let constraints = absolute_constraints(&stretched, &CONTAINER);
+        assert_eq!(constraints.min.width, 152.0);
+        assert_eq!(constraints.max.width, 152.0);
+        assert_eq!(constraints.max.height, 88.0);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::geometry::Size;
+use crate::style::{Dimension, Style};
+use super::Constraints;
+
+// The [min, max] range a style allows along one axis. Percentages resolve
+// against `reference`; an unbounded reference leaves them unresolved.
+pub fn size_range(min: Dimension, max: Dimension, reference: f32) -> (f32, f32) {
+    let min = resolve_definite(min, reference).unwrap_or(0.0).max(0.0);
+    let max = resolve_definite(max, reference).unwrap_or(f32::INFINITY).max(min);
+    (min, max)
+}
+
+// Narrows the constraints a parent passes down by the style's width, height
+// and min/max properties, with percentages resolved against the constraints'
+// percent basis. The parent's constraints still win on conflict.
+pub fn style_constraints(style: &Style, constraints: Constraints) -> Constraints {
+    let basis = constraints.percent_basis;
+    let (min_width, max_width) = axis_constraints(
+        style.width,
+        size_range(style.min_width, style.max_width, basis.width),
+        (constraints.min.width, constraints.max.width),
+        basis.width,
+    );
+    let (min_height, max_height) = axis_constraints(
+        style.height,
+        size_range(style.min_height, style.max_height, basis.height),
+        (constraints.min.height, constraints.max.height),
+        basis.height,
+    );
+    let mut constraints = Constraints::new(Size::new(min_width, min_height), Size::new(max_width, max_height))
+        .with_percent_basis(basis);
+
+    // One fixed axis fixes the other through the aspect ratio.
+    if let Some(ratio) = aspect_ratio(style) {
+        let width_fixed = min_width == max_width;
+        let height_fixed = min_height == max_height;
+        if width_fixed && !height_fixed {
+            let height = (min_width / ratio).clamp(min_height, max_height);
+            constraints = Constraints::new(Size::new(min_width, height), Size::new(max_width, height))
+                .with_percent_basis(basis);
+        } else if height_fixed && !width_fixed {
+            let width = (min_height * ratio).clamp(min_width, max_width);
+            constraints = Constraints::new(Size::new(width, min_height), Size::new(width, max_height))
+                .with_percent_basis(basis);
+        }
+    }
+    constraints
+}
+
+// Measures a component whose natural size is given by `content`, applying
+// its sizing properties. `content` receives the narrowed constraints and may
+// return a size outside them; the result is clamped.
+pub fn measure_styled<F>(style: &Style, constraints: Constraints, content: F) -> Size
+where
+    F: FnOnce(Constraints) -> Size,
+{
+    let constraints = style_constraints(style, constraints);
+    let size = constraints.constrain(content(constraints));
+    match aspect_ratio(style) {
+        Some(ratio) if constraints.min.height < constraints.max.height => {
+            constraints.constrain(Size::new(size.width, size.width / ratio))
+        }
+        Some(ratio) if constraints.min.width < constraints.max.width => {
+            constraints.constrain(Size::new(size.height * ratio, size.height))
+        }
+        _ => size,
+    }
+}
+
+fn aspect_ratio(style: &Style) -> Option<f32> {
+    style.aspect_ratio.filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+}
+
+fn resolve_definite(dimension: Dimension, reference: f32) -> Option<f32> {
+    dimension.resolve(reference).filter(|value| value.is_finite())
+}
+
+fn axis_constraints(
+    size: Dimension,
+    (min, max): (f32, f32),
+    (parent_min, parent_max): (f32, f32),
+    basis: f32,
+) -> (f32, f32) {
+    match resolve_definite(size, basis) {
+        Some(size) => {
+            let size = size.clamp(min, max).min(parent_max).max(parent_min);
+            (size, size)
+        }
+        None => {
+            let lower = parent_min.max(min).min(parent_max);
+            (lower, parent_max.min(max).max(lower))
+        }
+    }
+}
@@ -5,24 +5,32 @@ mod theme;
 mod platform;
 mod style;
 mod event;
+mod snapshot;
+mod text;
+pub mod font;
+pub mod texture;
+pub mod svg;
 pub mod dev_server;  // Make sure this is declared as a module
 pub mod window;      // Add window module
+pub mod testing;
 
 // Define geometry module inline to resolve ambiguity
 pub mod geometry {
-    #[derive(Debug, Clone, Copy)]
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     pub struct Size {
         pub width: f32,
         pub height: f32,
     }
 
-    #[derive(Debug, Clone, Copy)]
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
     pub struct Point {
         pub x: f32,
         pub y: f32,
     }
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
     pub struct Rect {
         pub origin: Point,
         pub size: Size,
@@ -47,6 +55,27 @@ pub mod geometry {
                 size: Size::new(width, height),
             }
         }
+
+        pub fn contains(&self, point: Point) -> bool {
+            point.x >= self.origin.x
+                && point.y >= self.origin.y
+                && point.x < self.origin.x + self.size.width
+                && point.y < self.origin.y + self.size.height
+        }
+
+        pub fn intersects(&self, other: &Rect) -> bool {
+            let overlap = self.intersection(other);
+            overlap.size.width > 0.0 && overlap.size.height > 0.0
+        }
+
+        // The overlapping area, empty when the rects do not overlap.
+        pub fn intersection(&self, other: &Rect) -> Rect {
+            let left = self.origin.x.max(other.origin.x);
+            let top = self.origin.y.max(other.origin.y);
+            let right = (self.origin.x + self.size.width).min(other.origin.x + other.size.width);
+            let bottom = (self.origin.y + self.size.height).min(other.origin.y + other.size.height);
+            Rect::new(left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+        }
     }
 
     impl Default for Size {
@@ -71,18 +100,38 @@ pub mod geometry {
     }
 }
 
-pub use components::{Button, Text, View, Component};  // Now Component is available at crate root
+pub use components::{
+    Button, Input, RichText, Span, Text, TextArea, View, Stack, Direction, Component,
+    ScrollView, ScrollAxis, ScrollbarVisibility, List, Image, ContentFit, Canvas, DrawContext, Svg,
+};  // Now Component is available at crate root
+pub use text::{Document, TextPosition};
 pub use geometry::{Size, Point, Rect};
-pub use event::{Event, KeyCode};
+pub use event::{
+    Event, KeyCode, KeyEvent, Modifiers, EventContext, EventPhase, FocusManager,
+    component_at_path, dispatch, dispatch_to, hit_test,
+};
 pub use layout::*;
-pub use style::{Style, Color, TextAlign};
+pub use style::{
+    Style, SpanStyle, Color, LayoutDirection, TextAlign, VerticalAlign, TextWrap, TextOverflow,
+    FontWeight, FontStyle, Dimension, EdgeInsets, Border, BorderSide, BorderStyle, Gradient, BoxShadow,
+    FlexWrap, JustifyContent, AlignItems, AlignSelf, GridPlacement, Position,
+};
 pub use theme::*;
 pub use dev_server::{DevServer, BuildStatus, Platform};
 pub use window::Window;  // Export Window type directly
-pub use renderer::Renderer;
+pub use renderer::{
+    Renderer, SoftwareRenderer, Framebuffer,
+    RecordingRenderer, DisplayItem, GroupRecord, ImageRecord, TextRecord, PathRecord, PathPaint,
+    Path, PathCommand, FillRule, LineCap, LineJoin, StrokeStyle, Brush, GradientStop, GradientSpread,
+};
+pub use texture::{ImageSource, Texture};
+pub use svg::SvgDocument;
+pub use snapshot::Snapshot;
 
 pub trait Application {
     fn init(&mut self);
+    // Called with each input event the platform delivers, before `update`.
+    fn handle_event(&mut self, _event: &Event) {}
     fn update(&mut self);
     fn render(&self, renderer: &mut dyn Renderer);
 }
@@ -95,13 +144,13 @@ pub struct RustUI {
 impl RustUI {
     pub fn new() -> Self {
         let window = Window::new();
-        let renderer = Box::new(renderer::DefaultRenderer::new(&window));
+        let renderer = Box::new(SoftwareRenderer::from_window(&window));
         Self { window, renderer }
     }
 
     pub fn new_with_platform(platform: &str) -> Self {
         let window = Window::new_with_platform(platform);
-        let renderer = Box::new(renderer::DefaultRenderer::new(&window));
+        let renderer = Box::new(SoftwareRenderer::from_window(&window));
         Self { window, renderer }
     }
 
@@ -1,6 +1,16 @@
+use std::time::Duration;
+use crossterm::event::{
+    self as term_event, Event as TermEvent, KeyCode as TermKeyCode, KeyEvent as TermKeyEvent, KeyEventKind,
+    KeyModifiers, ModifierKeyCode,
+};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use crate::event::{Event, KeyCode, KeyEvent, Modifiers};
 use crate::{Application, RustUI};
 use super::Platform;
 
+// How long to wait for input before running the next frame.
+const FRAME: Duration = Duration::from_millis(16);
+
 pub struct DesktopPlatform {
     rust_ui: RustUI,
 }
@@ -10,7 +20,7 @@ impl Platform for DesktopPlatform {
         Self { rust_ui: rust_native }
     }
 
-    fn run<F>(self, app: F)
+    fn run<F>(mut self, app: F)
     where
         F: FnOnce() -> Box<dyn Application>
     {
@@ -19,6 +29,175 @@ impl Platform for DesktopPlatform {
         
         let window = self.rust_ui.window();
         println!("Running desktop app with window size: {:?}", window.dimensions());
-        // Implement event loop
+
+        // Keys are read from the terminal, which only reports them one at a
+        // time in raw mode; without a terminal there is no input to wait for.
+        if enable_raw_mode().is_err() {
+            return;
+        }
+        'running: loop {
+            for event in poll(FRAME) {
+                // Raw mode swallows the interrupt, so Ctrl+C quits here.
+                if let Event::KeyDown(KeyEvent { code: KeyCode::C, modifiers, .. }) = &event {
+                    if modifiers.control {
+                        break 'running;
+                    }
+                }
+                app.handle_event(&event);
+            }
+            app.update();
+            app.render(self.rust_ui.renderer());
+        }
+        let _ = disable_raw_mode();
+    }
+}
+
+// Waits up to `timeout` for terminal input and translates what arrived.
+fn poll(timeout: Duration) -> Vec<Event> {
+    match term_event::poll(timeout) {
+        Ok(true) => match term_event::read() {
+            Ok(TermEvent::Key(key)) => from_term_key_event(key),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+// Translates a terminal key event. A printable character also produces an
+// `Event::TextInput` after its key-down, unless Control, Alt or Meta is held.
+fn from_term_key_event(key: TermKeyEvent) -> Vec<Event> {
+    let mut modifiers = Modifiers {
+        shift: key.modifiers.contains(KeyModifiers::SHIFT),
+        control: key.modifiers.contains(KeyModifiers::CONTROL),
+        alt: key.modifiers.contains(KeyModifiers::ALT),
+        meta: key.modifiers.intersects(KeyModifiers::SUPER | KeyModifiers::META),
+    };
+    let mut text = None;
+    let code = match key.code {
+        TermKeyCode::Char(c) => {
+            text = Some(c);
+            KeyCode::from_char(c)
+        }
+        TermKeyCode::BackTab => {
+            modifiers.shift = true;
+            KeyCode::Tab
+        }
+        TermKeyCode::F(number) => KeyCode::function(number),
+        TermKeyCode::Up => KeyCode::Up,
+        TermKeyCode::Down => KeyCode::Down,
+        TermKeyCode::Left => KeyCode::Left,
+        TermKeyCode::Right => KeyCode::Right,
+        TermKeyCode::Home => KeyCode::Home,
+        TermKeyCode::End => KeyCode::End,
+        TermKeyCode::PageUp => KeyCode::PageUp,
+        TermKeyCode::PageDown => KeyCode::PageDown,
+        TermKeyCode::Insert => KeyCode::Insert,
+        TermKeyCode::Delete => KeyCode::Delete,
+        TermKeyCode::Backspace => KeyCode::Backspace,
+        TermKeyCode::Tab => KeyCode::Tab,
+        TermKeyCode::Enter => KeyCode::Enter,
+        TermKeyCode::Esc => KeyCode::Escape,
+        TermKeyCode::CapsLock => KeyCode::CapsLock,
+        TermKeyCode::NumLock => KeyCode::NumLock,
+        TermKeyCode::ScrollLock => KeyCode::ScrollLock,
+        TermKeyCode::PrintScreen => KeyCode::PrintScreen,
+        TermKeyCode::Pause => KeyCode::Pause,
+        TermKeyCode::Menu => KeyCode::Menu,
+        TermKeyCode::Modifier(modifier) => match modifier {
+            ModifierKeyCode::LeftShift | ModifierKeyCode::RightShift => KeyCode::Shift,
+            ModifierKeyCode::LeftControl | ModifierKeyCode::RightControl => KeyCode::Control,
+            ModifierKeyCode::LeftAlt | ModifierKeyCode::RightAlt => KeyCode::Alt,
+            ModifierKeyCode::LeftSuper
+            | ModifierKeyCode::RightSuper
+            | ModifierKeyCode::LeftMeta
+            | ModifierKeyCode::RightMeta => KeyCode::Meta,
+            _ => KeyCode::Unknown,
+        },
+        _ => KeyCode::Unknown,
+    };
+
+    let event = KeyEvent { code, modifiers, repeat: key.kind == KeyEventKind::Repeat };
+    if key.kind == KeyEventKind::Release {
+        return vec![Event::KeyUp(event)];
+    }
+    let mut events = vec![Event::KeyDown(event)];
+    if let Some(c) = text.filter(|_| !(modifiers.control || modifiers.alt || modifiers.meta)) {
+        events.push(Event::TextInput(c.to_string()));
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(code: TermKeyCode, modifiers: KeyModifiers) -> Vec<Event> {
+        from_term_key_event(TermKeyEvent::new(code, modifiers))
+    }
+
+    // The key-down a single translated event carries, if that is all it is.
+    fn key_down(events: &[Event]) -> Option<KeyEvent> {
+        match events {
+            [Event::KeyDown(key)] => Some(*key),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn printable_keys_also_type_text() {
+        let events = translate(TermKeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert!(
+            matches!(events.as_slice(), [Event::KeyDown(key), Event::TextInput(text)]
+                if *key == KeyEvent::new(KeyCode::A, Modifiers::SHIFT) && text == "A"),
+            "{:?}",
+            events,
+        );
+        let events = translate(TermKeyCode::Char(' '), KeyModifiers::NONE);
+        assert!(
+            matches!(events.as_slice(), [Event::KeyDown(key), Event::TextInput(text)]
+                if key.code == KeyCode::Space && text == " "),
+            "{:?}",
+            events,
+        );
+    }
+
+    #[test]
+    fn shortcuts_do_not_type_text() {
+        let copy = translate(TermKeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(key_down(&copy), Some(KeyEvent::new(KeyCode::C, Modifiers::CONTROL)));
+        let cut = translate(TermKeyCode::Char('x'), KeyModifiers::ALT);
+        assert_eq!(key_down(&cut), Some(KeyEvent::new(KeyCode::X, Modifiers::ALT)));
+        let paste = translate(TermKeyCode::Char('v'), KeyModifiers::SUPER);
+        assert_eq!(key_down(&paste), Some(KeyEvent::new(KeyCode::V, Modifiers::META)));
+    }
+
+    #[test]
+    fn named_keys_map_to_key_codes() {
+        let cases = [
+            (TermKeyCode::BackTab, KeyModifiers::NONE, KeyEvent::new(KeyCode::Tab, Modifiers::SHIFT)),
+            (TermKeyCode::F(12), KeyModifiers::NONE, KeyCode::F12.into()),
+            (TermKeyCode::Esc, KeyModifiers::NONE, KeyCode::Escape.into()),
+            (TermKeyCode::Enter, KeyModifiers::NONE, KeyCode::Enter.into()),
+            (
+                TermKeyCode::Modifier(ModifierKeyCode::RightControl),
+                KeyModifiers::CONTROL,
+                KeyEvent::new(KeyCode::Control, Modifiers::CONTROL),
+            ),
+            (TermKeyCode::Null, KeyModifiers::NONE, KeyCode::Unknown.into()),
+        ];
+        for (code, modifiers, expected) in cases {
+            assert_eq!(key_down(&translate(code, modifiers)), Some(expected), "{:?}", code);
+        }
+    }
+
+    #[test]
+    fn repeats_and_releases_keep_their_kind() {
+        let repeat = TermKeyEvent::new_with_kind(TermKeyCode::Left, KeyModifiers::NONE, KeyEventKind::Repeat);
+        let held = KeyEvent { code: KeyCode::Left, modifiers: Modifiers::NONE, repeat: true };
+        assert_eq!(key_down(&from_term_key_event(repeat)), Some(held));
+
+        let release = TermKeyEvent::new_with_kind(TermKeyCode::Char('a'), KeyModifiers::NONE, KeyEventKind::Release);
+        let events = from_term_key_event(release);
+        assert!(matches!(events.as_slice(), [Event::KeyUp(key)] if key.code == KeyCode::A), "{:?}", events);
     }
 }
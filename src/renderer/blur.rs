@@ -0,0 +1,53 @@
+use tiny_skia::Mask;
+
+// Approximates a gaussian blur with standard deviation `sigma` (in pixels)
+// by three box blurs, applied to the pixels of `mask` inside the half-open
+// region `x0..x1` by `y0..y1`. Pixels outside it are read as transparent.
+pub(crate) fn blur_mask(mask: &mut Mask, region: (u32, u32, u32, u32), sigma: f32) {
+    let (x0, y0, x1, y1) = region;
+    if sigma <= 0.0 || x0 >= x1 || y0 >= y1 {
+        return;
+    }
+    // Three passes of width `w` add up to a variance of (w² - 1) / 4.
+    let radius = (((4.0 * sigma * sigma + 1.0).sqrt() - 1.0) / 2.0).round().max(1.0) as usize;
+    let stride = mask.width() as usize;
+    let (width, height) = ((x1 - x0) as usize, (y1 - y0) as usize);
+    let data = mask.data_mut();
+    let mut line = Vec::new();
+    let mut scratch = Vec::new();
+
+    for row in 0..height {
+        let start = (y0 as usize + row) * stride + x0 as usize;
+        line.clear();
+        line.extend_from_slice(&data[start..start + width]);
+        for _ in 0..3 {
+            box_blur(&mut line, &mut scratch, radius);
+        }
+        data[start..start + width].copy_from_slice(&line);
+    }
+    for column in 0..width {
+        line.clear();
+        line.extend((0..height).map(|row| data[(y0 as usize + row) * stride + x0 as usize + column]));
+        for _ in 0..3 {
+            box_blur(&mut line, &mut scratch, radius);
+        }
+        for (row, value) in line.iter().enumerate() {
+            data[(y0 as usize + row) * stride + x0 as usize + column] = *value;
+        }
+    }
+}
+
+// Replaces each value with the mean of the `2 * radius + 1` values around it.
+fn box_blur(line: &mut [u8], scratch: &mut Vec<u8>, radius: usize) {
+    scratch.clear();
+    scratch.extend_from_slice(line);
+    let len = line.len();
+    let window = (2 * radius + 1) as u32;
+    let at = |index: isize| if index >= 0 && (index as usize) < len { scratch[index as usize] as u32 } else { 0 };
+    let mut sum: u32 = (-(radius as isize)..=radius as isize).map(at).sum();
+    for (index, value) in line.iter_mut().enumerate() {
+        *value = ((sum + window / 2) / window) as u8;
+        let index = index as isize;
+        sum = sum + at(index + radius as isize + 1) - at(index - radius as isize);
+    }
+}
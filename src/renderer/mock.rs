@@ -1,4 +1,5 @@
 use crate::style::{Style, Color};
+use crate::geometry::Rect;
 
 pub struct MockRenderer {
     pub(crate) last_color: Option<Color>,
@@ -23,7 +24,7 @@ impl super::Renderer for MockRenderer {
         self.last_color = Some(color);
     }
 
-    fn begin_group(&mut self, style: &Style) {
+    fn begin_group(&mut self, style: &Style, _bounds: &Rect) {
         self.last_color = Some(style.background);
     }
 
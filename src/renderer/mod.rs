@@ -1,15 +1,42 @@
 use crate::style::{Style, Color};
+use crate::geometry::Rect;
+use crate::layout::content_box;
+use crate::texture::Texture;
 
-mod default;
+mod blur;
+mod brush;
+mod framebuffer;
+mod path;
+mod recording;
+mod software;
 #[cfg(test)]
 mod mock;
 
-pub use default::DefaultRenderer;
+pub use brush::{Brush, GradientSpread, GradientStop};
+pub use framebuffer::Framebuffer;
+pub use path::{FillRule, LineCap, LineJoin, Path, PathCommand, StrokeStyle};
+pub use recording::{DisplayItem, GroupRecord, ImageRecord, PathPaint, PathRecord, RecordingRenderer, TextRecord};
+pub use software::SoftwareRenderer;
 
 pub trait Renderer {
     fn clear(&mut self, color: Color);
-    fn begin_group(&mut self, style: &Style);
+    fn begin_group(&mut self, style: &Style, bounds: &Rect);
     fn end_group(&mut self);
     fn draw_text(&mut self, text: &str, style: &Style);
+    // Draws `texture` stretched over `rect`, clipped to the innermost group's
+    // content box with its rounded corners. A `tint` recolors every pixel,
+    // keeping the image's alpha.
+    //
+    // Renderers that cannot draw images, paths or clips may leave these
+    // methods as the default no-ops.
+    fn draw_image(&mut self, _texture: &Texture, _rect: &Rect, _tint: Option<Color>) {}
+    // Paths are given in the current translated space and, like text, are
+    // clipped only by pushed clips rather than by their group.
+    fn fill_path(&mut self, _path: &Path, _brush: &Brush, _rule: FillRule) {}
+    fn stroke_path(&mut self, _path: &Path, _brush: &Brush, _stroke: &StrokeStyle) {}
     fn translate(&mut self, x: f32, y: f32);
+    // Restricts drawing to `rect`, given in the current translated space and
+    // intersected with any clip already pushed, until the matching `pop_clip`.
+    fn push_clip(&mut self, _rect: &Rect) {}
+    fn pop_clip(&mut self) {}
 }
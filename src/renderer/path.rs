@@ -0,0 +1,410 @@
+use std::f32::consts::FRAC_PI_2;
+use serde::{Deserialize, Serialize};
+use crate::geometry::{Point, Rect};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PathCommand {
+    MoveTo { to: Point },
+    LineTo { to: Point },
+    QuadTo { control: Point, to: Point },
+    CubicTo { control1: Point, control2: Point, to: Point },
+    Close,
+}
+
+// A vector outline made of straight and curved segments. Arcs and shapes are
+// stored as the cubic curves approximating them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SerializedPath")]
+pub struct Path {
+    commands: Vec<PathCommand>,
+    // Where the current subpath started, for `close`.
+    #[serde(skip)]
+    start: Option<Point>,
+    #[serde(skip)]
+    current: Option<Point>,
+}
+
+// Only the commands are serialized; the subpath start and current point are
+// recovered by replaying them.
+#[derive(Deserialize)]
+struct SerializedPath {
+    commands: Vec<PathCommand>,
+}
+
+impl From<SerializedPath> for Path {
+    fn from(serialized: SerializedPath) -> Self {
+        let mut path = Path::new();
+        for command in serialized.commands {
+            match command {
+                PathCommand::MoveTo { to } => {
+                    path.start = Some(to);
+                    path.current = Some(to);
+                }
+                PathCommand::LineTo { to } | PathCommand::QuadTo { to, .. } | PathCommand::CubicTo { to, .. } => {
+                    path.current = Some(to);
+                }
+                PathCommand::Close => path.current = path.start,
+            }
+            path.commands.push(command);
+        }
+        path
+    }
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    // Starts a new subpath at (`x`, `y`).
+    pub fn move_to(&mut self, x: f32, y: f32) -> &mut Self {
+        let to = Point::new(x, y);
+        self.commands.push(PathCommand::MoveTo { to });
+        self.start = Some(to);
+        self.current = Some(to);
+        self
+    }
+
+    // Segments without a current point start a subpath at their first point.
+    pub fn line_to(&mut self, x: f32, y: f32) -> &mut Self {
+        if self.current.is_none() {
+            return self.move_to(x, y);
+        }
+        let to = Point::new(x, y);
+        self.commands.push(PathCommand::LineTo { to });
+        self.current = Some(to);
+        self
+    }
+
+    pub fn quad_to(&mut self, cx: f32, cy: f32, x: f32, y: f32) -> &mut Self {
+        if self.current.is_none() {
+            self.move_to(cx, cy);
+        }
+        let to = Point::new(x, y);
+        self.commands.push(PathCommand::QuadTo { control: Point::new(cx, cy), to });
+        self.current = Some(to);
+        self
+    }
+
+    pub fn cubic_to(&mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) -> &mut Self {
+        if self.current.is_none() {
+            self.move_to(c1x, c1y);
+        }
+        let to = Point::new(x, y);
+        self.commands.push(PathCommand::CubicTo {
+            control1: Point::new(c1x, c1y),
+            control2: Point::new(c2x, c2y),
+            to,
+        });
+        self.current = Some(to);
+        self
+    }
+
+    // An arc of the circle around (`cx`, `cy`) from `start_angle` to
+    // `end_angle`, in radians measured clockwise from the positive x axis as
+    // y grows downwards. It sweeps clockwise unless `counter_clockwise` is
+    // set, and is joined to the current point with a line.
+    pub fn arc(&mut self, cx: f32, cy: f32, radius: f32, start_angle: f32, end_angle: f32, counter_clockwise: bool) -> &mut Self {
+        let tau = std::f32::consts::TAU;
+        let mut sweep = end_angle - start_angle;
+        // Sweeps of a full turn or more draw the whole circle once.
+        if counter_clockwise {
+            sweep = if sweep <= -tau { -tau } else { -((-sweep).rem_euclid(tau)) };
+        } else {
+            sweep = if sweep >= tau { tau } else { sweep.rem_euclid(tau) };
+        }
+
+        let point = |angle: f32| (cx + radius * angle.cos(), cy + radius * angle.sin());
+        let (x, y) = point(start_angle);
+        self.line_to(x, y);
+        if sweep == 0.0 || radius <= 0.0 {
+            return self;
+        }
+
+        let segments = (sweep.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = sweep / segments as f32;
+        // Control point distance for a cubic approximating an arc of `step`.
+        let k = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+        let mut angle = start_angle;
+        for _ in 0..segments {
+            let next = angle + step;
+            let (x0, y0) = point(angle);
+            let (x1, y1) = point(next);
+            self.cubic_to(
+                x0 - k * angle.sin(),
+                y0 + k * angle.cos(),
+                x1 + k * next.sin(),
+                y1 - k * next.cos(),
+                x1,
+                y1,
+            );
+            angle = next;
+        }
+        self
+    }
+
+    // Closes the current subpath with a line back to its start.
+    pub fn close(&mut self) -> &mut Self {
+        if self.current.is_some() {
+            self.commands.push(PathCommand::Close);
+            self.current = self.start;
+        }
+        self
+    }
+
+    pub fn rect(&mut self, rect: &Rect) -> &mut Self {
+        let (x, y) = (rect.origin.x, rect.origin.y);
+        let (w, h) = (rect.size.width, rect.size.height);
+        self.move_to(x, y).line_to(x + w, y).line_to(x + w, y + h).line_to(x, y + h).close()
+    }
+
+    // `radius` is limited to half the shorter side.
+    pub fn rounded_rect(&mut self, rect: &Rect, radius: f32) -> &mut Self {
+        let (x, y) = (rect.origin.x, rect.origin.y);
+        let (w, h) = (rect.size.width, rect.size.height);
+        let r = radius.max(0.0).min(w.abs() / 2.0).min(h.abs() / 2.0);
+        if r <= 0.0 {
+            return self.rect(rect);
+        }
+        self.move_to(x + r, y)
+            .line_to(x + w - r, y)
+            .arc(x + w - r, y + r, r, -FRAC_PI_2, 0.0, false)
+            .line_to(x + w, y + h - r)
+            .arc(x + w - r, y + h - r, r, 0.0, FRAC_PI_2, false)
+            .line_to(x + r, y + h)
+            .arc(x + r, y + h - r, r, FRAC_PI_2, 2.0 * FRAC_PI_2, false)
+            .line_to(x, y + r)
+            .arc(x + r, y + r, r, 2.0 * FRAC_PI_2, 3.0 * FRAC_PI_2, false)
+            .close()
+    }
+
+    pub fn circle(&mut self, cx: f32, cy: f32, radius: f32) -> &mut Self {
+        self.move_to(cx + radius, cy);
+        self.arc(cx, cy, radius, 0.0, std::f32::consts::TAU, false).close()
+    }
+
+    // The same outline moved by (`dx`, `dy`).
+    pub fn translated(&self, dx: f32, dy: f32) -> Path {
+        self.map_points(|point| Point::new(point.x + dx, point.y + dy))
+    }
+
+    // The same outline with every point and control point passed through
+    // `f`, which should be affine for curves to keep their shape.
+    pub(crate) fn map_points<F: Fn(Point) -> Point>(&self, f: F) -> Path {
+        Path {
+            commands: self.commands.iter().map(|command| match *command {
+                PathCommand::MoveTo { to } => PathCommand::MoveTo { to: f(to) },
+                PathCommand::LineTo { to } => PathCommand::LineTo { to: f(to) },
+                PathCommand::QuadTo { control, to } => PathCommand::QuadTo { control: f(control), to: f(to) },
+                PathCommand::CubicTo { control1, control2, to } => PathCommand::CubicTo {
+                    control1: f(control1),
+                    control2: f(control2),
+                    to: f(to),
+                },
+                PathCommand::Close => PathCommand::Close,
+            }).collect(),
+            start: self.start.map(&f),
+            current: self.current.map(&f),
+        }
+    }
+
+    // The smallest rect containing every point and control point.
+    pub fn bounds(&self) -> Option<Rect> {
+        let mut points = self.commands.iter().flat_map(|command| match *command {
+            PathCommand::MoveTo { to } | PathCommand::LineTo { to } => vec![to],
+            PathCommand::QuadTo { control, to } => vec![control, to],
+            PathCommand::CubicTo { control1, control2, to } => vec![control1, control2, to],
+            PathCommand::Close => Vec::new(),
+        });
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            )
+        });
+        Some(Rect::new(min.x, min.y, max.x - min.x, max.y - min.y))
+    }
+}
+
+// How overlapping and self-intersecting parts of a path are filled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    // Miter joins longer than this many stroke widths are beveled.
+    pub miter_limit: f32,
+    // Alternating dash and gap lengths; empty for a solid line.
+    pub dash: Vec<f32>,
+    pub dash_offset: f32,
+}
+
+impl StrokeStyle {
+    pub fn new(width: f32) -> Self {
+        Self { width, ..Self::default() }
+    }
+
+    pub fn set_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn set_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn set_miter_limit(mut self, limit: f32) -> Self {
+        self.miter_limit = limit;
+        self
+    }
+
+    // An odd number of lengths is repeated to make the pattern even.
+    pub fn set_dash(mut self, dash: Vec<f32>, offset: f32) -> Self {
+        self.dash = dash;
+        self.dash_offset = offset;
+        self
+    }
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+            dash: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_rect_near(actual: Rect, expected: Rect) {
+        let near = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        assert!(
+            near(actual.origin.x, expected.origin.x)
+                && near(actual.origin.y, expected.origin.y)
+                && near(actual.size.width, expected.size.width)
+                && near(actual.size.height, expected.size.height),
+            "expected {:?}, got {:?}",
+            expected,
+            actual,
+        );
+    }
+
+    #[test]
+    fn segments_without_a_current_point_start_a_subpath() {
+        let mut path = Path::new();
+        path.line_to(1.0, 2.0).line_to(3.0, 4.0).close().line_to(5.0, 6.0);
+        assert_eq!(
+            path.commands(),
+            [
+                PathCommand::MoveTo { to: Point::new(1.0, 2.0) },
+                PathCommand::LineTo { to: Point::new(3.0, 4.0) },
+                PathCommand::Close,
+                // Closing returns to the start, so this continues from there.
+                PathCommand::LineTo { to: Point::new(5.0, 6.0) },
+            ],
+        );
+
+        let mut curve = Path::new();
+        curve.cubic_to(0.0, 0.0, 10.0, -5.0, 20.0, 10.0);
+        assert_eq!(curve.commands()[0], PathCommand::MoveTo { to: Point::new(0.0, 0.0) });
+        assert_eq!(
+            curve.commands()[1],
+            PathCommand::CubicTo { control1: Point::new(0.0, 0.0), control2: Point::new(10.0, -5.0), to: Point::new(20.0, 10.0) },
+        );
+        // Bounds include control points.
+        assert_eq!(curve.bounds(), Some(Rect::new(0.0, -5.0, 20.0, 15.0)));
+
+        let mut empty = Path::new();
+        assert_eq!(empty.close().bounds(), None);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn shapes_cover_their_bounds() {
+        let mut circle = Path::new();
+        circle.circle(50.0, 40.0, 10.0);
+        let cubics = circle.commands().iter().filter(|command| matches!(command, PathCommand::CubicTo { .. })).count();
+        assert_eq!(cubics, 4);
+        assert_eq!(circle.commands().last(), Some(&PathCommand::Close));
+        assert_rect_near(circle.bounds().unwrap(), Rect::new(40.0, 30.0, 20.0, 20.0));
+
+        let mut rounded = Path::new();
+        rounded.rounded_rect(&Rect::new(0.0, 0.0, 40.0, 20.0), 50.0);
+        assert_rect_near(rounded.bounds().unwrap(), Rect::new(0.0, 0.0, 40.0, 20.0));
+
+        let mut rect = Path::new();
+        rect.rect(&Rect::new(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(rect.translated(10.0, 20.0).bounds(), Some(Rect::new(11.0, 22.0, 3.0, 4.0)));
+    }
+
+    #[test]
+    fn deserialized_paths_continue_where_they_left_off() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).quad_to(5.0, 5.0, 10.0, 0.0);
+        let mut restored: Path = serde_json::from_str(&serde_json::to_string(&path).unwrap()).unwrap();
+        assert_eq!(restored, path);
+
+        path.line_to(10.0, 10.0).close();
+        restored.line_to(10.0, 10.0).close();
+        assert_eq!(restored, path);
+    }
+
+    #[test]
+    fn stroke_style_defaults_and_builders() {
+        let stroke = StrokeStyle::new(3.0);
+        assert_eq!(stroke, StrokeStyle { width: 3.0, ..StrokeStyle::default() });
+        assert_eq!((stroke.cap, stroke.join, stroke.miter_limit), (LineCap::Butt, LineJoin::Miter, 4.0));
+        assert!(stroke.dash.is_empty());
+
+        let stroke = stroke
+            .set_cap(LineCap::Round)
+            .set_join(LineJoin::Bevel)
+            .set_miter_limit(2.0)
+            .set_dash(vec![4.0, 2.0, 1.0], 3.0);
+        assert_eq!(stroke.cap, LineCap::Round);
+        assert_eq!(stroke.join, LineJoin::Bevel);
+        assert_eq!(stroke.miter_limit, 2.0);
+        assert_eq!((stroke.dash.as_slice(), stroke.dash_offset), ([4.0, 2.0, 1.0].as_slice(), 3.0));
+    }
+}
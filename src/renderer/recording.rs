@@ -0,0 +1,432 @@
+use serde::{Deserialize, Serialize};
+use crate::style::{Style, Color};
+use crate::geometry::{Point, Rect};
+use crate::texture::Texture;
+use super::{content_box, Brush, FillRule, Path, Renderer, StrokeStyle};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupRecord {
+    pub id: usize,
+    pub parent: Option<usize>,
+    pub bounds: Rect,
+    pub style: Style,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TextRecord {
+    pub text: String,
+    pub group: Option<usize>,
+    pub bounds: Rect,
+    pub style: Style,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImageRecord {
+    pub group: Option<usize>,
+    // Where the whole texture is drawn; parts outside the group's content box
+    // are clipped.
+    pub bounds: Rect,
+    pub width: u32,
+    pub height: u32,
+    pub tint: Option<Color>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PathPaint {
+    Fill { rule: FillRule },
+    Stroke { stroke: StrokeStyle },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PathRecord {
+    pub group: Option<usize>,
+    pub path: Path,
+    // Gradient coordinates are absolute, like the path's.
+    pub brush: Brush,
+    pub paint: PathPaint,
+}
+
+// Positions are resolved against every `translate` issued before the call,
+// so they are absolute within the render target.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DisplayItem {
+    Clear { color: Color },
+    BeginGroup(GroupRecord),
+    EndGroup { id: usize },
+    Text(TextRecord),
+    Image(ImageRecord),
+    Path(PathRecord),
+    Translate { x: f32, y: f32, offset: Point },
+    // `rect` is the absolute clip in effect, already intersected with the
+    // enclosing one.
+    PushClip { rect: Rect },
+    PopClip,
+}
+
+struct OpenGroup {
+    id: usize,
+    content: Rect,
+    translation: Point,
+}
+
+pub struct RecordingRenderer {
+    items: Vec<DisplayItem>,
+    groups: Vec<GroupRecord>,
+    open: Vec<OpenGroup>,
+    clips: Vec<Rect>,
+    transform: Point,
+}
+
+impl RecordingRenderer {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            groups: Vec::new(),
+            open: Vec::new(),
+            clips: Vec::new(),
+            transform: Point::default(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn items(&self) -> &[DisplayItem] {
+        &self.items
+    }
+
+    pub fn groups(&self) -> &[GroupRecord] {
+        &self.groups
+    }
+
+    pub fn group(&self, id: usize) -> Option<&GroupRecord> {
+        self.groups.get(id)
+    }
+
+    pub fn texts(&self) -> impl Iterator<Item = &TextRecord> {
+        self.items.iter().filter_map(|item| match item {
+            DisplayItem::Text(text) => Some(text),
+            _ => None,
+        })
+    }
+
+    pub fn images(&self) -> impl Iterator<Item = &ImageRecord> {
+        self.items.iter().filter_map(|item| match item {
+            DisplayItem::Image(image) => Some(image),
+            _ => None,
+        })
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &PathRecord> {
+        self.items.iter().filter_map(|item| match item {
+            DisplayItem::Path(path) => Some(path),
+            _ => None,
+        })
+    }
+
+    fn record_path(&mut self, path: &Path, brush: &Brush, paint: PathPaint) {
+        let (dx, dy) = (self.transform.x, self.transform.y);
+        let brush = brush.map_points(|point| Point::new(point.x + dx, point.y + dy), 1.0);
+        self.items.push(DisplayItem::Path(PathRecord {
+            group: self.open.last().map(|group| group.id),
+            path: path.translated(dx, dy),
+            brush,
+            paint,
+        }));
+    }
+
+    pub fn find_text(&self, text: &str) -> Option<&TextRecord> {
+        self.texts().find(|record| record.text == text)
+    }
+
+    // Innermost group first.
+    pub fn ancestors(&self, record: &TextRecord) -> Vec<&GroupRecord> {
+        let mut ancestors = Vec::new();
+        let mut next = record.group;
+        while let Some(id) = next {
+            let group = &self.groups[id];
+            ancestors.push(group);
+            next = group.parent;
+        }
+        ancestors
+    }
+
+    pub fn has_text_in_group<F>(&self, text: &str, predicate: F) -> bool
+    where
+        F: Fn(&GroupRecord) -> bool,
+    {
+        self.texts()
+            .filter(|record| record.text == text)
+            .any(|record| self.ancestors(record).into_iter().any(&predicate))
+    }
+
+    // Texts whose bounds fall entirely outside the clip in effect when they
+    // were drawn are left out.
+    pub fn visible_texts(&self) -> Vec<&TextRecord> {
+        let mut clips: Vec<&Rect> = Vec::new();
+        let mut visible = Vec::new();
+        for item in &self.items {
+            match item {
+                DisplayItem::PushClip { rect } => clips.push(rect),
+                DisplayItem::PopClip => {
+                    clips.pop();
+                }
+                DisplayItem::Text(text) if clips.last().is_none_or(|clip| clip.intersects(&text.bounds)) => {
+                    visible.push(text);
+                }
+                _ => {}
+            }
+        }
+        visible
+    }
+
+    pub fn assert_text(&self, text: &str) -> &TextRecord {
+        match self.find_text(text) {
+            Some(record) => record,
+            None => panic!(
+                "expected text {:?} to be drawn; drawn texts: {:?}",
+                text,
+                self.texts().map(|record| record.text.as_str()).collect::<Vec<_>>(),
+            ),
+        }
+    }
+
+    pub fn assert_text_in_group_with_background(&self, text: &str, background: Color) {
+        self.assert_text(text);
+        if !self.has_text_in_group(text, |group| group.style.background == background) {
+            panic!(
+                "expected text {:?} inside a group with background {:?}; enclosing backgrounds: {:?}",
+                text,
+                background,
+                self.texts()
+                    .filter(|record| record.text == text)
+                    .map(|record| self.ancestors(record).iter().map(|g| g.style.background).collect::<Vec<_>>())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.items)
+    }
+}
+
+impl Default for RecordingRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer for RecordingRenderer {
+    fn clear(&mut self, color: Color) {
+        self.items.push(DisplayItem::Clear { color });
+    }
+
+    fn begin_group(&mut self, style: &Style, bounds: &Rect) {
+        let rect = Rect::new(
+            bounds.origin.x + self.transform.x,
+            bounds.origin.y + self.transform.y,
+            bounds.size.width,
+            bounds.size.height,
+        );
+        let record = GroupRecord {
+            id: self.groups.len(),
+            parent: self.open.last().map(|group| group.id),
+            bounds: rect.clone(),
+            style: style.clone(),
+        };
+        self.open.push(OpenGroup {
+            id: record.id,
+            content: content_box(style, &rect),
+            translation: self.transform,
+        });
+        self.groups.push(record.clone());
+        self.items.push(DisplayItem::BeginGroup(record));
+    }
+
+    fn end_group(&mut self) {
+        if let Some(group) = self.open.pop() {
+            self.items.push(DisplayItem::EndGroup { id: group.id });
+        }
+    }
+
+    fn draw_text(&mut self, text: &str, style: &Style) {
+        let bounds = match self.open.last() {
+            Some(group) => Rect::new(
+                group.content.origin.x + self.transform.x - group.translation.x,
+                group.content.origin.y + self.transform.y - group.translation.y,
+                group.content.size.width,
+                group.content.size.height,
+            ),
+            None => Rect::new(self.transform.x, self.transform.y, 0.0, 0.0),
+        };
+        self.items.push(DisplayItem::Text(TextRecord {
+            text: text.to_string(),
+            group: self.open.last().map(|group| group.id),
+            bounds,
+            style: style.clone(),
+        }));
+    }
+
+    fn draw_image(&mut self, texture: &Texture, rect: &Rect, tint: Option<Color>) {
+        self.items.push(DisplayItem::Image(ImageRecord {
+            group: self.open.last().map(|group| group.id),
+            bounds: Rect::new(
+                rect.origin.x + self.transform.x,
+                rect.origin.y + self.transform.y,
+                rect.size.width,
+                rect.size.height,
+            ),
+            width: texture.width(),
+            height: texture.height(),
+            tint,
+        }));
+    }
+
+    fn fill_path(&mut self, path: &Path, brush: &Brush, rule: FillRule) {
+        self.record_path(path, brush, PathPaint::Fill { rule });
+    }
+
+    fn stroke_path(&mut self, path: &Path, brush: &Brush, stroke: &StrokeStyle) {
+        self.record_path(path, brush, PathPaint::Stroke { stroke: stroke.clone() });
+    }
+
+    fn translate(&mut self, x: f32, y: f32) {
+        self.transform.x += x;
+        self.transform.y += y;
+        self.items.push(DisplayItem::Translate { x, y, offset: self.transform });
+    }
+
+    fn push_clip(&mut self, rect: &Rect) {
+        let rect = Rect::new(
+            rect.origin.x + self.transform.x,
+            rect.origin.y + self.transform.y,
+            rect.size.width,
+            rect.size.height,
+        );
+        let rect = match self.clips.last() {
+            Some(outer) => outer.intersection(&rect),
+            None => rect,
+        };
+        self.clips.push(rect.clone());
+        self.items.push(DisplayItem::PushClip { rect });
+    }
+
+    fn pop_clip(&mut self) {
+        if self.clips.pop().is_some() {
+            self.items.push(DisplayItem::PopClip);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A padded white panel with a title, scrolled up by 30 inside a clip
+    // that shows its top 50 pixels: the red row stays in view and the black
+    // one lands below the clip.
+    fn record_panel() -> RecordingRenderer {
+        let mut renderer = RecordingRenderer::new();
+        renderer.clear(Color::WHITE);
+        renderer.begin_group(&Style::default().set_background(Color::WHITE).set_padding(10.0), &Rect::new(0.0, 0.0, 200.0, 100.0));
+        renderer.draw_text("title", &Style::default());
+        renderer.push_clip(&Rect::new(0.0, 0.0, 200.0, 50.0));
+        renderer.translate(0.0, -30.0);
+        renderer.begin_group(&Style::default().set_background(Color::RED), &Rect::new(0.0, 40.0, 200.0, 40.0));
+        renderer.draw_text("row", &Style::default());
+        renderer.end_group();
+        renderer.begin_group(&Style::default().set_background(Color::BLACK), &Rect::new(0.0, 100.0, 200.0, 40.0));
+        renderer.draw_text("hidden", &Style::default());
+        renderer.end_group();
+        renderer.translate(0.0, 30.0);
+        renderer.pop_clip();
+        renderer.end_group();
+        renderer
+    }
+
+    #[test]
+    fn records_items_in_absolute_coordinates() {
+        let renderer = record_panel();
+        let items = renderer.items();
+        assert_eq!(items.len(), 14);
+        assert_eq!(items[0], DisplayItem::Clear { color: Color::WHITE });
+        assert_eq!(items[3], DisplayItem::PushClip { rect: Rect::new(0.0, 0.0, 200.0, 50.0) });
+        assert_eq!(items[4], DisplayItem::Translate { x: 0.0, y: -30.0, offset: Point::new(0.0, -30.0) });
+        assert_eq!(items[13], DisplayItem::EndGroup { id: 0 });
+
+        let row = renderer.group(1).unwrap();
+        assert_eq!(row.parent, Some(0));
+        assert_eq!(row.bounds, Rect::new(0.0, 10.0, 200.0, 40.0));
+        assert_eq!(renderer.groups().len(), 3);
+        // Text fills its group's content box.
+        assert_eq!(renderer.assert_text("title").bounds, Rect::new(10.0, 10.0, 180.0, 80.0));
+        assert_eq!(renderer.assert_text("row").bounds, Rect::new(0.0, 10.0, 200.0, 40.0));
+    }
+
+    #[test]
+    fn nested_clips_intersect_and_pops_without_a_clip_are_ignored() {
+        let mut renderer = RecordingRenderer::new();
+        renderer.pop_clip();
+        renderer.push_clip(&Rect::new(0.0, 0.0, 100.0, 100.0));
+        renderer.translate(50.0, 50.0);
+        renderer.push_clip(&Rect::new(0.0, 0.0, 100.0, 100.0));
+        assert_eq!(renderer.items()[0], DisplayItem::PushClip { rect: Rect::new(0.0, 0.0, 100.0, 100.0) });
+        assert_eq!(renderer.items()[2], DisplayItem::PushClip { rect: Rect::new(50.0, 50.0, 50.0, 50.0) });
+    }
+
+    #[test]
+    fn queries_walk_from_a_text_out_to_the_root() {
+        let renderer = record_panel();
+        let row = renderer.find_text("row").unwrap();
+        let ids: Vec<usize> = renderer.ancestors(row).iter().map(|group| group.id).collect();
+        assert_eq!(ids, [1, 0]);
+        assert!(renderer.find_text("missing").is_none());
+
+        assert!(renderer.has_text_in_group("row", |group| group.style.background == Color::WHITE));
+        assert!(!renderer.has_text_in_group("title", |group| group.style.background == Color::RED));
+        renderer.assert_text_in_group_with_background("row", Color::RED);
+        renderer.assert_text_in_group_with_background("row", Color::WHITE);
+    }
+
+    #[test]
+    fn visible_texts_skip_texts_outside_the_clip() {
+        let renderer = record_panel();
+        let visible: Vec<&str> = renderer.visible_texts().iter().map(|record| record.text.as_str()).collect();
+        assert_eq!(visible, ["title", "row"]);
+        assert_eq!(renderer.texts().count(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = r#"expected text "missing" to be drawn; drawn texts: ["title", "row", "hidden"]"#)]
+    fn assert_text_lists_the_drawn_texts() {
+        record_panel().assert_text("missing");
+    }
+
+    #[test]
+    #[should_panic(expected = "enclosing backgrounds: [[Color { r: 1.0, g: 1.0, b: 1.0, a: 1.0 }]]")]
+    fn assert_text_in_group_with_background_lists_enclosing_backgrounds() {
+        record_panel().assert_text_in_group_with_background("title", Color::RED);
+    }
+
+    #[test]
+    fn json_round_trips_the_display_list() {
+        let mut renderer = record_panel();
+        let texture = Texture::from_rgba(2, 1, vec![255; 8]).unwrap();
+        renderer.draw_image(&texture, &Rect::new(5.0, 5.0, 20.0, 10.0), Some(Color::RED));
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).line_to(10.0, 10.0).close();
+        renderer.fill_path(&path, &Brush::from(Color::RED), FillRule::EvenOdd);
+        renderer.stroke_path(&path, &Brush::from(Color::BLACK), &StrokeStyle::new(2.0));
+        let json = renderer.to_json().unwrap();
+        assert!(json.contains(r#""kind": "begin_group""#));
+        let items: Vec<DisplayItem> = serde_json::from_str(&json).unwrap();
+        assert_eq!(items, renderer.items());
+
+        renderer.reset();
+        assert!(renderer.items().is_empty() && renderer.groups().is_empty());
+    }
+}
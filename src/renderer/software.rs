@@ -0,0 +1,793 @@
+use std::collections::HashMap;
+use tiny_skia::{
+    FillRule, FilterQuality, LineCap, LineJoin, Mask, Paint, PathBuilder, Pattern, Pixmap, PixmapPaint,
+    SpreadMode, Stroke, StrokeDash, Transform,
+};
+use ttf_parser::GlyphId;
+use crate::{
+    Window,
+    font,
+    text,
+    style::{BorderSide, BorderStyle, Style, Color, VerticalAlign},
+    geometry::{Point, Rect, Size},
+    layout::content_insets,
+    texture::Texture,
+};
+use super::blur::blur_mask;
+use super::{content_box, Brush, Framebuffer, GradientSpread, Path, PathCommand, Renderer, StrokeStyle};
+
+struct Group {
+    content: Rect,
+    // The border radius as it applies to the content box.
+    radius: f32,
+    translation: Point,
+    // For a translucent group, what was drawn before it; the group draws
+    // into a layer of its own until `end_group` composites it.
+    below: Option<Below>,
+}
+
+struct Below {
+    pixmap: Pixmap,
+    origin: (i32, i32),
+    clips: Vec<Clip>,
+    opacity: f32,
+}
+
+struct Clip {
+    rect: Rect,
+    mask: Mask,
+}
+
+// A texture recolored by a tint, and whether it was drawn this frame.
+struct Tinted {
+    pixmap: Pixmap,
+    used: bool,
+}
+
+// Rasterizes into an in-memory RGBA pixmap; coordinates are logical points and
+// are multiplied by `scale_factor` on the way to device pixels.
+pub struct SoftwareRenderer {
+    pixmap: Pixmap,
+    size: Size,
+    scale_factor: f32,
+    transform: Point,
+    // Where `pixmap` sits in the framebuffer, in device pixels; only layers
+    // of translucent groups are offset.
+    origin: (i32, i32),
+    groups: Vec<Group>,
+    clips: Vec<Clip>,
+    // Tinted textures by texture id and tint, kept while they are drawn
+    // every frame.
+    tinted: HashMap<(u64, [u32; 4]), Tinted>,
+}
+
+impl SoftwareRenderer {
+    pub fn new(size: Size, scale_factor: f32) -> Self {
+        let scale_factor = if scale_factor > 0.0 { scale_factor } else { 1.0 };
+        Self {
+            pixmap: Self::create_pixmap(size, scale_factor),
+            size,
+            scale_factor,
+            transform: Point::default(),
+            origin: (0, 0),
+            groups: Vec::new(),
+            clips: Vec::new(),
+            tinted: HashMap::new(),
+        }
+    }
+
+    pub fn from_window(window: &Window) -> Self {
+        Self::new(window.get_platform_specific_size(), window.get_scale_factor())
+    }
+
+    fn create_pixmap(size: Size, scale_factor: f32) -> Pixmap {
+        let width = (size.width * scale_factor).ceil().max(1.0) as u32;
+        let height = (size.height * scale_factor).ceil().max(1.0) as u32;
+        Pixmap::new(width, height).expect("framebuffer dimensions are non-zero")
+    }
+
+    pub fn resize(&mut self, size: Size) {
+        self.pixmap = Self::create_pixmap(size, self.scale_factor);
+        self.size = size;
+        self.transform = Point::default();
+        self.origin = (0, 0);
+        self.groups.clear();
+        self.clips.clear();
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    pub fn get_transform(&self) -> &Point {
+        &self.transform
+    }
+
+    pub fn framebuffer(&self) -> Framebuffer {
+        let mut pixels = Vec::with_capacity(self.pixmap.data().len());
+        for pixel in self.pixmap.pixels() {
+            let color = pixel.demultiply();
+            pixels.extend_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+        }
+        Framebuffer::from_rgba(self.pixmap.width(), self.pixmap.height(), pixels)
+            .expect("pixmap data matches its dimensions")
+    }
+
+    // Maps logical points to pixels of the pixmap being drawn into.
+    fn device_transform(&self) -> Transform {
+        Transform::from_scale(self.scale_factor, self.scale_factor)
+            .post_translate(-self.origin.0 as f32, -self.origin.1 as f32)
+    }
+
+    // The pixels covered by the logical `rect` grown by `margin` pixels, as a
+    // half-open range in the current pixmap. It is clamped to the pixmap
+    // grown by `slack` pixels.
+    fn pixel_region(&self, rect: &Rect, margin: f32, slack: f32) -> (i32, i32, i32, i32) {
+        let scale = self.scale_factor;
+        let clamp = |value: f32, origin: i32, max: u32| (value - origin as f32).clamp(-slack, max as f32 + slack) as i32;
+        let (width, height) = (self.pixmap.width(), self.pixmap.height());
+        (
+            clamp((rect.origin.x * scale - margin).floor(), self.origin.0, width),
+            clamp((rect.origin.y * scale - margin).floor(), self.origin.1, height),
+            clamp(((rect.origin.x + rect.size.width) * scale + margin).ceil(), self.origin.0, width),
+            clamp(((rect.origin.y + rect.size.height) * scale + margin).ceil(), self.origin.1, height),
+        )
+    }
+
+    fn text_area(&self) -> Rect {
+        match self.groups.last() {
+            Some(group) => Rect::new(
+                group.content.origin.x + self.transform.x - group.translation.x,
+                group.content.origin.y + self.transform.y - group.translation.y,
+                group.content.size.width,
+                group.content.size.height,
+            ),
+            None => Rect::new(
+                self.transform.x,
+                self.transform.y,
+                self.size.width - self.transform.x,
+                self.size.height - self.transform.y,
+            ),
+        }
+    }
+
+    fn fill_rounded_rect(&mut self, rect: &Rect, radius: f32, brush: &Brush) {
+        let Some(paint) = brush_paint(brush, Point::default()) else { return };
+        if let Some(path) = rounded_rect_path(rect, radius) {
+            let transform = self.device_transform();
+            let mask = self.clips.last().map(|clip| &clip.mask);
+            self.pixmap.fill_path(&path, &paint, FillRule::Winding, transform, mask);
+        }
+    }
+
+    // Switches drawing to a layer covering the border box `rect` and its
+    // outer shadows, returning what to composite it onto. Children drawn
+    // outside that area are clipped while the layer is open.
+    // The pixels an opacity layer for the border box `rect` covers, as
+    // `x0, y0, width, height`: the box and its outer shadows, cut to the clip
+    // and the pixmap.
+    fn layer_region(&self, rect: &Rect, style: &Style) -> (u32, u32, u32, u32) {
+        let (mut left, mut top) = (rect.origin.x, rect.origin.y);
+        let (mut right, mut bottom) = (left + rect.size.width, top + rect.size.height);
+        for shadow in style.box_shadows.iter().filter(|shadow| !shadow.inset) {
+            // Matches the reach of the blur in `draw_shadows`.
+            let reach = shadow.spread + shadow.blur.max(0.0) * 1.5;
+            left = left.min(rect.origin.x + shadow.offset_x - reach);
+            top = top.min(rect.origin.y + shadow.offset_y - reach);
+            right = right.max(rect.origin.x + rect.size.width + shadow.offset_x + reach);
+            bottom = bottom.max(rect.origin.y + rect.size.height + shadow.offset_y + reach);
+        }
+        let mut area = Rect::new(left, top, right - left, bottom - top);
+        if let Some(clip) = self.clips.last() {
+            area = area.intersection(&clip.rect);
+        }
+        let (x0, y0, x1, y1) = self.pixel_region(&area, 1.0, 0.0);
+        // A group with nothing visible still needs somewhere to draw.
+        (x0 as u32, y0 as u32, (x1 - x0).max(1) as u32, (y1 - y0).max(1) as u32)
+    }
+
+    fn begin_layer(&mut self, rect: &Rect, style: &Style) -> Below {
+        let (x0, y0, width, height) = self.layer_region(rect, style);
+        let layer = Pixmap::new(width, height).expect("layer dimensions are non-zero");
+
+        // Clips pushed before the group carry over, cut down to the layer.
+        let clips = std::mem::take(&mut self.clips);
+        if let Some(clip) = clips.last() {
+            self.clips.push(Clip { rect: clip.rect.clone(), mask: crop_mask(&clip.mask, x0, y0, width, height) });
+        }
+        let origin = self.origin;
+        self.origin = (origin.0 + x0 as i32, origin.1 + y0 as i32);
+        Below {
+            pixmap: std::mem::replace(&mut self.pixmap, layer),
+            origin,
+            clips,
+            opacity: style.opacity.max(0.0),
+        }
+    }
+
+    // The pixels a shadow's masks cover for its shape `area`, as a half-open
+    // range. The blur reaches about three standard deviations out, so the
+    // region includes what lies past the pixmap's edges within that reach.
+    fn shadow_region(&self, area: &Rect, sigma: f32) -> Option<(i32, i32, i32, i32)> {
+        let margin = sigma * 3.0 + 1.0;
+        let (x0, y0, x1, y1) = self.pixel_region(area, margin, margin);
+        (x0 < x1 && y0 < y1).then_some((x0, y0, x1, y1))
+    }
+
+    // Outer shadows are drawn below the background of the border box `rect`
+    // and inset ones above it, each list back to front.
+    fn draw_shadows(&mut self, rect: &Rect, style: &Style, inset: bool) {
+        for shadow in style.box_shadows.iter().rev().filter(|shadow| shadow.inset == inset) {
+            if shadow.color.a <= 0.0 {
+                continue;
+            }
+            let sigma = shadow.blur.max(0.0) / 2.0 * self.scale_factor;
+            let (dx, dy, spread) = (shadow.offset_x, shadow.offset_y, shadow.spread);
+            let widths = style.border.widths();
+            let padding_box = Rect::new(
+                rect.origin.x + widths.left,
+                rect.origin.y + widths.top,
+                rect.size.width - widths.horizontal(),
+                rect.size.height - widths.vertical(),
+            );
+            let shape = Rect::new(
+                rect.origin.x + dx - spread,
+                rect.origin.y + dy - spread,
+                rect.size.width + spread * 2.0,
+                rect.size.height + spread * 2.0,
+            );
+
+            // An inset shadow only shows inside the padding box but is
+            // blurred from beyond it.
+            let area = if inset { &padding_box } else { &shape };
+            let Some((x0, y0, x1, y1)) = self.shadow_region(area, sigma) else { continue };
+            let (width, height) = ((x1 - x0) as u32, (y1 - y0) as u32);
+            let transform = self.device_transform().post_translate(-(x0 as f32), -(y0 as f32));
+            let mut coverage = Mask::new(width, height).expect("shadow region is not empty");
+            // Where the shadow may show.
+            let mut limit = Mask::new(width, height).expect("shadow region is not empty");
+
+            if inset {
+                let radius = (style.border_radius - widths.top.max(widths.left)).max(0.0);
+                let hole = Rect::new(
+                    padding_box.origin.x + dx + spread,
+                    padding_box.origin.y + dy + spread,
+                    padding_box.size.width - spread * 2.0,
+                    padding_box.size.height - spread * 2.0,
+                );
+                if let Some(path) = rounded_rect_path(&hole, radius - spread) {
+                    coverage.fill_path(&path, FillRule::Winding, true, transform);
+                }
+                coverage.invert();
+                if let Some(path) = rounded_rect_path(&padding_box, radius) {
+                    limit.fill_path(&path, FillRule::Winding, true, transform);
+                }
+            } else {
+                let radius = if style.border_radius > 0.0 { style.border_radius + spread } else { 0.0 };
+                if let Some(path) = rounded_rect_path(&shape, radius) {
+                    coverage.fill_path(&path, FillRule::Winding, true, transform);
+                }
+                // Hidden beneath the box itself.
+                if let Some(path) = rounded_rect_path(rect, style.border_radius) {
+                    limit.fill_path(&path, FillRule::Winding, true, transform);
+                }
+                limit.invert();
+            }
+            blur_mask(&mut coverage, (0, 0, width, height), sigma);
+
+            // The shadow color with the combined coverage as its alpha.
+            let color = to_skia_color(shadow.color).premultiply().to_color_u8();
+            let mut layer = Pixmap::new(width, height).expect("shadow region is not empty");
+            let clip = self.clips.last().map(|clip| clip.mask.data());
+            let (stride, rows) = (self.pixmap.width() as i32, self.pixmap.height() as i32);
+            for (index, pixel) in layer.pixels_mut().iter_mut().enumerate() {
+                let x = x0 + (index % width as usize) as i32;
+                let y = y0 + (index / width as usize) as i32;
+                let mut value = coverage.data()[index] as u32 * limit.data()[index] as u32 / 255;
+                match clip {
+                    // Past the pixmap's edges nothing is drawn anyway.
+                    Some(_) if x < 0 || y < 0 || x >= stride || y >= rows => value = 0,
+                    Some(clip) => value = value * clip[(y * stride + x) as usize] as u32 / 255,
+                    None => {}
+                }
+                let channel = |channel: u8| (channel as u32 * value / 255) as u8;
+                *pixel = tiny_skia::PremultipliedColorU8::from_rgba(
+                    channel(color.red()),
+                    channel(color.green()),
+                    channel(color.blue()),
+                    channel(color.alpha()),
+                )
+                .unwrap_or(tiny_skia::PremultipliedColorU8::TRANSPARENT);
+            }
+            self.pixmap.draw_pixmap(x0, y0, layer.as_ref(), &PixmapPaint::default(), Transform::identity(), None);
+        }
+    }
+
+    // A uniform border follows the rounded outline; otherwise each side is
+    // stroked as a straight band, with the vertical sides fitted between the
+    // horizontal ones so corners are not painted twice.
+    fn stroke_border(&mut self, rect: &Rect, style: &Style) {
+        let border = &style.border;
+        let (x, y) = (rect.origin.x, rect.origin.y);
+        let (w, h) = (rect.size.width, rect.size.height);
+
+        if border.is_uniform() {
+            let side = border.top;
+            let half = side.effective_width() / 2.0;
+            let inner = Rect::new(x + half, y + half, w - half * 2.0, h - half * 2.0);
+            if let Some(path) = rounded_rect_path(&inner, style.border_radius - half) {
+                self.stroke_side(&path, &side);
+            }
+            return;
+        }
+
+        let widths = border.widths();
+        let top = y + widths.top / 2.0;
+        let bottom = y + h - widths.bottom / 2.0;
+        let left = x + widths.left / 2.0;
+        let right = x + w - widths.right / 2.0;
+        let sides = [
+            (&border.top, (x, top), (x + w, top)),
+            (&border.bottom, (x, bottom), (x + w, bottom)),
+            (&border.left, (left, y + widths.top), (left, y + h - widths.bottom)),
+            (&border.right, (right, y + widths.top), (right, y + h - widths.bottom)),
+        ];
+        for (side, from, to) in sides {
+            let mut builder = PathBuilder::new();
+            builder.move_to(from.0, from.1);
+            builder.line_to(to.0, to.1);
+            if let Some(path) = builder.finish() {
+                self.stroke_side(&path, side);
+            }
+        }
+    }
+
+    fn stroke_side(&mut self, path: &tiny_skia::Path, side: &BorderSide) {
+        if !side.is_visible() {
+            return;
+        }
+        let Some(paint) = solid_paint(side.color) else { return };
+        let width = side.effective_width();
+        let dash = match side.style {
+            BorderStyle::Dashed => StrokeDash::new(vec![width * 3.0, width * 3.0], 0.0),
+            BorderStyle::Dotted => StrokeDash::new(vec![width, width], 0.0),
+            BorderStyle::None | BorderStyle::Solid => None,
+        };
+        let stroke = Stroke { width, line_cap: LineCap::Butt, dash, ..Stroke::default() };
+        let transform = self.device_transform();
+        let mask = self.clips.last().map(|clip| &clip.mask);
+        self.pixmap.stroke_path(path, &paint, &stroke, transform, mask);
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    // Clearing starts a new frame, so tinted textures the last one did not
+    // draw are dropped.
+    fn clear(&mut self, color: Color) {
+        self.pixmap.fill(to_skia_color(color));
+        self.tinted.retain(|_, tinted| std::mem::take(&mut tinted.used));
+    }
+
+    fn begin_group(&mut self, style: &Style, bounds: &Rect) {
+        let rect = Rect::new(
+            bounds.origin.x + self.transform.x,
+            bounds.origin.y + self.transform.y,
+            bounds.size.width,
+            bounds.size.height,
+        );
+        // A translucent group draws into a layer of its own, so its parts
+        // fade together rather than showing through each other.
+        let below = (style.opacity < 1.0).then(|| self.begin_layer(&rect, style));
+        self.draw_shadows(&rect, style, false);
+        self.fill_rounded_rect(&rect, style.border_radius, &Brush::from(style.background));
+        if let Some(gradient) = &style.background_gradient {
+            self.fill_rounded_rect(&rect, style.border_radius, &gradient.brush(&rect));
+        }
+        self.draw_shadows(&rect, style, true);
+        self.stroke_border(&rect, style);
+        let insets = content_insets(style);
+        self.groups.push(Group {
+            content: content_box(style, &rect),
+            radius: style.border_radius - insets.top.max(insets.left),
+            translation: self.transform,
+            below,
+        });
+    }
+
+    fn end_group(&mut self) {
+        let Some(group) = self.groups.pop() else { return };
+        if let Some(below) = group.below {
+            let layer = std::mem::replace(&mut self.pixmap, below.pixmap);
+            let (x, y) = (self.origin.0 - below.origin.0, self.origin.1 - below.origin.1);
+            self.origin = below.origin;
+            self.clips = below.clips;
+            let paint = PixmapPaint { opacity: below.opacity, ..PixmapPaint::default() };
+            self.pixmap.draw_pixmap(x, y, layer.as_ref(), &paint, Transform::identity(), None);
+        }
+    }
+
+    fn draw_text(&mut self, text: &str, style: &Style) {
+        let Some(paint) = solid_paint(style.color) else { return };
+        let area = self.text_area();
+        let paragraph = text::layout_paragraph(text, style, area.size.width);
+        let free = area.size.height - paragraph.size().height;
+        let top = area.origin.y + match style.vertical_align {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Center => free / 2.0,
+            VerticalAlign::Bottom => free,
+        };
+
+        let mut builder = PathBuilder::new();
+        let mut line_top = top;
+        for line in &paragraph.lines {
+            let (offset, word_spacing) = line.alignment(style, area.size.width);
+            let baseline = line_top + line.ascent;
+
+            for fragment in &line.fragments {
+                for glyph in &fragment.shaped.glyphs {
+                    glyph.font.face().outline_glyph(GlyphId(glyph.glyph_id), &mut font::GlyphPath {
+                        builder: &mut builder,
+                        x: area.origin.x + offset + line.glyph_x(fragment, glyph, word_spacing),
+                        y: baseline + glyph.y,
+                        scale: glyph.font.scale(glyph.font_size),
+                    });
+                }
+            }
+            line_top += line.height();
+        }
+
+        if let Some(path) = builder.finish() {
+            let transform = self.device_transform();
+            let mask = self.clips.last().map(|clip| &clip.mask);
+            self.pixmap.fill_path(&path, &paint, FillRule::Winding, transform, mask);
+        }
+    }
+
+    fn draw_image(&mut self, texture: &Texture, rect: &Rect, tint: Option<Color>) {
+        if rect.size.width <= 0.0 || rect.size.height <= 0.0 {
+            return;
+        }
+        let frame = self.text_area();
+        let dest = Rect::new(rect.origin.x + self.transform.x, rect.origin.y + self.transform.y, rect.size.width, rect.size.height);
+        let visible = frame.intersection(&dest);
+        let Some(area) = tiny_skia::Rect::from_xywh(visible.origin.x, visible.origin.y, visible.size.width, visible.size.height) else {
+            return;
+        };
+
+        let key = tint.map(|tint| (texture.id(), [tint.r, tint.g, tint.b, tint.a].map(f32::to_bits)));
+        if let (Some(key), Some(tint)) = (key, tint) {
+            self.tinted
+                .entry(key)
+                .or_insert_with(|| Tinted { pixmap: tinted_pixmap(texture.pixmap(), tint), used: false })
+                .used = true;
+        }
+        let source = match &key {
+            Some(key) => &self.tinted[key].pixmap,
+            None => texture.pixmap(),
+        };
+        let scale_x = dest.size.width / texture.width() as f32;
+        let scale_y = dest.size.height / texture.height() as f32;
+        let paint = Paint {
+            shader: Pattern::new(
+                source.as_ref(),
+                SpreadMode::Pad,
+                FilterQuality::Bilinear,
+                1.0,
+                Transform::from_row(scale_x, 0.0, 0.0, scale_y, dest.origin.x, dest.origin.y),
+            ),
+            anti_alias: true,
+            ..Paint::default()
+        };
+
+        let transform = self.device_transform();
+        let radius = self.groups.last().map_or(0.0, |group| group.radius);
+        let rounded = match rounded_rect_path(&frame, radius) {
+            Some(path) if radius > 0.0 => Some(path),
+            _ => None,
+        };
+        match rounded {
+            // Rounded corners need a mask combining them with any clip.
+            Some(path) => {
+                let mask = match self.clips.last() {
+                    Some(clip) => {
+                        let mut mask = clip.mask.clone();
+                        mask.intersect_path(&path, FillRule::Winding, true, transform);
+                        mask
+                    }
+                    None => {
+                        let mut mask = Mask::new(self.pixmap.width(), self.pixmap.height()).expect("pixmap dimensions are non-zero");
+                        mask.fill_path(&path, FillRule::Winding, true, transform);
+                        mask
+                    }
+                };
+                self.pixmap.fill_rect(area, &paint, transform, Some(&mask));
+            }
+            None => {
+                let mask = self.clips.last().map(|clip| &clip.mask);
+                self.pixmap.fill_rect(area, &paint, transform, mask);
+            }
+        }
+    }
+
+    fn fill_path(&mut self, path: &Path, brush: &Brush, rule: super::FillRule) {
+        let Some(paint) = brush_paint(brush, self.transform) else { return };
+        let Some(path) = skia_path(path, self.transform) else { return };
+        let rule = match rule {
+            super::FillRule::NonZero => FillRule::Winding,
+            super::FillRule::EvenOdd => FillRule::EvenOdd,
+        };
+        let transform = self.device_transform();
+        let mask = self.clips.last().map(|clip| &clip.mask);
+        self.pixmap.fill_path(&path, &paint, rule, transform, mask);
+    }
+
+    fn stroke_path(&mut self, path: &Path, brush: &Brush, stroke: &StrokeStyle) {
+        if stroke.width <= 0.0 {
+            return;
+        }
+        let Some(paint) = brush_paint(brush, self.transform) else { return };
+        let Some(path) = skia_path(path, self.transform) else { return };
+        let mut dash = stroke.dash.clone();
+        if dash.len() % 2 == 1 {
+            dash.extend_from_within(..);
+        }
+        let stroke = Stroke {
+            width: stroke.width,
+            miter_limit: stroke.miter_limit,
+            line_cap: match stroke.cap {
+                super::LineCap::Butt => LineCap::Butt,
+                super::LineCap::Round => LineCap::Round,
+                super::LineCap::Square => LineCap::Square,
+            },
+            line_join: match stroke.join {
+                super::LineJoin::Miter => LineJoin::Miter,
+                super::LineJoin::Round => LineJoin::Round,
+                super::LineJoin::Bevel => LineJoin::Bevel,
+            },
+            // Invalid patterns, e.g. with negative lengths, draw a solid line.
+            dash: StrokeDash::new(dash, stroke.dash_offset),
+        };
+        let transform = self.device_transform();
+        let mask = self.clips.last().map(|clip| &clip.mask);
+        self.pixmap.stroke_path(&path, &paint, &stroke, transform, mask);
+    }
+
+    fn translate(&mut self, x: f32, y: f32) {
+        self.transform.x += x;
+        self.transform.y += y;
+    }
+
+    fn push_clip(&mut self, rect: &Rect) {
+        let rect = Rect::new(
+            rect.origin.x + self.transform.x,
+            rect.origin.y + self.transform.y,
+            rect.size.width,
+            rect.size.height,
+        );
+        let rect = match self.clips.last() {
+            Some(outer) => outer.rect.intersection(&rect),
+            None => rect,
+        };
+        let mut mask = Mask::new(self.pixmap.width(), self.pixmap.height()).expect("pixmap dimensions are non-zero");
+        if let Some(path) = rounded_rect_path(&rect, 0.0) {
+            mask.fill_path(&path, FillRule::Winding, true, self.device_transform());
+        }
+        self.clips.push(Clip { rect, mask });
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+}
+
+// `path` moved by `offset`, or `None` when it has no area to draw.
+fn skia_path(path: &Path, offset: Point) -> Option<tiny_skia::Path> {
+    let (dx, dy) = (offset.x, offset.y);
+    let mut builder = PathBuilder::new();
+    for command in path.commands() {
+        match *command {
+            PathCommand::MoveTo { to } => builder.move_to(to.x + dx, to.y + dy),
+            PathCommand::LineTo { to } => builder.line_to(to.x + dx, to.y + dy),
+            PathCommand::QuadTo { control, to } => builder.quad_to(control.x + dx, control.y + dy, to.x + dx, to.y + dy),
+            PathCommand::CubicTo { control1, control2, to } => builder.cubic_to(
+                control1.x + dx,
+                control1.y + dy,
+                control2.x + dx,
+                control2.y + dy,
+                to.x + dx,
+                to.y + dy,
+            ),
+            PathCommand::Close => builder.close(),
+        }
+    }
+    builder.finish()
+}
+
+// `pixmap` with every pixel in `tint`, keeping its alpha.
+fn tinted_pixmap(pixmap: &Pixmap, tint: Color) -> Pixmap {
+    let mut tinted = pixmap.clone();
+    let color = to_skia_color(tint);
+    for pixel in tinted.pixels_mut() {
+        let alpha = pixel.alpha() as f32 / 255.0 * color.alpha();
+        let channel = |value: f32| (value * alpha * 255.0).round() as u8;
+        *pixel = tiny_skia::PremultipliedColorU8::from_rgba(
+            channel(color.red()),
+            channel(color.green()),
+            channel(color.blue()),
+            (alpha * 255.0).round() as u8,
+        )
+        .unwrap_or(tiny_skia::PremultipliedColorU8::TRANSPARENT);
+    }
+    tinted
+}
+
+// The `width` by `height` part of `mask` from `x`, `y`; pixels past its
+// edges are left empty.
+fn crop_mask(mask: &Mask, x: u32, y: u32, width: u32, height: u32) -> Mask {
+    let mut cropped = Mask::new(width, height).expect("crop dimensions are non-zero");
+    let stride = mask.width() as usize;
+    let columns = mask.width().saturating_sub(x).min(width) as usize;
+    for row in 0..mask.height().saturating_sub(y).min(height) as usize {
+        let source = (y as usize + row) * stride + x as usize;
+        let target = row * width as usize;
+        cropped.data_mut()[target..target + columns].copy_from_slice(&mask.data()[source..source + columns]);
+    }
+    cropped
+}
+
+fn to_skia_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba(
+        color.r.clamp(0.0, 1.0),
+        color.g.clamp(0.0, 1.0),
+        color.b.clamp(0.0, 1.0),
+        color.a.clamp(0.0, 1.0),
+    )
+    .unwrap_or(tiny_skia::Color::TRANSPARENT)
+}
+
+fn solid_paint(color: Color) -> Option<Paint<'static>> {
+    if color.a <= 0.0 {
+        return None;
+    }
+    let mut paint = Paint::default();
+    paint.set_color(to_skia_color(color));
+    paint.anti_alias = true;
+    Some(paint)
+}
+
+// Gradients are placed in the space moved by `offset`, like the paths they
+// fill. A gradient without length or radius paints its last stop's color.
+fn brush_paint(brush: &Brush, offset: Point) -> Option<Paint<'static>> {
+    if brush.is_transparent() {
+        return None;
+    }
+    let (stops, spread) = match brush {
+        Brush::Solid { color } => return solid_paint(*color),
+        Brush::LinearGradient { stops, spread, .. } | Brush::RadialGradient { stops, spread, .. } => (stops, spread),
+    };
+    let last = stops.last()?.color;
+    let skia_stops = stops
+        .iter()
+        .map(|stop| tiny_skia::GradientStop::new(stop.offset, to_skia_color(stop.color)))
+        .collect();
+    let mode = match spread {
+        GradientSpread::Pad => SpreadMode::Pad,
+        GradientSpread::Reflect => SpreadMode::Reflect,
+        GradientSpread::Repeat => SpreadMode::Repeat,
+    };
+    let transform = Transform::from_translate(offset.x, offset.y);
+    let shader = match brush {
+        Brush::LinearGradient { start, end, .. } => tiny_skia::LinearGradient::new(
+            tiny_skia::Point::from_xy(start.x, start.y),
+            tiny_skia::Point::from_xy(end.x, end.y),
+            skia_stops,
+            mode,
+            transform,
+        ),
+        Brush::RadialGradient { center, radius, .. } => {
+            let center = tiny_skia::Point::from_xy(center.x, center.y);
+            tiny_skia::RadialGradient::new(center, center, *radius, skia_stops, mode, transform)
+        }
+        Brush::Solid { .. } => None,
+    };
+    match shader {
+        Some(shader) => Some(Paint { shader, anti_alias: true, ..Paint::default() }),
+        None => solid_paint(last),
+    }
+}
+
+pub(crate) fn rounded_rect_path(rect: &Rect, radius: f32) -> Option<tiny_skia::Path> {
+    let (x, y) = (rect.origin.x, rect.origin.y);
+    let (w, h) = (rect.size.width, rect.size.height);
+    if w <= 0.0 || h <= 0.0 {
+        return None;
+    }
+
+    let r = radius.max(0.0).min(w / 2.0).min(h / 2.0);
+    if r <= 0.0 {
+        return tiny_skia::Rect::from_xywh(x, y, w, h).map(PathBuilder::from_rect);
+    }
+
+    // Distance from a corner to the cubic control points approximating a quarter circle.
+    const KAPPA: f32 = 0.552_284_8;
+    let k = r * (1.0 - KAPPA);
+
+    let mut builder = PathBuilder::new();
+    builder.move_to(x + r, y);
+    builder.line_to(x + w - r, y);
+    builder.cubic_to(x + w - k, y, x + w, y + k, x + w, y + r);
+    builder.line_to(x + w, y + h - r);
+    builder.cubic_to(x + w, y + h - k, x + w - k, y + h, x + w - r, y + h);
+    builder.line_to(x + r, y + h);
+    builder.cubic_to(x + k, y + h, x, y + h - k, x, y + h - r);
+    builder.line_to(x, y + r);
+    builder.cubic_to(x, y + k, x + k, y, x + r, y);
+    builder.close();
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::BoxShadow;
+
+    #[test]
+    fn shadow_masks_reach_past_the_pixmap_edge_by_the_blur() {
+        let renderer = SoftwareRenderer::new(Size::new(100.0, 100.0), 1.0);
+        // A 20x20 box against the top-right corner with a spread of 4 and a
+        // blur of 8, so sigma is 4 and the masks reach 13 pixels further.
+        let shape = Rect::new(76.0, 6.0, 28.0, 28.0);
+        let region = renderer.shadow_region(&shape, 4.0);
+        assert_eq!(
+            region,
+            Some((63, -7, 113, 47)),
+            "the mask for {:?} should cover it grown by 13 pixels, cut 13 pixels past the pixmap",
+            shape,
+        );
+
+        let outside = Rect::new(150.0, 0.0, 20.0, 20.0);
+        assert_eq!(renderer.shadow_region(&outside, 4.0), None, "a shadow well past the pixmap should be skipped");
+    }
+
+    #[test]
+    fn opacity_layers_cover_the_visible_part_of_the_group() {
+        let renderer = SoftwareRenderer::new(Size::new(100.0, 100.0), 1.0);
+        let rect = Rect::new(-30.0, 50.0, 60.0, 80.0);
+        let style = Style::default().set_opacity(0.5);
+        assert_eq!(
+            renderer.layer_region(&rect, &style),
+            (0, 49, 31, 51),
+            "the layer for {:?} should be the on-screen part grown by a pixel",
+            rect,
+        );
+
+        // Outer shadows widen the layer by their offset, spread and 1.5 times
+        // their blur; inset ones do not.
+        let style = style.set_box_shadows([
+            BoxShadow::new(0.0, -4.0, 4.0, Color::BLACK).set_spread(2.0),
+            BoxShadow::new(20.0, 0.0, 0.0, Color::BLACK).set_inset(true),
+        ]);
+        assert_eq!(
+            renderer.layer_region(&rect, &style),
+            (0, 37, 39, 63),
+            "the layer for {:?} should include its outer shadow",
+            rect,
+        );
+    }
+
+    #[test]
+    fn partly_off_screen_translucent_groups_draw_their_visible_part() {
+        let mut renderer = SoftwareRenderer::new(Size::new(100.0, 100.0), 1.0);
+        let style = Style::default().set_background(Color::RED).set_opacity(0.5);
+        renderer.begin_group(&style, &Rect::new(-30.0, 50.0, 60.0, 80.0));
+        renderer.end_group();
+        let frame = renderer.framebuffer();
+        let inside = frame.pixel(10, 60).unwrap();
+        assert!(inside[0] > 120 && inside[3] > 120, "expected half-transparent red at (10, 60), got {:?}", inside);
+        assert_eq!(frame.pixel(40, 60), Some([0, 0, 0, 0]), "nothing should be drawn right of the group");
+    }
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+use crate::{
+    components::Component,
+    geometry::{Rect, Size},
+    renderer::{Framebuffer, Renderer, SoftwareRenderer},
+    style::Color,
+};
+
+// Lays out and renders a component tree offscreen through the same
+// `Component::arrange`/`render` paths the live app uses, so designers can
+// review screens as PNG files.
+pub struct Snapshot {
+    size: Size,
+    scale_factor: f32,
+    background: Color,
+}
+
+impl Snapshot {
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            scale_factor: 1.0,
+            background: Color::WHITE,
+        }
+    }
+
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    pub fn render(&self, component: &mut dyn Component) -> Framebuffer {
+        component.arrange(Rect::new(0.0, 0.0, self.size.width, self.size.height));
+        let mut renderer = SoftwareRenderer::new(self.size, self.scale_factor);
+        renderer.clear(self.background);
+        component.render(&mut renderer);
+        renderer.framebuffer()
+    }
+
+    pub fn save_png<P: AsRef<Path>>(
+        &self,
+        component: &mut dyn Component,
+        path: P,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.render(component).save_png(path)
+    }
+}
@@ -1,16 +1,83 @@
-#[derive(Clone, Debug, Default)]
+use serde::{Deserialize, Serialize};
+use crate::geometry::{Point, Rect};
+use crate::renderer::{Brush, GradientStop};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Style {
     pub background: Color,
+    // Painted over `background`, inside the same rounded outline.
+    pub background_gradient: Option<Gradient>,
+    // The first shadow is drawn on top, as in CSS.
+    pub box_shadows: Vec<BoxShadow>,
+    // Applied to the component and its children as a whole, after they are
+    // drawn together.
+    pub opacity: f32,
     pub color: Color,
-    pub padding: f32,
-    pub margin: f32,
+    pub padding: EdgeInsets,
+    pub margin: EdgeInsets,
+    pub border: Border,
+    pub width: Dimension,
+    pub height: Dimension,
+    pub min_width: Dimension,
+    pub min_height: Dimension,
+    pub max_width: Dimension,
+    pub max_height: Dimension,
+    pub aspect_ratio: Option<f32>,
     pub gap: f32,
     pub font_size: f32,
+    // Families tried in order before the global fallbacks; empty uses the
+    // bundled font.
+    pub font_family: Vec<String>,
+    pub font_weight: FontWeight,
+    pub font_style: FontStyle,
+    // A multiple of the font size; 0 uses the font's own line spacing.
+    pub line_height: f32,
+    pub letter_spacing: f32,
     pub border_radius: f32,
+    pub direction: LayoutDirection,
     pub text_align: TextAlign,
+    pub vertical_align: VerticalAlign,
+    pub text_wrap: TextWrap,
+    pub text_overflow: TextOverflow,
+    // Lines beyond this are dropped, with an ellipsis if `text_overflow`
+    // asks for one.
+    pub max_lines: Option<usize>,
+    pub flex_wrap: FlexWrap,
+    pub justify_content: JustifyContent,
+    pub align_items: AlignItems,
+    pub align_self: AlignSelf,
+    // Inline-axis alignment of grid items inside their areas.
+    pub justify_items: AlignItems,
+    pub justify_self: AlignSelf,
+    pub flex_grow: f32,
+    pub flex_shrink: f32,
+    pub flex_basis: Dimension,
+    pub grid_row: GridPlacement,
+    pub grid_column: GridPlacement,
+    pub grid_area: Option<String>,
+    pub position: Position,
+    pub top: Dimension,
+    pub right: Dimension,
+    pub bottom: Dimension,
+    pub left: Dimension,
+    pub z_index: i32,
+}
+
+// Text properties that override a base `Style` for one span of rich text;
+// `None` inherits.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpanStyle {
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+    pub font_size: Option<f32>,
+    pub font_family: Option<Vec<String>>,
+    pub font_weight: Option<FontWeight>,
+    pub font_style: Option<FontStyle>,
+    pub letter_spacing: Option<f32>,
+    pub underline: Option<bool>,
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -18,12 +85,516 @@ pub struct Color {
     pub a: f32,
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum TextAlign {
+    // The side text starts from: left in LTR, right in RTL.
     #[default]
+    Start,
+    End,
     Left,
     Center,
     Right,
+    // Stretches wrapped lines to the full width; the last line of each
+    // paragraph stays start aligned.
+    Justify,
+}
+
+// The inline direction of text and of `Layout::Row`. RTL also swaps the
+// sides of `padding`, so `padding.left` is the start side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutDirection {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+// Where a block of text sits in a taller content box.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum VerticalAlign {
+    #[default]
+    Top,
+    Center,
+    Bottom,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TextWrap {
+    // Lines only break at `\n`.
+    #[default]
+    NoWrap,
+    // Breaks after whitespace, and inside words too long for a line.
+    Word,
+    // Breaks between any two graphemes.
+    Character,
+}
+
+// What happens to text that does not fit its width or `max_lines`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TextOverflow {
+    #[default]
+    Clip,
+    EllipsisStart,
+    EllipsisMiddle,
+    EllipsisEnd,
+}
+
+// A weight on the usual 100 (thin) to 900 (black) scale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct FontWeight(pub u16);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FontStyle {
+    #[default]
+    Normal,
+    Italic,
+    Oblique,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Dimension {
+    #[default]
+    Auto,
+    Points(f32),
+    Percent(f32),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum FlexWrap {
+    #[default]
+    NoWrap,
+    Wrap,
+    WrapReverse,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum JustifyContent {
+    #[default]
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum AlignItems {
+    #[default]
+    Stretch,
+    Start,
+    End,
+    Center,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum AlignSelf {
+    #[default]
+    Auto,
+    Stretch,
+    Start,
+    End,
+    Center,
+}
+
+// Per-side lengths, used for padding, margin and border widths.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EdgeInsets {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum BorderStyle {
+    #[default]
+    None,
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BorderSide {
+    pub width: f32,
+    pub color: Color,
+    pub style: BorderStyle,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Border {
+    pub top: BorderSide,
+    pub right: BorderSide,
+    pub bottom: BorderSide,
+    pub left: BorderSide,
+}
+
+// Gradients are laid out against the box they fill, so one style fits any
+// size.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Gradient {
+    // `angle` is in degrees clockwise from pointing up, as in CSS: 90 runs
+    // left to right and 180 top to bottom. The gradient line passes through
+    // the center and is long enough for the end colors to reach the corners.
+    Linear { angle: f32, stops: Vec<GradientStop> },
+    // A circle around `center`, given as fractions of the box's width and
+    // height, reaching its farthest corner.
+    Radial { center: Point, stops: Vec<GradientStop> },
+}
+
+// An outer shadow is cast by the border box and hidden beneath it; an inset
+// one falls inside the padding box, as if the box were a hole. `blur` is
+// the CSS blur radius and `spread` grows the shadow's shape before blurring
+// (or shrinks the hole, for inset shadows).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BoxShadow {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub blur: f32,
+    pub spread: f32,
+    pub color: Color,
+    pub inset: bool,
+}
+
+// `Relative` shifts a child from its laid-out position by the offsets;
+// `Absolute` takes it out of flow and places it against the parent's bounds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Position {
+    #[default]
+    Static,
+    Relative,
+    Absolute,
+}
+
+// Places a grid child by zero-based track index; `start: None` lets the grid
+// auto-place it. `span` is the number of tracks covered (at least one).
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GridPlacement {
+    pub start: Option<usize>,
+    pub span: usize,
+}
+
+impl Default for GridPlacement {
+    fn default() -> Self {
+        Self { start: None, span: 1 }
+    }
+}
+
+impl GridPlacement {
+    pub fn at(start: usize) -> Self {
+        Self { start: Some(start), span: 1 }
+    }
+
+    pub fn auto() -> Self {
+        Self::default()
+    }
+
+    pub fn span(mut self, span: usize) -> Self {
+        self.span = span;
+        self
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            background: Color::default(),
+            background_gradient: None,
+            box_shadows: Vec::new(),
+            opacity: 1.0,
+            color: Color::default(),
+            padding: EdgeInsets::default(),
+            margin: EdgeInsets::default(),
+            border: Border::default(),
+            width: Dimension::Auto,
+            height: Dimension::Auto,
+            min_width: Dimension::Auto,
+            min_height: Dimension::Auto,
+            max_width: Dimension::Auto,
+            max_height: Dimension::Auto,
+            aspect_ratio: None,
+            gap: 0.0,
+            font_size: 0.0,
+            font_family: Vec::new(),
+            font_weight: FontWeight::NORMAL,
+            font_style: FontStyle::Normal,
+            line_height: 0.0,
+            letter_spacing: 0.0,
+            border_radius: 0.0,
+            direction: LayoutDirection::default(),
+            text_align: TextAlign::default(),
+            vertical_align: VerticalAlign::default(),
+            text_wrap: TextWrap::default(),
+            text_overflow: TextOverflow::default(),
+            max_lines: None,
+            flex_wrap: FlexWrap::default(),
+            justify_content: JustifyContent::default(),
+            align_items: AlignItems::default(),
+            align_self: AlignSelf::default(),
+            justify_items: AlignItems::default(),
+            justify_self: AlignSelf::default(),
+            flex_grow: 0.0,
+            flex_shrink: 1.0,
+            flex_basis: Dimension::Auto,
+            grid_row: GridPlacement::default(),
+            grid_column: GridPlacement::default(),
+            grid_area: None,
+            position: Position::default(),
+            top: Dimension::Auto,
+            right: Dimension::Auto,
+            bottom: Dimension::Auto,
+            left: Dimension::Auto,
+            z_index: 0,
+        }
+    }
+}
+
+impl SpanStyle {
+    pub fn set_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn set_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn set_font_size(mut self, size: f32) -> Self {
+        self.font_size = Some(size);
+        self
+    }
+
+    pub fn set_font_family<I, S>(mut self, families: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.font_family = Some(families.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn set_font_weight(mut self, weight: FontWeight) -> Self {
+        self.font_weight = Some(weight);
+        self
+    }
+
+    pub fn set_font_style(mut self, style: FontStyle) -> Self {
+        self.font_style = Some(style);
+        self
+    }
+
+    pub fn set_letter_spacing(mut self, spacing: f32) -> Self {
+        self.letter_spacing = Some(spacing);
+        self
+    }
+
+    pub fn set_underline(mut self, underline: bool) -> Self {
+        self.underline = Some(underline);
+        self
+    }
+
+    pub fn bold(self) -> Self {
+        self.set_font_weight(FontWeight::BOLD)
+    }
+
+    pub fn italic(self) -> Self {
+        self.set_font_style(FontStyle::Italic)
+    }
+
+    // The text properties of `base` with these overrides applied. Box
+    // properties such as padding and borders are not inherited.
+    pub fn resolve(&self, base: &Style) -> Style {
+        Style {
+            color: self.color.unwrap_or(base.color),
+            font_size: self.font_size.unwrap_or(base.font_size),
+            font_family: self.font_family.clone().unwrap_or_else(|| base.font_family.clone()),
+            font_weight: self.font_weight.unwrap_or(base.font_weight),
+            font_style: self.font_style.unwrap_or(base.font_style),
+            letter_spacing: self.letter_spacing.unwrap_or(base.letter_spacing),
+            line_height: base.line_height,
+            direction: base.direction,
+            ..Style::default()
+        }
+    }
+}
+
+impl FontWeight {
+    pub const THIN: Self = Self(100);
+    pub const EXTRA_LIGHT: Self = Self(200);
+    pub const LIGHT: Self = Self(300);
+    pub const NORMAL: Self = Self(400);
+    pub const MEDIUM: Self = Self(500);
+    pub const SEMI_BOLD: Self = Self(600);
+    pub const BOLD: Self = Self(700);
+    pub const EXTRA_BOLD: Self = Self(800);
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+impl Dimension {
+    pub fn resolve(&self, reference: f32) -> Option<f32> {
+        match self {
+            Dimension::Auto => None,
+            Dimension::Points(points) => Some(*points),
+            Dimension::Percent(percent) => Some(reference * percent / 100.0),
+        }
+    }
+}
+
+impl EdgeInsets {
+    pub fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self { top, right, bottom, left }
+    }
+
+    pub fn all(value: f32) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    pub fn symmetric(vertical: f32, horizontal: f32) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+
+    // The same insets with left and right swapped.
+    pub fn mirrored(self) -> Self {
+        Self::new(self.top, self.left, self.bottom, self.right)
+    }
+}
+
+impl TextAlign {
+    // Resolves `Start` and `End` to a physical side for `direction`.
+    pub fn resolve(self, direction: LayoutDirection) -> TextAlign {
+        match (self, direction) {
+            (TextAlign::Start, LayoutDirection::Ltr) | (TextAlign::End, LayoutDirection::Rtl) => TextAlign::Left,
+            (TextAlign::Start, LayoutDirection::Rtl) | (TextAlign::End, LayoutDirection::Ltr) => TextAlign::Right,
+            (align, _) => align,
+        }
+    }
+}
+
+impl From<f32> for EdgeInsets {
+    fn from(value: f32) -> Self {
+        Self::all(value)
+    }
+}
+
+impl std::ops::Add for EdgeInsets {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.top + other.top,
+            self.right + other.right,
+            self.bottom + other.bottom,
+            self.left + other.left,
+        )
+    }
+}
+
+impl BorderSide {
+    pub fn new(width: f32, color: Color, style: BorderStyle) -> Self {
+        Self { width, color, style }
+    }
+
+    pub fn solid(width: f32, color: Color) -> Self {
+        Self::new(width, color, BorderStyle::Solid)
+    }
+
+    // A side with `BorderStyle::None` takes no space, whatever its width.
+    pub fn effective_width(&self) -> f32 {
+        match self.style {
+            BorderStyle::None => 0.0,
+            _ => self.width.max(0.0),
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.effective_width() > 0.0 && self.color.a > 0.0
+    }
+}
+
+impl Gradient {
+    pub fn linear(angle: f32, stops: Vec<GradientStop>) -> Self {
+        Gradient::Linear { angle, stops }
+    }
+
+    // Centered in the box.
+    pub fn radial(stops: Vec<GradientStop>) -> Self {
+        Gradient::Radial { center: Point::new(0.5, 0.5), stops }
+    }
+
+    // The brush painting this gradient over `rect`.
+    pub(crate) fn brush(&self, rect: &Rect) -> Brush {
+        let (x, y) = (rect.origin.x, rect.origin.y);
+        let (w, h) = (rect.size.width, rect.size.height);
+        match self {
+            Gradient::Linear { angle, stops } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let half = (w * sin.abs() + h * cos.abs()) / 2.0;
+                let center = Point::new(x + w / 2.0, y + h / 2.0);
+                Brush::linear_gradient(
+                    Point::new(center.x - sin * half, center.y + cos * half),
+                    Point::new(center.x + sin * half, center.y - cos * half),
+                    stops.clone(),
+                )
+            }
+            Gradient::Radial { center, stops } => {
+                let center = Point::new(x + center.x * w, y + center.y * h);
+                let dx = (center.x - x).max(x + w - center.x);
+                let dy = (center.y - y).max(y + h - center.y);
+                Brush::radial_gradient(center, dx.hypot(dy), stops.clone())
+            }
+        }
+    }
+}
+
+impl BoxShadow {
+    pub fn new(offset_x: f32, offset_y: f32, blur: f32, color: Color) -> Self {
+        Self { offset_x, offset_y, blur, spread: 0.0, color, inset: false }
+    }
+
+    pub fn set_spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn set_inset(mut self, inset: bool) -> Self {
+        self.inset = inset;
+        self
+    }
+}
+
+impl Border {
+    pub fn all(side: BorderSide) -> Self {
+        Self { top: side, right: side, bottom: side, left: side }
+    }
+
+    pub fn widths(&self) -> EdgeInsets {
+        EdgeInsets::new(
+            self.top.effective_width(),
+            self.right.effective_width(),
+            self.bottom.effective_width(),
+            self.left.effective_width(),
+        )
+    }
+
+    pub fn is_uniform(&self) -> bool {
+        self.top == self.right && self.top == self.bottom && self.top == self.left
+    }
 }
 
 impl Style {
@@ -36,18 +607,39 @@ impl Style {
         self
     }
 
+    pub fn set_background_gradient(mut self, gradient: Gradient) -> Self {
+        self.background_gradient = Some(gradient);
+        self
+    }
+
+    pub fn set_box_shadows<I: IntoIterator<Item = BoxShadow>>(mut self, shadows: I) -> Self {
+        self.box_shadows = shadows.into_iter().collect();
+        self
+    }
+
+    // Clamped to 0..=1.
+    pub fn set_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
     pub fn set_color(mut self, color: Color) -> Self {
         self.color = color;
         self
     }
 
-    pub fn set_padding(mut self, padding: f32) -> Self {
-        self.padding = padding;
+    pub fn set_padding<E: Into<EdgeInsets>>(mut self, padding: E) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    pub fn set_margin<E: Into<EdgeInsets>>(mut self, margin: E) -> Self {
+        self.margin = margin.into();
         self
     }
 
-    pub fn set_margin(mut self, margin: f32) -> Self {
-        self.margin = margin;
+    pub fn set_border(mut self, border: Border) -> Self {
+        self.border = border;
         self
     }
 
@@ -56,21 +648,201 @@ impl Style {
         self
     }
 
+    pub fn set_width(mut self, width: Dimension) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn set_height(mut self, height: Dimension) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn set_min_width(mut self, width: Dimension) -> Self {
+        self.min_width = width;
+        self
+    }
+
+    pub fn set_min_height(mut self, height: Dimension) -> Self {
+        self.min_height = height;
+        self
+    }
+
+    pub fn set_max_width(mut self, width: Dimension) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    pub fn set_max_height(mut self, height: Dimension) -> Self {
+        self.max_height = height;
+        self
+    }
+
+    // Width divided by height.
+    pub fn set_aspect_ratio(mut self, ratio: f32) -> Self {
+        self.aspect_ratio = Some(ratio);
+        self
+    }
+
     pub fn set_font_size(mut self, size: f32) -> Self {
         self.font_size = size;
         self
     }
 
+    pub fn set_font_family<I, S>(mut self, families: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.font_family = families.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn set_font_weight(mut self, weight: FontWeight) -> Self {
+        self.font_weight = weight;
+        self
+    }
+
+    pub fn set_font_style(mut self, style: FontStyle) -> Self {
+        self.font_style = style;
+        self
+    }
+
+    pub fn set_line_height(mut self, line_height: f32) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    pub fn set_letter_spacing(mut self, spacing: f32) -> Self {
+        self.letter_spacing = spacing;
+        self
+    }
+
     pub fn set_border_radius(mut self, radius: f32) -> Self {
         self.border_radius = radius;
         self
     }
 
+    pub fn set_direction(mut self, direction: LayoutDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
     pub fn set_text_align(mut self, align: TextAlign) -> Self {
         self.text_align = align;
         self
     }
 
+    pub fn set_vertical_align(mut self, align: VerticalAlign) -> Self {
+        self.vertical_align = align;
+        self
+    }
+
+    pub fn set_text_wrap(mut self, wrap: TextWrap) -> Self {
+        self.text_wrap = wrap;
+        self
+    }
+
+    pub fn set_text_overflow(mut self, overflow: TextOverflow) -> Self {
+        self.text_overflow = overflow;
+        self
+    }
+
+    pub fn set_max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = Some(lines);
+        self
+    }
+
+    pub fn set_flex_wrap(mut self, wrap: FlexWrap) -> Self {
+        self.flex_wrap = wrap;
+        self
+    }
+
+    pub fn set_justify_content(mut self, justify: JustifyContent) -> Self {
+        self.justify_content = justify;
+        self
+    }
+
+    pub fn set_align_items(mut self, align: AlignItems) -> Self {
+        self.align_items = align;
+        self
+    }
+
+    pub fn set_align_self(mut self, align: AlignSelf) -> Self {
+        self.align_self = align;
+        self
+    }
+
+    pub fn set_justify_items(mut self, justify: AlignItems) -> Self {
+        self.justify_items = justify;
+        self
+    }
+
+    pub fn set_justify_self(mut self, justify: AlignSelf) -> Self {
+        self.justify_self = justify;
+        self
+    }
+
+    pub fn set_flex_grow(mut self, grow: f32) -> Self {
+        self.flex_grow = grow;
+        self
+    }
+
+    pub fn set_flex_shrink(mut self, shrink: f32) -> Self {
+        self.flex_shrink = shrink;
+        self
+    }
+
+    pub fn set_flex_basis(mut self, basis: Dimension) -> Self {
+        self.flex_basis = basis;
+        self
+    }
+
+    pub fn set_grid_row(mut self, placement: GridPlacement) -> Self {
+        self.grid_row = placement;
+        self
+    }
+
+    pub fn set_grid_column(mut self, placement: GridPlacement) -> Self {
+        self.grid_column = placement;
+        self
+    }
+
+    pub fn set_grid_area<S: Into<String>>(mut self, area: S) -> Self {
+        self.grid_area = Some(area.into());
+        self
+    }
+
+    pub fn set_position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn set_top(mut self, top: Dimension) -> Self {
+        self.top = top;
+        self
+    }
+
+    pub fn set_right(mut self, right: Dimension) -> Self {
+        self.right = right;
+        self
+    }
+
+    pub fn set_bottom(mut self, bottom: Dimension) -> Self {
+        self.bottom = bottom;
+        self
+    }
+
+    pub fn set_left(mut self, left: Dimension) -> Self {
+        self.left = left;
+        self
+    }
+
+    pub fn set_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
     pub fn get_gap(&self) -> f32 {
         self.gap
     }
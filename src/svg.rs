@@ -0,0 +1,230 @@
+use std::path::PathBuf;
+use usvg::tiny_skia_path::{PathSegment, Transform};
+use crate::geometry::{Point, Rect, Size};
+use crate::renderer::{
+    Brush, FillRule, GradientSpread, GradientStop, LineCap, LineJoin, Path, Renderer, StrokeStyle,
+};
+use crate::style::Color;
+use crate::texture::ImageSource;
+
+struct Shape {
+    path: Path,
+    fill: Option<(Brush, FillRule)>,
+    stroke: Option<(Brush, StrokeStyle)>,
+    // `paint-order: stroke`.
+    stroke_first: bool,
+}
+
+// An SVG flattened into filled and stroked paths in document coordinates,
+// so it can be drawn crisply at any size. Paths, basic shapes, groups,
+// transforms, solid colors, linear and radial gradients and opacity are
+// supported; opacity is folded into the paint colors, so overlapping shapes
+// in a translucent group show through each other. Text, embedded images,
+// patterns, clip paths, masks and filters are not drawn.
+pub struct SvgDocument {
+    size: Size,
+    shapes: Vec<Shape>,
+}
+
+impl SvgDocument {
+    // Parses SVG or gzip-compressed SVGZ data.
+    pub fn parse(data: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default())?;
+        let mut shapes = Vec::new();
+        collect_shapes(tree.root(), 1.0, &mut shapes);
+        Ok(Self {
+            size: Size::new(tree.size().width(), tree.size().height()),
+            shapes,
+        })
+    }
+
+    pub fn load(source: &ImageSource) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::parse(&source.read()?)
+    }
+
+    pub fn from_file<P: Into<PathBuf>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load(&ImageSource::file(path))
+    }
+
+    // The document's width and height, which its viewBox is mapped onto.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    // Draws the document stretched over `rect`, in the renderer's current
+    // translated space. With `color` set, every fill and stroke is painted
+    // in it instead, keeping its own alpha.
+    pub fn draw(&self, renderer: &mut dyn Renderer, rect: &Rect, color: Option<Color>) {
+        if self.size.width <= 0.0 || self.size.height <= 0.0 {
+            return;
+        }
+        let scale_x = rect.size.width / self.size.width;
+        let scale_y = rect.size.height / self.size.height;
+        let place = |point: Point| Point::new(rect.origin.x + point.x * scale_x, rect.origin.y + point.y * scale_y);
+        let length_scale = (scale_x * scale_y).abs().sqrt();
+        let paint = |brush: &Brush| {
+            let brush = brush.map_points(place, length_scale);
+            match color {
+                Some(color) => brush.map_colors(|original| Color::rgba(color.r, color.g, color.b, color.a * original.a)),
+                None => brush,
+            }
+        };
+
+        for shape in &self.shapes {
+            let path = shape.path.map_points(place);
+            let fill = |renderer: &mut dyn Renderer| {
+                if let Some((brush, rule)) = &shape.fill {
+                    renderer.fill_path(&path, &paint(brush), *rule);
+                }
+            };
+            let stroke = |renderer: &mut dyn Renderer| {
+                if let Some((brush, stroke)) = &shape.stroke {
+                    renderer.stroke_path(&path, &paint(brush), &scale_stroke(stroke, length_scale));
+                }
+            };
+            if shape.stroke_first {
+                stroke(renderer);
+                fill(renderer);
+            } else {
+                fill(renderer);
+                stroke(renderer);
+            }
+        }
+    }
+}
+
+fn collect_shapes(group: &usvg::Group, opacity: f32, shapes: &mut Vec<Shape>) {
+    let opacity = opacity * group.opacity().get();
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(group) => collect_shapes(group, opacity, shapes),
+            usvg::Node::Path(path) if path.is_visible() => {
+                if let Some(shape) = convert_path(path, opacity) {
+                    shapes.push(shape);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn convert_path(source: &usvg::Path, opacity: f32) -> Option<Shape> {
+    let transform = source.abs_transform();
+    let data = source.data().clone().transform(transform)?;
+    let mut path = Path::new();
+    for segment in data.segments() {
+        match segment {
+            PathSegment::MoveTo(to) => path.move_to(to.x, to.y),
+            PathSegment::LineTo(to) => path.line_to(to.x, to.y),
+            PathSegment::QuadTo(control, to) => path.quad_to(control.x, control.y, to.x, to.y),
+            PathSegment::CubicTo(control1, control2, to) => {
+                path.cubic_to(control1.x, control1.y, control2.x, control2.y, to.x, to.y)
+            }
+            PathSegment::Close => path.close(),
+        };
+    }
+
+    let fill = source.fill().and_then(|fill| {
+        let brush = convert_paint(fill.paint(), opacity * fill.opacity().get(), transform)?;
+        let rule = match fill.rule() {
+            usvg::FillRule::NonZero => FillRule::NonZero,
+            usvg::FillRule::EvenOdd => FillRule::EvenOdd,
+        };
+        Some((brush, rule))
+    });
+    let stroke = source.stroke().and_then(|stroke| {
+        let brush = convert_paint(stroke.paint(), opacity * stroke.opacity().get(), transform)?;
+        let scale = length_scale(transform);
+        let style = StrokeStyle {
+            width: stroke.width().get() * scale,
+            cap: match stroke.linecap() {
+                usvg::LineCap::Butt => LineCap::Butt,
+                usvg::LineCap::Round => LineCap::Round,
+                usvg::LineCap::Square => LineCap::Square,
+            },
+            join: match stroke.linejoin() {
+                usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => LineJoin::Miter,
+                usvg::LineJoin::Round => LineJoin::Round,
+                usvg::LineJoin::Bevel => LineJoin::Bevel,
+            },
+            miter_limit: stroke.miterlimit().get(),
+            dash: stroke.dasharray().map_or(Vec::new(), |dash| dash.iter().map(|length| length * scale).collect()),
+            dash_offset: stroke.dashoffset() * scale,
+        };
+        Some((brush, style))
+    });
+
+    if fill.is_none() && stroke.is_none() {
+        return None;
+    }
+    Some(Shape {
+        path,
+        fill,
+        stroke,
+        stroke_first: source.paint_order() == usvg::PaintOrder::StrokeAndFill,
+    })
+}
+
+// Gradients are placed by mapping their defining points through the
+// shape's transform, which is exact for translation, rotation and uniform
+// scaling and approximate for skews and uneven scaling.
+fn convert_paint(paint: &usvg::Paint, opacity: f32, transform: Transform) -> Option<Brush> {
+    let color = |color: usvg::Color, alpha: f32| {
+        Color::rgba(color.red as f32 / 255.0, color.green as f32 / 255.0, color.blue as f32 / 255.0, alpha)
+    };
+    let stops = |stops: &[usvg::Stop]| {
+        stops
+            .iter()
+            .map(|stop| GradientStop::new(stop.offset().get(), color(stop.color(), stop.opacity().get() * opacity)))
+            .collect::<Vec<_>>()
+    };
+    let spread = |method: usvg::SpreadMethod| match method {
+        usvg::SpreadMethod::Pad => GradientSpread::Pad,
+        usvg::SpreadMethod::Reflect => GradientSpread::Reflect,
+        usvg::SpreadMethod::Repeat => GradientSpread::Repeat,
+    };
+
+    match paint {
+        usvg::Paint::Color(value) => Some(Brush::Solid { color: color(*value, opacity) }),
+        usvg::Paint::LinearGradient(gradient) => {
+            let transform = transform.pre_concat(gradient.transform());
+            Some(Brush::LinearGradient {
+                start: map_point(transform, gradient.x1(), gradient.y1()),
+                end: map_point(transform, gradient.x2(), gradient.y2()),
+                stops: stops(gradient.stops()),
+                spread: spread(gradient.spread_method()),
+            })
+        }
+        usvg::Paint::RadialGradient(gradient) => {
+            let transform = transform.pre_concat(gradient.transform());
+            Some(Brush::RadialGradient {
+                center: map_point(transform, gradient.cx(), gradient.cy()),
+                radius: gradient.r().get() * length_scale(transform),
+                stops: stops(gradient.stops()),
+                spread: spread(gradient.spread_method()),
+            })
+        }
+        usvg::Paint::Pattern(_) => None,
+    }
+}
+
+fn map_point(transform: Transform, x: f32, y: f32) -> Point {
+    Point::new(
+        transform.sx * x + transform.kx * y + transform.tx,
+        transform.ky * x + transform.sy * y + transform.ty,
+    )
+}
+
+// How much `transform` scales lengths on average, e.g. stroke widths.
+fn length_scale(transform: Transform) -> f32 {
+    (transform.sx * transform.sy - transform.kx * transform.ky).abs().sqrt()
+}
+
+fn scale_stroke(stroke: &StrokeStyle, scale: f32) -> StrokeStyle {
+    StrokeStyle {
+        width: stroke.width * scale,
+        dash: stroke.dash.iter().map(|length| length * scale).collect(),
+        dash_offset: stroke.dash_offset * scale,
+        ..stroke.clone()
+    }
+}
@@ -0,0 +1,277 @@
+use std::{env, fs, path::PathBuf};
+use crate::{
+    components::Component,
+    renderer::Framebuffer,
+    snapshot::Snapshot,
+};
+
+// Set to `1` to (re)write reference images instead of comparing against them.
+pub const UPDATE_GOLDENS_ENV: &str = "RUSTUI_UPDATE_GOLDENS";
+
+#[derive(Clone, Debug)]
+pub struct ImageDiff {
+    pub differing_pixels: usize,
+    pub max_difference: u8,
+    pub size_mismatch: bool,
+    pub diff: Framebuffer,
+}
+
+impl ImageDiff {
+    pub fn is_match(&self, max_differing_pixels: usize) -> bool {
+        !self.size_mismatch && self.differing_pixels <= max_differing_pixels
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum GoldenOutcome {
+    Matched(ImageDiff),
+    Updated(PathBuf),
+    Missing {
+        reference: PathBuf,
+        actual: PathBuf,
+    },
+    Mismatch {
+        diff: ImageDiff,
+        actual: PathBuf,
+        expected: PathBuf,
+        diff_image: PathBuf,
+    },
+}
+
+impl GoldenOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, GoldenOutcome::Matched(_) | GoldenOutcome::Updated(_))
+    }
+}
+
+// Compares two framebuffers channel by channel. A pixel counts as different when
+// any channel differs by more than `tolerance`. The diff image shows matching
+// pixels as a faded grayscale copy of `expected` and differing pixels in red.
+pub fn compare_images(expected: &Framebuffer, actual: &Framebuffer, tolerance: u8) -> ImageDiff {
+    let width = expected.width().max(actual.width());
+    let height = expected.height().max(actual.height());
+    let mut diff = Framebuffer::new(width, height);
+    let mut differing_pixels = 0;
+    let mut max_difference = 0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let (Some(e), Some(a)) = (expected.pixel(x, y), actual.pixel(x, y)) else {
+                differing_pixels += 1;
+                max_difference = u8::MAX;
+                diff.set_pixel(x, y, [255, 0, 0, 255]);
+                continue;
+            };
+
+            let delta = e.iter().zip(a.iter()).map(|(e, a)| e.abs_diff(*a)).max().unwrap_or(0);
+            max_difference = max_difference.max(delta);
+
+            if delta > tolerance {
+                differing_pixels += 1;
+                diff.set_pixel(x, y, [255, 0, 0, 255]);
+            } else {
+                let luma = (0.299 * e[0] as f32 + 0.587 * e[1] as f32 + 0.114 * e[2] as f32) as u8;
+                let faded = 255 - (255 - luma) / 4;
+                diff.set_pixel(x, y, [faded, faded, faded, 255]);
+            }
+        }
+    }
+
+    ImageDiff {
+        differing_pixels,
+        max_difference,
+        size_mismatch: expected.width() != actual.width() || expected.height() != actual.height(),
+        diff,
+    }
+}
+
+// Renders components offscreen and compares them with reference PNGs stored
+// as `<reference_dir>/<name>.png`. Failures leave `<name>.actual.png`,
+// `<name>.expected.png` and `<name>.diff.png` in the output directory.
+pub struct Golden {
+    reference_dir: PathBuf,
+    output_dir: PathBuf,
+    snapshot: Snapshot,
+    tolerance: u8,
+    max_differing_pixels: usize,
+}
+
+impl Golden {
+    pub fn new<P: Into<PathBuf>>(reference_dir: P, snapshot: Snapshot) -> Self {
+        let reference_dir = reference_dir.into();
+        Self {
+            output_dir: reference_dir.join("failures"),
+            reference_dir,
+            snapshot,
+            tolerance: 0,
+            max_differing_pixels: 0,
+        }
+    }
+
+    pub fn with_output_dir<P: Into<PathBuf>>(mut self, output_dir: P) -> Self {
+        self.output_dir = output_dir.into();
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_differing_pixels(mut self, count: usize) -> Self {
+        self.max_differing_pixels = count;
+        self
+    }
+
+    pub fn reference_path(&self, name: &str) -> PathBuf {
+        self.reference_dir.join(format!("{}.png", name))
+    }
+
+    fn output_path(&self, name: &str, kind: &str) -> PathBuf {
+        self.output_dir.join(format!("{}.{}.png", name, kind))
+    }
+
+    pub fn check(
+        &self,
+        name: &str,
+        component: &mut dyn Component,
+    ) -> Result<GoldenOutcome, Box<dyn std::error::Error>> {
+        let actual = self.snapshot.render(component);
+        let reference = self.reference_path(name);
+
+        if env::var(UPDATE_GOLDENS_ENV).map(|v| v == "1").unwrap_or(false) {
+            actual.save_png(&reference)?;
+            return Ok(GoldenOutcome::Updated(reference));
+        }
+
+        let actual_path = self.output_path(name, "actual");
+        if !reference.exists() {
+            actual.save_png(&actual_path)?;
+            return Ok(GoldenOutcome::Missing { reference, actual: actual_path });
+        }
+
+        let expected = Framebuffer::load_png(&reference)?;
+        let diff = compare_images(&expected, &actual, self.tolerance);
+        if diff.is_match(self.max_differing_pixels) {
+            // Drop artifacts left behind by an earlier failing run.
+            for kind in ["actual", "expected", "diff"] {
+                let _ = fs::remove_file(self.output_path(name, kind));
+            }
+            return Ok(GoldenOutcome::Matched(diff));
+        }
+
+        let expected_path = self.output_path(name, "expected");
+        let diff_path = self.output_path(name, "diff");
+        actual.save_png(&actual_path)?;
+        expected.save_png(&expected_path)?;
+        diff.diff.save_png(&diff_path)?;
+
+        Ok(GoldenOutcome::Mismatch {
+            diff,
+            actual: actual_path,
+            expected: expected_path,
+            diff_image: diff_path,
+        })
+    }
+
+    pub fn assert_matches(&self, name: &str, component: &mut dyn Component) {
+        match self.check(name, component) {
+            Ok(GoldenOutcome::Matched(_)) | Ok(GoldenOutcome::Updated(_)) => {}
+            Ok(GoldenOutcome::Missing { reference, actual }) => panic!(
+                "golden `{}` has no reference image at {}; actual output written to {} (set {}=1 to record it)",
+                name,
+                reference.display(),
+                actual.display(),
+                UPDATE_GOLDENS_ENV,
+            ),
+            Ok(GoldenOutcome::Mismatch { diff, actual, expected, diff_image }) => panic!(
+                "golden `{}` differs in {} pixels (max channel difference {}, tolerance {}{}); see {}, {} and {}",
+                name,
+                diff.differing_pixels,
+                diff.max_difference,
+                self.tolerance,
+                if diff.size_mismatch { ", image sizes differ" } else { "" },
+                actual.display(),
+                expected.display(),
+                diff_image.display(),
+            ),
+            Err(error) => panic!("golden `{}` could not be checked: {}", name, error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::View;
+    use crate::geometry::Size;
+    use crate::style::{Color, Style};
+
+    fn filled(width: u32, height: u32, rgba: [u8; 4]) -> Framebuffer {
+        let mut framebuffer = Framebuffer::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                framebuffer.set_pixel(x, y, rgba);
+            }
+        }
+        framebuffer
+    }
+
+    #[test]
+    fn differences_within_the_tolerance_match() {
+        let expected = filled(2, 2, [100, 100, 100, 255]);
+        let mut actual = expected.clone();
+        actual.set_pixel(1, 0, [100, 103, 100, 255]);
+
+        let diff = compare_images(&expected, &actual, 2);
+        assert_eq!((diff.differing_pixels, diff.max_difference), (1, 3));
+        assert!(!diff.is_match(0) && diff.is_match(1));
+        assert_eq!(diff.diff.pixel(1, 0), Some([255, 0, 0, 255]));
+        assert_ne!(diff.diff.pixel(0, 0), Some([255, 0, 0, 255]));
+
+        let diff = compare_images(&expected, &actual, 3);
+        assert_eq!(diff.differing_pixels, 0);
+        assert!(diff.is_match(0));
+    }
+
+    #[test]
+    fn images_of_different_sizes_never_match() {
+        let expected = filled(2, 2, [0, 0, 0, 255]);
+        let actual = filled(3, 2, [0, 0, 0, 255]);
+        let diff = compare_images(&expected, &actual, 0);
+        assert!(diff.size_mismatch);
+        assert_eq!((diff.differing_pixels, diff.max_difference), (2, u8::MAX));
+        assert_eq!((diff.diff.width(), diff.diff.height()), (3, 2));
+        assert!(!diff.is_match(usize::MAX));
+    }
+
+    // The only test that reads or sets `UPDATE_GOLDENS_ENV`, so it can change
+    // it without racing the others.
+    #[test]
+    fn check_records_missing_and_mismatched_references() {
+        let dir = env::temp_dir().join(format!("rustui-golden-{}", std::process::id()));
+        let golden = Golden::new(&dir, Snapshot::new(Size::new(4.0, 4.0)));
+        let square = |color| View::new().with_style(Style::default().set_background(color));
+
+        env::remove_var(UPDATE_GOLDENS_ENV);
+        let outcome = golden.check("square", &mut square(Color::RED)).unwrap();
+        assert!(matches!(&outcome, GoldenOutcome::Missing { actual, .. } if actual.exists()));
+        assert!(!golden.reference_path("square").exists());
+
+        env::set_var(UPDATE_GOLDENS_ENV, "1");
+        let outcome = golden.check("square", &mut square(Color::RED));
+        env::remove_var(UPDATE_GOLDENS_ENV);
+        assert!(matches!(outcome.unwrap(), GoldenOutcome::Updated(path) if path.exists()));
+
+        assert!(matches!(golden.check("square", &mut square(Color::RED)).unwrap(), GoldenOutcome::Matched(_)));
+        let outcome = golden.check("square", &mut square(Color::BLACK)).unwrap();
+        match outcome {
+            GoldenOutcome::Mismatch { diff, actual, expected, diff_image } => {
+                assert_eq!(diff.differing_pixels, 16);
+                assert!(actual.exists() && expected.exists() && diff_image.exists());
+            }
+            other => panic!("expected a mismatch, got {:?}", other),
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,3 @@
+mod golden;
+
+pub use golden::{compare_images, Golden, GoldenOutcome, ImageDiff, UPDATE_GOLDENS_ENV};
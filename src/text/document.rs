@@ -0,0 +1,148 @@
+// A location in a multi-line document: `column` is a byte offset into the
+// line, which never includes its terminating newline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl TextPosition {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+// One replacement, recorded so it can be reverted and reapplied.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Edit {
+    pub start: TextPosition,
+    pub removed: String,
+    pub inserted: String,
+}
+
+impl Edit {
+    pub fn inserted_end(&self) -> TextPosition {
+        end_of(self.start, &self.inserted)
+    }
+
+    pub fn removed_end(&self) -> TextPosition {
+        end_of(self.start, &self.removed)
+    }
+}
+
+// Text stored as separate lines so edits and lookups near the caret do not
+// touch the rest of a large document.
+#[derive(Clone, Debug)]
+pub struct Document {
+    lines: Vec<String>,
+}
+
+impl Document {
+    pub(crate) fn new(text: &str) -> Self {
+        Self { lines: normalize(text).split('\n').map(str::to_string).collect() }
+    }
+
+    // The whole text, joined with newlines.
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn line(&self, index: usize) -> &str {
+        &self.lines[index]
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn end(&self) -> TextPosition {
+        let line = self.lines.len() - 1;
+        TextPosition::new(line, self.lines[line].len())
+    }
+
+    pub fn slice(&self, start: TextPosition, end: TextPosition) -> String {
+        if start.line == end.line {
+            return self.lines[start.line][start.column..end.column].to_string();
+        }
+        let mut text = self.lines[start.line][start.column..].to_string();
+        for line in &self.lines[start.line + 1..end.line] {
+            text.push('\n');
+            text.push_str(line);
+        }
+        text.push('\n');
+        text.push_str(&self.lines[end.line][..end.column]);
+        text
+    }
+
+    // Replaces the text between `start` and `end` with `text` and returns the
+    // edit that did it.
+    pub(crate) fn replace(&mut self, start: TextPosition, end: TextPosition, text: &str) -> Edit {
+        let removed = self.slice(start, end);
+        let inserted = normalize(text);
+        let joined = format!(
+            "{}{}{}",
+            &self.lines[start.line][..start.column],
+            inserted,
+            &self.lines[end.line][end.column..],
+        );
+        self.lines.splice(start.line..=end.line, joined.split('\n').map(str::to_string));
+        Edit { start, removed, inserted }
+    }
+
+    pub(crate) fn revert(&mut self, edit: &Edit) {
+        self.replace(edit.start, edit.inserted_end(), &edit.removed);
+    }
+
+    pub(crate) fn reapply(&mut self, edit: &Edit) {
+        self.replace(edit.start, edit.removed_end(), &edit.inserted);
+    }
+}
+
+// Where `text` ends when inserted at `start`.
+pub(crate) fn end_of(start: TextPosition, text: &str) -> TextPosition {
+    match text.rfind('\n') {
+        Some(index) => TextPosition::new(start.line + text.matches('\n').count(), text.len() - index - 1),
+        None => TextPosition::new(start.line, start.column + text.len()),
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_endings_are_normalized() {
+        let document = Document::new("one\r\ntwo\rthree");
+        assert_eq!(document.lines(), ["one", "two", "three"]);
+        assert_eq!(document.end(), TextPosition::new(2, 5));
+    }
+
+    #[test]
+    fn replace_across_lines_and_revert() {
+        let mut document = Document::new("first line\nsecond line\nthird");
+        let edit = document.replace(TextPosition::new(0, 6), TextPosition::new(1, 7), "row\nnew ");
+        assert_eq!(document.text(), "first row\nnew line\nthird");
+        assert_eq!(edit.removed, "line\nsecond ");
+        assert_eq!(edit.inserted_end(), TextPosition::new(1, 4));
+
+        document.revert(&edit);
+        assert_eq!(document.text(), "first line\nsecond line\nthird");
+        document.reapply(&edit);
+        assert_eq!(document.text(), "first row\nnew line\nthird");
+    }
+
+    #[test]
+    fn slice_spans_lines() {
+        let document = Document::new("ab\ncd\nef");
+        assert_eq!(document.slice(TextPosition::new(0, 1), TextPosition::new(2, 1)), "b\ncd\ne");
+        assert_eq!(document.slice(TextPosition::new(1, 0), TextPosition::new(1, 2)), "cd");
+    }
+}
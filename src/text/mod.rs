@@ -0,0 +1,10 @@
+mod document;
+mod paragraph;
+mod segment;
+mod wrap;
+
+pub use document::{Document, TextPosition};
+pub(crate) use document::*;
+pub(crate) use paragraph::*;
+pub(crate) use segment::*;
+pub(crate) use wrap::*;
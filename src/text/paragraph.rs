@@ -0,0 +1,487 @@
+use std::ops::Range;
+use crate::font::{self, PositionedGlyph, ShapedLine};
+use crate::geometry::Size;
+use crate::style::{LayoutDirection, Style, TextAlign, TextOverflow};
+use super::{grapheme_boundaries, wrap_with};
+
+const ELLIPSIS: &str = "\u{2026}";
+
+// A run of text with its own style within a paragraph.
+pub(crate) struct StyledRun<'a> {
+    pub text: &'a str,
+    pub style: Style,
+}
+
+// The part of one run placed on a line.
+pub(crate) struct LineFragment {
+    // Index of the run the text came from.
+    pub run: usize,
+    pub text: String,
+    pub shaped: ShapedLine,
+    // Left edge from the start of the line, in visual order.
+    pub x: f32,
+    pub width: f32,
+}
+
+// One line of a laid-out paragraph.
+pub(crate) struct ParagraphLine {
+    pub fragments: Vec<LineFragment>,
+    // Whitespace at the end of a wrapped line hangs past the edge and is left
+    // out of its width.
+    pub width: f32,
+    pub ascent: f32,
+    pub descent: f32,
+    // Only lines that wrapped onto a following line are justified.
+    justify: bool,
+    // Left edges of the space glyphs justification widens, in visual order.
+    spaces: Vec<f32>,
+}
+
+impl ParagraphLine {
+    // `pieces` are (run, text) in logical order; neighbours from the same run
+    // are shaped together.
+    fn new(pieces: Vec<(usize, String)>, runs: &[StyledRun], direction: LayoutDirection, wrapped: bool) -> Self {
+        let mut merged: Vec<(usize, String)> = Vec::new();
+        for (run, text) in pieces {
+            match merged.last_mut() {
+                Some((last, existing)) if *last == run => existing.push_str(&text),
+                _ => merged.push((run, text)),
+            }
+        }
+        // An empty line keeps one empty fragment for its metrics.
+        if merged.len() > 1 {
+            merged.retain(|(_, text)| !text.is_empty());
+        }
+
+        let mut line = Self {
+            fragments: Vec::new(),
+            width: 0.0,
+            ascent: 0.0,
+            descent: 0.0,
+            justify: wrapped,
+            spaces: Vec::new(),
+        };
+        for (run, text) in merged {
+            let style = &runs[run].style;
+            let metrics = font::line_metrics(style);
+            line.ascent = line.ascent.max(metrics.ascent);
+            line.descent = line.descent.max(metrics.line_height - metrics.ascent);
+            let shaped = font::shape_line(&text, style);
+            let width = shaped.width;
+            line.fragments.push(LineFragment { run, x: line.width, width, shaped, text });
+            line.width += width;
+        }
+        // Runs follow each other from the right edge in RTL.
+        if direction == LayoutDirection::Rtl {
+            for fragment in &mut line.fragments {
+                fragment.x = line.width - fragment.x - fragment.width;
+            }
+        }
+        // Spaces are found by position rather than byte offset so the extra
+        // space goes where it shows, whichever direction the text runs.
+        for fragment in &line.fragments {
+            line.spaces.extend(
+                fragment
+                    .shaped
+                    .glyphs
+                    .iter()
+                    .filter(|glyph| fragment.text[glyph.cluster..].starts_with(char::is_whitespace))
+                    .map(|glyph| fragment.x + glyph.x),
+            );
+        }
+        line.spaces.sort_by(f32::total_cmp);
+        line
+    }
+
+    pub fn height(&self) -> f32 {
+        self.ascent + self.descent
+    }
+
+    // The line's x offset within `available` and the space added after each
+    // inner space when justifying.
+    pub fn alignment(&self, style: &Style, available: f32) -> (f32, f32) {
+        let free = available - self.width;
+        match style.text_align {
+            TextAlign::Justify if self.justify && !self.spaces.is_empty() && free > 0.0 && free.is_finite() => {
+                (0.0, free / self.spaces.len() as f32)
+            }
+            _ => (align_offset(style, free), 0.0),
+        }
+    }
+
+    // Where `glyph` of `fragment` starts on the line once `word_spacing` widens
+    // each space.
+    pub fn glyph_x(&self, fragment: &LineFragment, glyph: &PositionedGlyph, word_spacing: f32) -> f32 {
+        let x = fragment.x + glyph.x;
+        x + self.spaces_before(x) as f32 * word_spacing
+    }
+
+    // Where the text of `fragment` between two byte offsets starts on the
+    // line once `word_spacing` widens each space.
+    pub fn range_x(&self, fragment: &LineFragment, start: usize, end: usize, word_spacing: f32) -> f32 {
+        let x = fragment.x + fragment.shaped.x_for_offset(start).min(fragment.shaped.x_for_offset(end));
+        x + self.spaces_before(x) as f32 * word_spacing
+    }
+
+    // The number of spaces left of `x`.
+    fn spaces_before(&self, x: f32) -> usize {
+        self.spaces.partition_point(|&space| space < x)
+    }
+}
+
+// How far a line is shifted right when `free` space is left over. `Start`
+// and `End` follow the style's direction, and lines that are not justified
+// sit at the start.
+pub(crate) fn align_offset(style: &Style, free: f32) -> f32 {
+    match style.text_align.resolve(style.direction) {
+        TextAlign::Center => free / 2.0,
+        TextAlign::Right => free,
+        TextAlign::Justify if style.direction == LayoutDirection::Rtl => free,
+        _ => 0.0,
+    }
+}
+
+pub(crate) struct Paragraph {
+    pub lines: Vec<ParagraphLine>,
+}
+
+impl Paragraph {
+    pub fn size(&self) -> Size {
+        let width = self.lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        Size::new(width, self.lines.iter().map(ParagraphLine::height).sum())
+    }
+}
+
+// Breaks `text` into lines for `width` following the style's wrapping,
+// `max_lines` and overflow settings. An unbounded width only breaks at `\n`.
+pub(crate) fn layout_paragraph(text: &str, style: &Style, width: f32) -> Paragraph {
+    layout_runs(&[StyledRun { text, style: style.clone() }], style, width)
+}
+
+// Lays out `runs` as one paragraph: each run is shaped with its own style
+// while wrapping, alignment, `max_lines` and overflow come from `style`.
+pub(crate) fn layout_runs(runs: &[StyledRun], style: &Style, width: f32) -> Paragraph {
+    let fallback;
+    let runs = if runs.is_empty() {
+        fallback = [StyledRun { text: "", style: style.clone() }];
+        &fallback[..]
+    } else {
+        runs
+    };
+    let text: String = runs.iter().map(|run| run.text).collect();
+    let mut ranges = Vec::with_capacity(runs.len());
+    for run in runs {
+        let start = ranges.last().map_or(0, |range: &Range<usize>| range.end);
+        ranges.push(start..start + run.text.len());
+    }
+    let max_lines = style.max_lines.unwrap_or(usize::MAX).max(1);
+
+    // The shaped runs of each hard line, and the rows as (hard line, range in
+    // `text`, whether it wrapped onto the next row).
+    let mut shaped_lines = Vec::new();
+    let mut rows: Vec<(usize, Range<usize>, bool)> = Vec::new();
+    let mut dropped = false;
+    'lines: for line in hard_lines(&text) {
+        let segments = shape_segments(&text, &line, runs, &ranges);
+        let starts = wrap_with(&text[line.clone()], width, style.text_wrap, |start, end| {
+            advance_between(&segments, line.start + start, line.start + end)
+        });
+        shaped_lines.push(segments);
+        for (index, &start) in starts.iter().enumerate() {
+            if rows.len() == max_lines {
+                dropped = true;
+                break 'lines;
+            }
+            let end = starts.get(index + 1).map_or(line.end, |&end| line.start + end);
+            rows.push((shaped_lines.len() - 1, line.start + start..end, index + 1 < starts.len()));
+        }
+    }
+
+    let count = rows.len();
+    let lines = rows
+        .into_iter()
+        .enumerate()
+        .map(|(index, (line, row, wrapped))| {
+            let segments = &shaped_lines[line];
+            let advance = |start: usize, end: usize| advance_between(segments, start, end);
+            // Cutting later lines always ellipsizes the end of the last one.
+            let forced = dropped && index + 1 == count;
+            let overflow = if forced { TextOverflow::EllipsisEnd } else { style.text_overflow };
+            let cut = match style.text_overflow {
+                TextOverflow::Clip => None,
+                _ => {
+                    let ellipsis = ranges
+                        .iter()
+                        .zip(runs)
+                        .filter(|(range, _)| range.start <= row.end && row.start <= range.end)
+                        .map(|(_, run)| font::text_width(ELLIPSIS, &run.style))
+                        .fold(0.0, f32::max);
+                    ellipsize(&text, row.clone(), advance, ellipsis, width, overflow, forced)
+                }
+            };
+            match cut {
+                Some((head, tail)) => {
+                    let at = if head.is_empty() { tail.start } else { head.end - 1 };
+                    let mut pieces = split_runs(&text, head, &ranges);
+                    pieces.push((run_at(&ranges, at), ELLIPSIS.to_string()));
+                    pieces.extend(split_runs(&text, tail, &ranges));
+                    ParagraphLine::new(pieces, runs, style.direction, false)
+                }
+                None => {
+                    let end = if wrapped { row.start + text[row.clone()].trim_end().len() } else { row.end };
+                    ParagraphLine::new(split_runs(&text, row.start..end, &ranges), runs, style.direction, wrapped)
+                }
+            }
+        })
+        .collect();
+
+    Paragraph { lines }
+}
+
+// The ranges of `text` between newlines, like `str::lines` except that
+// empty text is one empty line.
+fn hard_lines(text: &str) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    for (offset, _) in text.match_indices('\n') {
+        let end = if text[start..offset].ends_with('\r') { offset - 1 } else { offset };
+        lines.push(start..end);
+        start = offset + 1;
+    }
+    if start < text.len() || lines.is_empty() {
+        lines.push(start..text.len());
+    }
+    lines
+}
+
+// The part of a line from one run, shaped with the run's style.
+struct Segment {
+    range: Range<usize>,
+    shaped: ShapedLine,
+}
+
+fn shape_segments(text: &str, line: &Range<usize>, runs: &[StyledRun], ranges: &[Range<usize>]) -> Vec<Segment> {
+    ranges
+        .iter()
+        .zip(runs)
+        .filter_map(|(range, run)| {
+            let range = range.start.max(line.start)..range.end.min(line.end);
+            (range.start < range.end).then(|| Segment {
+                shaped: font::shape_line(&text[range.clone()], &run.style),
+                range,
+            })
+        })
+        .collect()
+}
+
+fn advance_between(segments: &[Segment], start: usize, end: usize) -> f32 {
+    segments
+        .iter()
+        .filter(|segment| segment.range.start < end && start < segment.range.end)
+        .map(|segment| {
+            let offset = segment.range.start;
+            segment.shaped.advance_between(start.max(offset) - offset, end.min(segment.range.end) - offset)
+        })
+        .sum()
+}
+
+// The index of the run holding the character at `offset`.
+fn run_at(ranges: &[Range<usize>], offset: usize) -> usize {
+    ranges.iter().position(|range| offset < range.end).unwrap_or(ranges.len() - 1)
+}
+
+// `range` of `text` as (run, text) pieces. An empty range still gives one
+// empty piece so its line gets the run's metrics.
+fn split_runs(text: &str, range: Range<usize>, ranges: &[Range<usize>]) -> Vec<(usize, String)> {
+    if range.is_empty() {
+        return vec![(run_at(ranges, range.start), String::new())];
+    }
+    ranges
+        .iter()
+        .enumerate()
+        .filter_map(|(run, run_range)| {
+            let start = run_range.start.max(range.start);
+            let end = run_range.end.min(range.end);
+            (start < end).then(|| (run, text[start..end].to_string()))
+        })
+        .collect()
+}
+
+// Shortens the `row` of `text` to fit `width`, returning the ranges kept
+// before and after the ellipsis at `overflow`'s position, or `None` when the
+// row fits. `force` adds the ellipsis even when it does.
+fn ellipsize<F>(
+    text: &str,
+    row: Range<usize>,
+    advance_between: F,
+    ellipsis: f32,
+    width: f32,
+    overflow: TextOverflow,
+    force: bool,
+) -> Option<(Range<usize>, Range<usize>)>
+where
+    F: Fn(usize, usize) -> f32,
+{
+    let line = &text[row.clone()];
+    let x = |offset: usize| advance_between(row.start, row.start + offset);
+    let full = x(line.trim_end().len());
+    if !force && (full <= width || !width.is_finite()) {
+        return None;
+    }
+    let budget = width - ellipsis;
+    let boundaries = grapheme_boundaries(line);
+    // Absolute range of `line[start..end]` without surrounding whitespace.
+    let trimmed = |start: usize, end: usize| {
+        let piece = &line[start..end];
+        let start = start + piece.len() - piece.trim_start().len();
+        row.start + start..row.start + start + piece.trim().len()
+    };
+    let empty = row.end..row.end;
+
+    match overflow {
+        TextOverflow::EllipsisStart => {
+            let start = boundaries.iter().copied().find(|&b| full - x(b) <= budget).unwrap_or(line.len());
+            Some((row.start..row.start, trimmed(start, line.len())))
+        }
+        TextOverflow::EllipsisMiddle => {
+            let head = boundaries.iter().copied().rev().find(|&b| x(b) <= budget / 2.0).unwrap_or(0);
+            let tail = boundaries
+                .iter()
+                .copied()
+                .find(|&b| b >= head && full - x(b) <= budget - x(head))
+                .unwrap_or(line.len());
+            Some((row.start..row.start + line[..head].trim_end().len(), trimmed(tail, line.len())))
+        }
+        _ => {
+            let end = boundaries.iter().copied().rev().find(|&b| x(b) <= budget).unwrap_or(0);
+            Some((row.start..row.start + line[..end].trim_end().len(), empty))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::TextWrap;
+
+    fn style() -> Style {
+        Style::default().set_font_size(16.0)
+    }
+
+    fn lines(paragraph: &Paragraph) -> Vec<String> {
+        paragraph
+            .lines
+            .iter()
+            .map(|line| line.fragments.iter().map(|fragment| fragment.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn hard_breaks_split_lines_without_wrapping() {
+        assert_eq!(lines(&layout_paragraph("a\r\nb\n", &style(), f32::INFINITY)), ["a", "b"]);
+        assert_eq!(lines(&layout_paragraph("", &style(), 100.0)), [""]);
+        assert_eq!(lines(&layout_paragraph("one two three", &style(), 10.0)), ["one two three"]);
+    }
+
+    #[test]
+    fn word_wrap_breaks_after_spaces() {
+        let style = style().set_text_wrap(TextWrap::Word);
+        let width = font::text_width("one two", &style) + 1.0;
+        let paragraph = layout_paragraph("one two three", &style, width);
+        assert_eq!(lines(&paragraph), ["one two", "three"]);
+        assert!(paragraph.lines.iter().all(|line| line.width <= width));
+        assert_eq!(paragraph.size().height, paragraph.lines[0].height() * 2.0);
+    }
+
+    #[test]
+    fn long_words_break_between_graphemes() {
+        let style = style().set_text_wrap(TextWrap::Word);
+        let width = font::text_width("abc", &style) + 0.5;
+        assert_eq!(lines(&layout_paragraph("abcdefg", &style, width)), ["abc", "def", "g"]);
+    }
+
+    #[test]
+    fn ellipsis_replaces_what_does_not_fit() {
+        let text = "Hello wonderful world";
+        let base = style();
+        let width = font::text_width("Hello wonder", &base);
+        let cut = |overflow| {
+            let paragraph = layout_paragraph(text, &base.clone().set_text_overflow(overflow), width);
+            assert!(overflow == TextOverflow::Clip || paragraph.lines[0].width <= width);
+            lines(&paragraph).remove(0)
+        };
+        assert_eq!(cut(TextOverflow::Clip), text);
+        assert!(cut(TextOverflow::EllipsisEnd).starts_with("Hello won"));
+        assert!(cut(TextOverflow::EllipsisEnd).ends_with(ELLIPSIS));
+        assert!(cut(TextOverflow::EllipsisStart).starts_with(ELLIPSIS));
+        assert!(cut(TextOverflow::EllipsisStart).ends_with("ful world"));
+        let middle = cut(TextOverflow::EllipsisMiddle);
+        assert!(middle.starts_with("Hello") && middle.contains(ELLIPSIS) && middle.ends_with("world"));
+    }
+
+    #[test]
+    fn max_lines_drops_lines_and_ellipsizes_the_last() {
+        let style = style().set_text_wrap(TextWrap::Word).set_max_lines(2).set_text_overflow(TextOverflow::EllipsisEnd);
+        let width = font::text_width("one two", &style) + 1.0;
+        let paragraph = layout_paragraph("one two three four five six", &style, width);
+        let text = lines(&paragraph);
+        assert_eq!(text.len(), 2);
+        assert_eq!(text[0], "one two");
+        assert!(text[1].ends_with(ELLIPSIS));
+    }
+
+    #[test]
+    fn runs_keep_their_styles_on_a_line() {
+        let big = style().set_font_size(32.0);
+        let runs = [StyledRun { text: "small ", style: style() }, StyledRun { text: "big", style: big.clone() }];
+        let paragraph = layout_runs(&runs, &style(), f32::INFINITY);
+        let line = &paragraph.lines[0];
+        assert_eq!(line.fragments.iter().map(|fragment| fragment.run).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(line.ascent, font::line_metrics(&big).ascent);
+        assert_eq!(line.fragments[1].x, line.fragments[0].width);
+    }
+
+    #[test]
+    fn rtl_runs_start_at_the_right() {
+        let rtl = style().set_direction(LayoutDirection::Rtl);
+        let runs = [StyledRun { text: "שלום ", style: rtl.clone() }, StyledRun { text: "עולם", style: rtl.clone() }];
+        let line = &layout_runs(&runs, &rtl, f32::INFINITY).lines[0];
+        assert!(line.fragments[0].x > line.fragments[1].x);
+        assert_eq!(line.fragments[1].x, 0.0);
+    }
+
+    #[test]
+    fn justify_widens_spaces_on_wrapped_lines_only() {
+        let style = style().set_text_wrap(TextWrap::Word).set_text_align(TextAlign::Justify);
+        let width = font::text_width("aa bb cc", &style) + 20.0;
+        let paragraph = layout_paragraph("aa bb cc dddddd", &style, width);
+        let (first, last) = (&paragraph.lines[0], &paragraph.lines[1]);
+        let (offset, spacing) = first.alignment(&style, width);
+        assert_eq!(offset, 0.0);
+        assert!((spacing * 2.0 - (width - first.width)).abs() < 0.01);
+        assert_eq!(last.alignment(&style, width), (0.0, 0.0));
+
+        // The last word ends flush with the right edge.
+        let fragment = &first.fragments[0];
+        let end = first.range_x(fragment, 6, 8, spacing) + fragment.shaped.advance_between(6, 8);
+        assert!((end - width).abs() < 0.01);
+    }
+
+    #[test]
+    fn justified_rtl_lines_span_the_width() {
+        let style = style()
+            .set_text_wrap(TextWrap::Word)
+            .set_text_align(TextAlign::Justify)
+            .set_direction(LayoutDirection::Rtl);
+        // Each Hebrew letter is two bytes, so the first word is 0..4.
+        let width = font::text_width("אא בב גג", &style) + 20.0;
+        let paragraph = layout_paragraph("אא בב גג דדדדדד", &style, width);
+        let first = &paragraph.lines[0];
+        let (offset, spacing) = first.alignment(&style, width);
+        assert_eq!(offset, 0.0);
+        let fragment = &first.fragments[0];
+        let start = first.range_x(fragment, 0, 4, spacing) + fragment.shaped.advance_between(0, 4);
+        assert!((start - width).abs() < 0.01, "the first word ends at {start}, not at the right edge {width}");
+        assert!(first.range_x(fragment, 10, 14, spacing).abs() < 0.01);
+    }
+}
@@ -0,0 +1,90 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+// Extended grapheme cluster and word boundaries for editing, following
+// UAX #29. Offsets are byte offsets into the text and always fall on char
+// boundaries.
+
+// Every grapheme boundary in `text`, including 0 and `text.len()`.
+pub(crate) fn grapheme_boundaries(text: &str) -> Vec<usize> {
+    let mut boundaries: Vec<usize> = text.grapheme_indices(true).map(|(offset, _)| offset).collect();
+    if boundaries.is_empty() {
+        boundaries.push(0);
+    } else {
+        boundaries.push(text.len());
+    }
+    boundaries
+}
+
+pub(crate) fn grapheme_count(text: &str) -> usize {
+    grapheme_boundaries(text).len().saturating_sub(1)
+}
+
+pub(crate) fn previous_grapheme(text: &str, offset: usize) -> usize {
+    grapheme_boundaries(text).into_iter().rev().find(|&boundary| boundary < offset).unwrap_or(0)
+}
+
+pub(crate) fn next_grapheme(text: &str, offset: usize) -> usize {
+    grapheme_boundaries(text).into_iter().find(|&boundary| boundary > offset).unwrap_or(text.len())
+}
+
+// The byte offset just past the first `count` graphemes.
+pub(crate) fn grapheme_prefix(text: &str, count: usize) -> usize {
+    grapheme_boundaries(text).get(count).copied().unwrap_or(text.len())
+}
+
+// The start of the word before `offset`, skipping any separators first.
+pub(crate) fn previous_word(text: &str, offset: usize) -> usize {
+    text.unicode_word_indices()
+        .take_while(|&(start, _)| start < offset)
+        .last()
+        .map_or(0, |(start, _)| start)
+}
+
+// The end of the word after `offset`, skipping any separators first.
+pub(crate) fn next_word(text: &str, offset: usize) -> usize {
+    text.unicode_word_indices()
+        .map(|(start, word)| start + word.len())
+        .find(|&end| end > offset)
+        .unwrap_or(text.len())
+}
+
+// `text` split at every UAX #29 word boundary, so words and the runs of
+// spaces between them come out as separate ranges.
+pub(crate) fn word_bound_ranges(text: &str) -> Vec<(usize, usize)> {
+    text.split_word_bound_indices().map(|(start, piece)| (start, start + piece.len())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graphemes_keep_clusters_together() {
+        // "e" with a combining acute, a ZWJ family and a flag.
+        let text = "e\u{301}👨‍👩‍👧🇯🇵!";
+        let boundaries = grapheme_boundaries(text);
+        assert_eq!(grapheme_count(text), 4);
+        assert_eq!(boundaries[1], 3);
+        assert_eq!(&text[boundaries[1]..boundaries[2]], "👨‍👩‍👧");
+        assert_eq!(next_grapheme(text, 0), 3);
+        assert_eq!(previous_grapheme(text, text.len()), text.len() - 1);
+        assert_eq!(grapheme_prefix(text, 2), boundaries[2]);
+        assert_eq!(grapheme_boundaries(""), [0]);
+    }
+
+    #[test]
+    fn word_motion_skips_separators() {
+        let text = "hello,  wide world";
+        assert_eq!(next_word(text, 0), 5);
+        assert_eq!(next_word(text, 5), 12);
+        assert_eq!(previous_word(text, text.len()), 13);
+        assert_eq!(previous_word(text, 13), 8);
+        assert_eq!(previous_word(text, 3), 0);
+    }
+
+    #[test]
+    fn word_bound_ranges_split_words_from_spaces() {
+        let ranges = word_bound_ranges("to be");
+        assert_eq!(ranges, [(0, 2), (2, 3), (3, 5)]);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::font;
+use crate::style::{Style, TextWrap};
+use super::grapheme_boundaries;
+
+// The byte offsets at which `line` starts each visual row when wrapped to
+// `width`, beginning with 0. Word wrapping breaks after whitespace where
+// possible and between graphemes otherwise; character wrapping breaks
+// between any graphemes. Trailing whitespace may hang past `width`, and
+// every row keeps at least one grapheme. An unbounded width never wraps.
+pub(crate) fn wrap_line(line: &str, width: f32, style: &Style, wrap: TextWrap) -> Vec<usize> {
+    if wrap == TextWrap::NoWrap || !width.is_finite() || width <= 0.0 {
+        return vec![0];
+    }
+    let shaped = font::shape_line(line, style);
+    wrap_with(line, width, wrap, |start, end| shaped.advance_between(start, end))
+}
+
+// `wrap_line` for text whose advances come from elsewhere, such as a line
+// made of differently styled runs.
+pub(crate) fn wrap_with<F>(line: &str, width: f32, wrap: TextWrap, advance_between: F) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> f32,
+{
+    let mut rows = vec![0];
+    if wrap == TextWrap::NoWrap || !width.is_finite() || width <= 0.0 {
+        return rows;
+    }
+
+    let mut row_start = 0;
+    let mut row_width = 0.0;
+    let mut last_break = None;
+    for pair in grapheme_boundaries(line).windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let grapheme = &line[start..end];
+        let advance = advance_between(start, end);
+        let is_space = grapheme.chars().all(char::is_whitespace);
+
+        if row_width + advance > width && start > row_start && !is_space {
+            let at = last_break.filter(|&offset| offset > row_start).unwrap_or(start);
+            rows.push(at);
+            row_start = at;
+            row_width = advance_between(row_start, start);
+            last_break = None;
+        }
+        row_width += advance;
+        if is_space && wrap == TextWrap::Word {
+            last_break = Some(end);
+        }
+    }
+    rows
+}
+
+// The width of the widest piece of `text` that wrapping keeps on one row:
+// a whole line without wrapping, a word with word wrapping and a grapheme
+// with character wrapping.
+pub(crate) fn min_content_width(text: &str, style: &Style) -> f32 {
+    text.lines()
+        .map(|line| {
+            let shaped = font::shape_line(line, style);
+            let pieces: Vec<(usize, usize)> = match style.text_wrap {
+                TextWrap::NoWrap => vec![(0, line.len())],
+                TextWrap::Character => grapheme_boundaries(line).windows(2).map(|pair| (pair[0], pair[1])).collect(),
+                TextWrap::Word => {
+                    let mut words = Vec::new();
+                    let mut start = None;
+                    for (offset, c) in line.char_indices() {
+                        if !c.is_whitespace() {
+                            start.get_or_insert(offset);
+                        } else if let Some(word_start) = start.take() {
+                            words.push((word_start, offset));
+                        }
+                    }
+                    words.extend(start.map(|word_start| (word_start, line.len())));
+                    words
+                }
+            };
+            pieces
+                .into_iter()
+                .map(|(start, end)| shaped.advance_between(start, end))
+                .fold(0.0, f32::max)
+        })
+        .fold(0.0, f32::max)
+}
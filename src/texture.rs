@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use tiny_skia::{IntSize, Pixmap};
+use crate::geometry::Size;
+
+const DEFAULT_CACHE_LIMIT: usize = 64 * 1024 * 1024;
+
+// Decoded pixels ready to draw, stored premultiplied as the renderers
+// composite them.
+pub struct Texture {
+    pixmap: Pixmap,
+    // Unique per texture, so renderers can cache what they derive from it.
+    id: u64,
+}
+
+impl Texture {
+    // Builds a texture from unpremultiplied RGBA8 rows, top to bottom.
+    pub fn from_rgba(width: u32, height: u32, mut pixels: Vec<u8>) -> Result<Self, Box<dyn std::error::Error>> {
+        let size = IntSize::from_wh(width, height).ok_or("texture dimensions must be non-zero")?;
+        if pixels.len() != width as usize * height as usize * 4 {
+            return Err(format!("expected {} bytes of RGBA data for {width}x{height}, got {}", width as usize * height as usize * 4, pixels.len()).into());
+        }
+        for pixel in pixels.chunks_exact_mut(4) {
+            let alpha = pixel[3] as u16;
+            for channel in &mut pixel[..3] {
+                *channel = ((*channel as u16 * alpha + 127) / 255) as u8;
+            }
+        }
+        let pixmap = Pixmap::from_vec(pixels, size).ok_or("invalid texture data")?;
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Ok(Self { pixmap, id: NEXT_ID.fetch_add(1, Ordering::Relaxed) })
+    }
+
+    // Decodes a PNG, JPEG or WebP file's contents.
+    pub fn decode(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let image = image::load_from_memory(bytes)?.into_rgba8();
+        let (width, height) = image.dimensions();
+        Self::from_rgba(width, height, image.into_raw())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.pixmap.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.pixmap.height()
+    }
+
+    // The size in points when drawn at one point per pixel.
+    pub fn size(&self) -> Size {
+        Size::new(self.width() as f32, self.height() as f32)
+    }
+
+    // Memory held by the decoded pixels.
+    pub fn byte_size(&self) -> usize {
+        self.pixmap.data().len()
+    }
+
+    pub(crate) fn pixmap(&self) -> &Pixmap {
+        &self.pixmap
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl fmt::Debug for Texture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Texture")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .finish()
+    }
+}
+
+// Where an image's encoded data comes from.
+#[derive(Clone, Debug)]
+pub enum ImageSource {
+    File(PathBuf),
+    Bytes(Arc<[u8]>),
+}
+
+impl ImageSource {
+    pub fn file<P: Into<PathBuf>>(path: P) -> Self {
+        ImageSource::File(path.into())
+    }
+
+    pub fn bytes<B: Into<Arc<[u8]>>>(bytes: B) -> Self {
+        ImageSource::Bytes(bytes.into())
+    }
+
+    // The encoded data, read from disk for files.
+    pub(crate) fn read(&self) -> Result<Arc<[u8]>, Box<dyn std::error::Error>> {
+        match self {
+            ImageSource::File(path) => {
+                let bytes = std::fs::read(path).map_err(|error| format!("{}: {error}", path.display()))?;
+                Ok(bytes.into())
+            }
+            ImageSource::Bytes(bytes) => Ok(bytes.clone()),
+        }
+    }
+
+    // In-memory data is keyed by its contents, so identical bytes loaded
+    // from different places share one texture.
+    fn key(&self) -> CacheKey {
+        match self {
+            ImageSource::File(path) => CacheKey::File(path.clone()),
+            ImageSource::Bytes(bytes) => CacheKey::Bytes(bytes.clone()),
+        }
+    }
+}
+
+// Keys for in-memory data hash and compare the bytes themselves, so two
+// sources whose hashes collide never share a texture. The entry keeps the
+// bytes alive while it is cached.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum CacheKey {
+    File(PathBuf),
+    Bytes(Arc<[u8]>),
+}
+
+struct Entry {
+    texture: Arc<Texture>,
+    last_used: u64,
+}
+
+// Decoded textures by source, evicted least recently used first once their
+// total size passes the limit. Textures still held elsewhere stay alive
+// after eviction; they are only dropped from the cache.
+struct Cache {
+    entries: HashMap<CacheKey, Entry>,
+    bytes: usize,
+    limit: usize,
+    clock: u64,
+}
+
+impl Cache {
+    fn trim(&mut self, keep: Option<&CacheKey>) {
+        while self.bytes > self.limit {
+            let oldest = self.entries
+                .iter()
+                .filter(|(key, _)| Some(*key) != keep)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            let Some(key) = oldest else { break };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.texture.byte_size();
+            }
+        }
+    }
+}
+
+fn cache() -> MutexGuard<'static, Cache> {
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+    CACHE
+        .get_or_init(|| Mutex::new(Cache { entries: HashMap::new(), bytes: 0, limit: DEFAULT_CACHE_LIMIT, clock: 0 }))
+        .lock()
+        // Nothing panics while the lock is held, so a poisoned lock is still usable.
+        .unwrap_or_else(|error| error.into_inner())
+}
+
+// The decoded texture for `source`, from the cache when it was loaded
+// before. Decoding happens outside the cache lock, so two threads loading
+// the same new source may both decode it.
+pub fn load(source: &ImageSource) -> Result<Arc<Texture>, Box<dyn std::error::Error>> {
+    let key = source.key();
+    {
+        let mut cache = cache();
+        cache.clock += 1;
+        let clock = cache.clock;
+        if let Some(entry) = cache.entries.get_mut(&key) {
+            entry.last_used = clock;
+            return Ok(entry.texture.clone());
+        }
+    }
+
+    let texture = Arc::new(Texture::decode(&source.read()?)?);
+    let mut cache = cache();
+    let clock = cache.clock;
+    cache.bytes += texture.byte_size();
+    if let Some(previous) = cache.entries.insert(key.clone(), Entry { texture: texture.clone(), last_used: clock }) {
+        cache.bytes -= previous.texture.byte_size();
+    }
+    // A texture larger than the limit on its own is still kept until the
+    // next load.
+    cache.trim(Some(&key));
+    Ok(texture)
+}
+
+// Caps the memory the cache holds on to, evicting as needed. Defaults to
+// 64 MiB.
+pub fn set_cache_limit(bytes: usize) {
+    let mut cache = cache();
+    cache.limit = bytes;
+    cache.trim(None);
+}
+
+// The memory currently held by cached textures.
+pub fn cache_usage() -> usize {
+    cache().bytes
+}
+
+// Forgets the cached texture for `source`, e.g. after its file changed.
+pub fn evict(source: &ImageSource) {
+    let mut cache = cache();
+    if let Some(entry) = cache.entries.remove(&source.key()) {
+        cache.bytes -= entry.texture.byte_size();
+    }
+}
+
+pub fn clear_cache() {
+    let mut cache = cache();
+    cache.entries.clear();
+    cache.bytes = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 10x10 PNG filled with `shade`; each decodes to 400 bytes.
+    fn png_bytes(shade: u8) -> Vec<u8> {
+        let mut pixmap = Pixmap::new(10, 10).unwrap();
+        pixmap.fill(tiny_skia::Color::from_rgba8(shade, 0, 0, 255));
+        pixmap.encode_png().unwrap()
+    }
+
+    fn png(shade: u8) -> ImageSource {
+        ImageSource::bytes(png_bytes(shade))
+    }
+
+    // The cache is global, so tests that use it run one at a time and start
+    // from an empty cache at the default limit.
+    fn serial() -> MutexGuard<'static, ()> {
+        static LOCK: Mutex<()> = Mutex::new(());
+        let guard = LOCK.lock().unwrap_or_else(|error| error.into_inner());
+        clear_cache();
+        set_cache_limit(DEFAULT_CACHE_LIMIT);
+        guard
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_texture_first() {
+        let _serial = serial();
+        let (a, b, c, d) = (png(1), png(2), png(3), png(4));
+        set_cache_limit(1200);
+        let first_a = load(&a).unwrap();
+        let first_b = load(&b).unwrap();
+        let first_c = load(&c).unwrap();
+        assert_eq!(cache_usage(), 1200);
+
+        // Using `a` again leaves `b` as the oldest, so `d` pushes it out.
+        load(&a).unwrap();
+        load(&d).unwrap();
+        assert_eq!(cache_usage(), 1200);
+        assert!(Arc::ptr_eq(&load(&a).unwrap(), &first_a), "a was evicted");
+        assert!(!Arc::ptr_eq(&load(&b).unwrap(), &first_b), "b should have been evicted");
+        // Reloading `b` evicted `c`, now the oldest.
+        assert!(!Arc::ptr_eq(&load(&c).unwrap(), &first_c), "c should have been evicted");
+    }
+
+    #[test]
+    fn keeps_an_oversize_texture_until_the_next_load() {
+        let _serial = serial();
+        let (a, b) = (png(1), png(2));
+        set_cache_limit(300);
+        let first_a = load(&a).unwrap();
+        assert_eq!(cache_usage(), 400);
+        assert!(Arc::ptr_eq(&load(&a).unwrap(), &first_a), "a was dropped on the load that cached it");
+
+        load(&b).unwrap();
+        assert_eq!(cache_usage(), 400);
+        assert!(!Arc::ptr_eq(&load(&a).unwrap(), &first_a), "a should have been evicted by the next load");
+
+        set_cache_limit(0);
+        assert_eq!(cache_usage(), 0);
+    }
+
+    #[test]
+    fn usage_drops_on_evict_and_clear() {
+        let _serial = serial();
+        let (a, b) = (png(1), png(2));
+        load(&a).unwrap();
+        load(&b).unwrap();
+        assert_eq!(cache_usage(), 800);
+        evict(&a);
+        assert_eq!(cache_usage(), 400);
+        evict(&a);
+        assert_eq!(cache_usage(), 400);
+        clear_cache();
+        assert_eq!(cache_usage(), 0);
+    }
+
+    #[test]
+    fn equal_bytes_share_one_texture() {
+        let _serial = serial();
+        let bytes = png_bytes(1);
+        let first = load(&ImageSource::bytes(bytes.clone())).unwrap();
+        let second = load(&ImageSource::bytes(bytes)).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(cache_usage(), 400);
+        assert!(!Arc::ptr_eq(&first, &load(&png(2)).unwrap()));
+    }
+}
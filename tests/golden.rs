@@ -0,0 +1,204 @@
+// Golden snapshots of the software renderer. Reference images live next to
+// this file in `golden/`; run with RUSTUI_UPDATE_GOLDENS=1 to rerecord them
+// after an intended rendering change.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use rust_native::testing::Golden;
+use rust_native::{
+    Border, BorderSide, BorderStyle, BoxShadow, Brush, Canvas, Color, ContentFit, Dimension, EdgeInsets,
+    FontWeight, Gradient, GradientStop, Image, Layout, LayoutDirection, LineCap, LineJoin, Path, Point, Rect,
+    RichText, Size, Snapshot, Span, SpanStyle, StrokeStyle, Style, Svg, Text, TextAlign, TextOverflow, TextWrap,
+    Texture, View,
+};
+
+fn golden(width: f32, height: f32) -> Golden {
+    let references = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden");
+    Golden::new(references, Snapshot::new(Size::new(width, height))).with_tolerance(2)
+}
+
+fn sized(width: f32, height: f32) -> Style {
+    Style::default()
+        .set_width(Dimension::Points(width))
+        .set_height(Dimension::Points(height))
+}
+
+fn row() -> View {
+    View::new()
+        .with_layout(Layout::Row)
+        .with_style(Style::default().set_padding(EdgeInsets::all(10.0)).set_gap(10.0))
+}
+
+fn stops(from: Color, to: Color) -> Vec<GradientStop> {
+    vec![GradientStop::new(0.0, from), GradientStop::new(1.0, to)]
+}
+
+const BLUE: Color = Color { r: 0.2, g: 0.4, b: 0.9, a: 1.0 };
+const ORANGE: Color = Color { r: 0.95, g: 0.55, b: 0.1, a: 1.0 };
+
+#[test]
+fn backgrounds_and_radii() {
+    let mut view = row()
+        .child(View::new().with_style(sized(50.0, 50.0).set_background(BLUE)))
+        .child(View::new().with_style(sized(50.0, 50.0).set_background(ORANGE).set_border_radius(12.0)))
+        .child(View::new().with_style(sized(50.0, 50.0).set_background(Color::BLACK).set_border_radius(25.0)))
+        .child(View::new().with_style(sized(50.0, 50.0).set_background(Color::rgba(0.2, 0.4, 0.9, 0.4))));
+    golden(250.0, 70.0).assert_matches("backgrounds_and_radii", &mut view);
+}
+
+#[test]
+fn text() {
+    let body = Style::default().set_font_size(14.0).set_color(Color::BLACK);
+    let mut view = View::new()
+        .with_layout(Layout::Column)
+        .with_style(Style::default().set_padding(EdgeInsets::all(10.0)).set_gap(6.0))
+        .child(Text::new("Hello, golden world").with_style(body.clone().set_font_size(20.0)))
+        .child(Text::new("A paragraph that wraps onto several lines inside its box.").with_style(
+            body.clone().set_text_wrap(TextWrap::Word).set_width(Dimension::Points(180.0)),
+        ))
+        .child(Text::new("Centered").with_style(body.clone().set_text_align(TextAlign::Center)))
+        .child(Text::new("This line is far too long to fit").with_style(
+            body.clone().set_text_overflow(TextOverflow::EllipsisEnd).set_width(Dimension::Points(150.0)),
+        ))
+        .child(Text::new("שלום עולם").with_style(body.clone().set_direction(LayoutDirection::Rtl)));
+    golden(220.0, 180.0).assert_matches("text", &mut view);
+}
+
+#[test]
+fn rich_text() {
+    let mut view = View::new()
+        .with_style(Style::default().set_padding(EdgeInsets::all(10.0)))
+        .child(
+            RichText::new(vec![
+                Span::new("Plain, "),
+                Span::new("bold, ").with_style(SpanStyle::default().bold()),
+                Span::new("large ").with_style(SpanStyle::default().set_font_size(22.0)),
+                Span::new("highlighted ").with_style(SpanStyle::default().set_background(ORANGE)),
+                Span::link("and a link", "https://example.com"),
+            ])
+            .with_style(Style::default().set_font_size(14.0).set_color(Color::BLACK).set_text_wrap(TextWrap::Word)),
+        );
+    golden(220.0, 90.0).assert_matches("rich_text", &mut view);
+}
+
+#[test]
+fn borders() {
+    let side = |style| BorderSide::new(4.0, BLUE, style);
+    let per_side = Border {
+        top: BorderSide::solid(2.0, Color::BLACK),
+        right: BorderSide::solid(6.0, ORANGE),
+        bottom: BorderSide::solid(10.0, BLUE),
+        left: BorderSide::solid(4.0, Color::RED),
+    };
+    let mut view = row()
+        .child(View::new().with_style(sized(50.0, 50.0).set_border(Border::all(side(BorderStyle::Solid)))))
+        .child(View::new().with_style(
+            sized(50.0, 50.0).set_border(Border::all(side(BorderStyle::Solid))).set_border_radius(14.0),
+        ))
+        .child(View::new().with_style(sized(50.0, 50.0).set_border(per_side)))
+        .child(View::new().with_style(sized(50.0, 50.0).set_border(Border::all(side(BorderStyle::Dashed)))))
+        .child(View::new().with_style(
+            sized(50.0, 50.0).set_border(Border::all(side(BorderStyle::Dotted))).set_border_radius(25.0),
+        ));
+    golden(310.0, 70.0).assert_matches("borders", &mut view);
+}
+
+#[test]
+fn gradients_shadows_and_opacity() {
+    let card = sized(60.0, 60.0).set_border_radius(8.0);
+    let mut view = row()
+        .with_style(Style::default().set_padding(EdgeInsets::all(20.0)).set_gap(24.0))
+        .child(View::new().with_style(card.clone().set_background_gradient(Gradient::linear(90.0, stops(BLUE, ORANGE)))))
+        .child(View::new().with_style(card.clone().set_background_gradient(Gradient::radial(stops(Color::WHITE, BLUE)))))
+        .child(View::new().with_style(
+            card.clone()
+                .set_background(Color::WHITE)
+                .set_box_shadows([BoxShadow::new(0.0, 4.0, 8.0, Color::rgba(0.0, 0.0, 0.0, 0.5))]),
+        ))
+        .child(View::new().with_style(
+            card.clone()
+                .set_background(Color::WHITE)
+                .set_box_shadows([BoxShadow::new(2.0, 2.0, 6.0, Color::rgba(0.0, 0.0, 0.0, 0.6)).set_inset(true)]),
+        ))
+        .child(
+            View::new()
+                .with_style(card.clone().set_background(BLUE).set_opacity(0.5))
+                .child(View::new().with_style(sized(30.0, 30.0).set_background(ORANGE))),
+        );
+    golden(420.0, 100.0).assert_matches("gradients_shadows_and_opacity", &mut view);
+}
+
+// A 4x4 checkerboard of opaque black and translucent white.
+fn checkerboard() -> Arc<Texture> {
+    let mut pixels = Vec::new();
+    for y in 0..4 {
+        for x in 0..4 {
+            let pixel = if (x + y) % 2 == 0 { [0, 0, 0, 255] } else { [255, 255, 255, 128] };
+            pixels.extend_from_slice(&pixel);
+        }
+    }
+    Arc::new(Texture::from_rgba(4, 4, pixels).expect("pixels match the size"))
+}
+
+#[test]
+fn images() {
+    let texture = checkerboard();
+    let image = |fit| Image::from_texture(texture.clone()).with_fit(fit);
+    let mut view = row()
+        .child(image(ContentFit::Fill).with_style(sized(60.0, 40.0)))
+        .child(image(ContentFit::Contain).with_style(sized(60.0, 40.0).set_background(Color::rgb(0.9, 0.9, 0.9))))
+        .child(image(ContentFit::Cover).with_style(sized(60.0, 40.0).set_border_radius(10.0)))
+        .child(image(ContentFit::Fill).tint(BLUE).with_style(sized(40.0, 40.0)));
+    golden(290.0, 60.0).assert_matches("images", &mut view);
+}
+
+#[test]
+fn canvas_paths() {
+    let mut canvas = Canvas::new().with_size(Size::new(200.0, 100.0)).on_draw(|context| {
+        let mut triangle = Path::new();
+        triangle.move_to(10.0, 90.0).line_to(50.0, 10.0).line_to(90.0, 90.0).close();
+        context.fill(&triangle, Brush::linear_gradient(Point::new(10.0, 0.0), Point::new(90.0, 0.0), stops(BLUE, ORANGE)));
+
+        let mut curve = Path::new();
+        curve.move_to(100.0, 80.0).cubic_to(120.0, 0.0, 160.0, 100.0, 190.0, 20.0);
+        let stroke = StrokeStyle::new(6.0).set_cap(LineCap::Round).set_join(LineJoin::Round);
+        context.stroke(&curve, Color::BLACK, &stroke);
+
+        let mut circle = Path::new();
+        circle.circle(150.0, 75.0, 15.0);
+        context.stroke(&circle, Color::RED, &StrokeStyle::new(2.0).set_dash(vec![6.0, 4.0], 0.0));
+
+        context.fill_rect(&Rect::new(120.0, 10.0, 20.0, 20.0), Color::rgba(0.2, 0.4, 0.9, 0.5));
+    });
+    golden(200.0, 100.0).assert_matches("canvas_paths", &mut canvas);
+}
+
+const ICON: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24">
+  <circle cx="12" cy="12" r="10" fill="#3366e6"/>
+  <path d="M7 12l3 3 7-7" fill="none" stroke="#ffffff" stroke-width="2.5" stroke-linecap="round" stroke-linejoin="round"/>
+</svg>"##;
+
+#[test]
+fn svg() {
+    let svg = |size: f32| Svg::from_bytes(ICON.as_bytes().to_vec()).with_style(sized(size, size));
+    let mut view = row()
+        .child(svg(24.0))
+        .child(svg(48.0))
+        .child(Svg::from_bytes(ICON.as_bytes().to_vec()).with_fit(ContentFit::Contain).with_style(
+            sized(80.0, 48.0).set_background(Color::rgb(0.9, 0.9, 0.9)),
+        ));
+    golden(200.0, 70.0).assert_matches("svg", &mut view);
+}
+
+#[test]
+fn weights_and_scale() {
+    let mut view = View::new()
+        .with_style(Style::default().set_padding(EdgeInsets::all(6.0)).set_background(BLUE).set_border_radius(6.0))
+        .child(Text::new("Bold @2x").with_style(
+            Style::default().set_font_size(16.0).set_font_weight(FontWeight::BOLD).set_color(Color::WHITE),
+        ));
+    let references = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden");
+    Golden::new(references, Snapshot::new(Size::new(100.0, 40.0)).with_scale_factor(2.0))
+        .with_tolerance(2)
+        .assert_matches("weights_and_scale", &mut view);
+}